@@ -0,0 +1,41 @@
+use std::collections::HashMap;
+
+use bc_envelope::prelude::*;
+
+use crate::Path;
+
+/// Replaces every captured path with a path holding only its tip, the
+/// envelope that was actually captured.
+///
+/// Empty paths are left empty.
+pub fn coerce_captures_to_leaf_paths(
+    captures: HashMap<String, Vec<Path>>,
+) -> HashMap<String, Vec<Path>> {
+    coerce_captures(captures, |path| path.last())
+}
+
+/// Replaces every captured path with a path holding only its root, the
+/// envelope the capture started from.
+///
+/// Empty paths are left empty.
+pub fn coerce_to_root_paths(
+    captures: HashMap<String, Vec<Path>>,
+) -> HashMap<String, Vec<Path>> {
+    coerce_captures(captures, |path| path.first())
+}
+
+fn coerce_captures(
+    captures: HashMap<String, Vec<Path>>,
+    keep: fn(&Path) -> Option<&Envelope>,
+) -> HashMap<String, Vec<Path>> {
+    captures
+        .into_iter()
+        .map(|(name, paths)| {
+            let paths = paths
+                .iter()
+                .map(|path| keep(path).cloned().into_iter().collect())
+                .collect();
+            (name, paths)
+        })
+        .collect()
+}
@@ -1,8 +1,10 @@
-use logos::Span;
 use thiserror::Error;
 
 use crate::parse::Token;
 
+/// A byte range in the pattern source.
+type Span = std::ops::Range<usize>;
+
 /// Errors that can occur during parsing of Envelope patterns.
 #[derive(Debug, Clone, Error, PartialEq, Default)]
 pub enum Error {
@@ -39,6 +41,15 @@ pub enum Error {
     #[error("Invalid number format at {0:?}")]
     InvalidNumberFormat(Span),
 
+    #[error("Invalid divisor at {0:?}")]
+    InvalidDivisor(Span),
+
+    #[error("Invalid divisor: {0}")]
+    InvalidDivisorValue(f64),
+
+    #[error("Empty length range")]
+    EmptyLengthRange,
+
     #[error("Invalid UR: {0} at {1:?}")]
     InvalidUr(String, Span),
 
@@ -69,6 +80,30 @@ pub enum Error {
     #[error("Invalid pattern at {0:?}")]
     InvalidPattern(Span),
 
+    #[error("Unknown known value name: {0}")]
+    UnknownKnownValueName(String),
+
+    #[error("Unknown envelope case: {0}")]
+    UnknownEnvelopeCase(String),
+
+    #[error("Invalid JSON Schema: {0}")]
+    InvalidJsonSchema(String),
+
+    #[error("Invalid pattern CBOR: {0}")]
+    InvalidPatternCBOR(String),
+
+    #[error("Pattern cannot be encoded as CBOR: {0}")]
+    UnencodablePattern(String),
+
+    #[error("Invalid path specification: {0}")]
+    InvalidPathSpec(String),
+
+    #[error("Invalid dCBOR diagnostic notation: {0}")]
+    InvalidDCBORDiagnostic(String),
+
+    #[error("Alternatives may overlap: {0} and {1}")]
+    OverlappingAlternatives(String, String),
+
     #[error("Unknown error")]
     #[default]
     Unknown,
@@ -0,0 +1,129 @@
+use std::collections::HashSet;
+
+use bc_envelope::prelude::*;
+
+use crate::{Matcher, Path, Pattern};
+
+/// Extracts a typed value from the subject of the last element of each path.
+///
+/// Paths whose tip does not hold a value of type `T` are skipped.
+fn try_extract<T>(paths: &[Path]) -> Vec<T>
+where
+    T: TryFrom<CBOR, Error = dcbor::Error> + 'static,
+{
+    paths
+        .iter()
+        .filter_map(|path| path.last())
+        .filter_map(|tip| tip.extract_subject::<T>().ok())
+        .collect()
+}
+
+/// Extracts the text values at the tips of the given paths.
+///
+/// Paths whose tip is not a text leaf are skipped.
+pub fn try_extract_text(paths: &[Path]) -> Vec<String> { try_extract(paths) }
+
+/// Extracts the numeric values at the tips of the given paths.
+///
+/// Paths whose tip is not a numeric leaf are skipped.
+pub fn try_extract_number(paths: &[Path]) -> Vec<f64> { try_extract(paths) }
+
+/// Extracts the date values at the tips of the given paths.
+///
+/// Paths whose tip is not a date leaf are skipped.
+pub fn try_extract_date(paths: &[Path]) -> Vec<Date> { try_extract(paths) }
+
+/// Extracts the boolean values at the tips of the given paths.
+///
+/// Paths whose tip is not a boolean leaf are skipped.
+pub fn try_extract_bool(paths: &[Path]) -> Vec<bool> { try_extract(paths) }
+
+impl Pattern {
+    /// Returns the subject of the tip of the first path matched in
+    /// `haystack`, extracted as `T`.
+    ///
+    /// Returns `None` if the pattern does not match or the first match does
+    /// not hold a value of type `T`.
+    pub fn extract_first<T>(&self, haystack: &Envelope) -> Option<T>
+    where
+        T: TryFrom<CBOR, Error = dcbor::Error> + 'static,
+    {
+        let path = self.paths(haystack).into_iter().next()?;
+        path.last()?.extract_subject::<T>().ok()
+    }
+
+    /// Returns the subjects of the tips of every path matched in `haystack`,
+    /// extracted as `T`. Matches not holding a value of type `T` are
+    /// skipped.
+    pub fn extract_all<T>(&self, haystack: &Envelope) -> Vec<T>
+    where
+        T: TryFrom<CBOR, Error = dcbor::Error> + 'static,
+    {
+        try_extract(&self.paths(haystack))
+    }
+}
+
+/// Returns every text value anywhere in `haystack`, in search order.
+///
+/// Shorthand for `Pattern::search(Pattern::any_text()).extract_all(haystack)`.
+pub fn search_all_text_values(haystack: &Envelope) -> Vec<String> {
+    Pattern::search(Pattern::any_text()).extract_all(haystack)
+}
+
+/// Returns every numeric value anywhere in `haystack`, in search order.
+pub fn search_all_numbers(haystack: &Envelope) -> Vec<f64> {
+    Pattern::search(Pattern::any_number()).extract_all(haystack)
+}
+
+/// Returns every date value anywhere in `haystack`, in search order.
+pub fn search_all_dates(haystack: &Envelope) -> Vec<Date> {
+    Pattern::search(Pattern::any_date()).extract_all(haystack)
+}
+
+/// Returns every known value anywhere in `haystack`, in search order.
+pub fn search_all_known_values(haystack: &Envelope) -> Vec<KnownValue> {
+    Pattern::search(Pattern::any_known_value())
+        .paths(haystack)
+        .iter()
+        .filter_map(|path| path.last())
+        .filter_map(|tip| tip.subject().as_known_value().cloned())
+        .collect()
+}
+
+/// Counts the distinct assertions at the tips of the given paths.
+///
+/// Tips that are not assertions are ignored, and an assertion reached by
+/// several paths is counted once.
+pub fn count_distinct_assertion_paths(paths: &[Path]) -> usize {
+    paths
+        .iter()
+        .filter_map(|path| path.last())
+        .filter(|tip| tip.is_assertion())
+        .map(|tip| tip.digest())
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+/// Counts the distinct subjects of the tips of the given paths.
+pub fn count_distinct_subjects_in_paths(paths: &[Path]) -> usize {
+    paths
+        .iter()
+        .filter_map(|path| path.last())
+        .map(|tip| tip.subject().digest())
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+/// Counts the distinct objects of the assertions at the tips of the given
+/// paths.
+///
+/// Tips that are not assertions are ignored.
+pub fn count_distinct_objects_in_paths(paths: &[Path]) -> usize {
+    paths
+        .iter()
+        .filter_map(|path| path.last())
+        .filter_map(|tip| tip.as_object())
+        .map(|object| object.digest())
+        .collect::<HashSet<_>>()
+        .len()
+}
@@ -1,12 +1,15 @@
 use bc_envelope::prelude::*;
 
-use crate::Path;
+use crate::{Path, compare_paths};
 
 /// A builder that provides formatting options for each path element.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PathElementFormat {
     /// Summary format, with optional maximum length for truncation.
     Summary(Option<usize>),
+    /// The last element as a multi-line tree, as rendered by
+    /// `Envelope::tree_format()`, with the elements before it as summaries.
+    TreeFormat,
     EnvelopeUR,
     DigestUR,
 }
@@ -30,6 +33,14 @@ pub struct FormatPathsOpts {
     /// This is useful for displaying only the final destination of a path.
     /// If false, all elements will be formatted.
     last_element_only: bool,
+
+    /// If true, paths are sorted with `compare_paths` before formatting,
+    /// giving output that does not depend on match order.
+    sorted: bool,
+
+    /// Number of hex characters of each element's digest shown in summaries.
+    /// If None, the short 8-character digest is used.
+    summary_prefix_length: Option<usize>,
 }
 
 impl Default for FormatPathsOpts {
@@ -37,11 +48,15 @@ impl Default for FormatPathsOpts {
     /// - `indent`: true
     /// - `element_format`: PathElementFormat::Summary(None)
     /// - `last_element_only`: false
+    /// - `sorted`: false
+    /// - `summary_prefix_length`: None
     fn default() -> Self {
         Self {
             indent: true,
             element_format: PathElementFormat::default(),
             last_element_only: false,
+            sorted: false,
+            summary_prefix_length: None,
         }
     }
 }
@@ -71,14 +86,39 @@ impl FormatPathsOpts {
         self.last_element_only = last_element_only;
         self
     }
+
+    /// Sets whether to sort paths by their digests before formatting.
+    /// If false, paths are formatted in the order given.
+    pub fn sorted(mut self, sorted: bool) -> Self {
+        self.sorted = sorted;
+        self
+    }
+
+    /// Sets the number of hex characters of each element's digest shown in
+    /// summaries, up to the full 64. Default is 8.
+    pub fn summary_prefix_length(mut self, length: usize) -> Self {
+        self.summary_prefix_length = Some(length);
+        self
+    }
 }
 
 impl AsRef<FormatPathsOpts> for FormatPathsOpts {
     fn as_ref(&self) -> &FormatPathsOpts { self }
 }
 
-pub fn envelope_summary(env: &Envelope) -> String {
-    let id = env.short_id(DigestDisplayFormat::Short);
+/// Returns the summary of an envelope, showing `prefix_length` hex characters
+/// of its digest, or the short digest if None.
+pub fn envelope_summary(
+    env: &Envelope,
+    prefix_length: Option<usize>,
+) -> String {
+    let id = match prefix_length {
+        Some(length) => {
+            let hex = env.digest().hex();
+            hex[..length.min(hex.len())].to_string()
+        }
+        None => env.short_id(DigestDisplayFormat::Short),
+    };
     let summary = match env.case() {
         EnvelopeCase::Node { .. } => {
             format!("NODE {}", env.format_flat())
@@ -113,13 +153,15 @@ pub fn envelope_summary(env: &Envelope) -> String {
     format!("{} {}", id, summary)
 }
 
-/// Truncates a string to the specified maximum length, appending an ellipsis if
-/// truncated. If `max_length` is None, returns the original string.
+/// Truncates a string to the specified maximum length in characters,
+/// appending an ellipsis if truncated. If `max_length` is None, returns the
+/// original string.
 fn truncate_with_ellipsis(s: &str, max_length: Option<usize>) -> String {
     match max_length {
-        Some(max_len) if s.len() > max_len => {
+        Some(max_len) if s.chars().count() > max_len => {
             if max_len > 1 {
-                format!("{}…", &s[0..(max_len - 1)])
+                let kept: String = s.chars().take(max_len - 1).collect();
+                format!("{}…", kept)
             } else {
                 "…".to_string()
             }
@@ -128,6 +170,25 @@ fn truncate_with_ellipsis(s: &str, max_length: Option<usize>) -> String {
     }
 }
 
+/// Truncates an envelope summary to the specified maximum length, keeping the
+/// leading digest prefix intact and truncating only the content after it.
+fn truncate_summary(summary: &str, max_length: Option<usize>) -> String {
+    match (max_length, summary.split_once(' ')) {
+        (Some(max_len), Some((id, content)))
+            if summary.chars().count() > max_len =>
+        {
+            let prefix_len = id.chars().count() + 1;
+            let content_len = max_len.saturating_sub(prefix_len).max(1);
+            format!(
+                "{} {}",
+                id,
+                truncate_with_ellipsis(content, Some(content_len))
+            )
+        }
+        _ => truncate_with_ellipsis(summary, max_length),
+    }
+}
+
 /// Format each path element on its own line, each line successively indented by
 /// 4 spaces. Options can be provided to customize the formatting.
 pub fn format_path_opt(
@@ -141,9 +202,11 @@ pub fn format_path_opt(
         if let Some(element) = path.iter().last() {
             match opts.element_format {
                 PathElementFormat::Summary(max_length) => {
-                    let summary = envelope_summary(element);
-                    truncate_with_ellipsis(&summary, max_length)
+                    let summary =
+                        envelope_summary(element, opts.summary_prefix_length);
+                    truncate_summary(&summary, max_length)
                 }
+                PathElementFormat::TreeFormat => element.tree_format(),
                 PathElementFormat::EnvelopeUR => element.ur_string(),
                 PathElementFormat::DigestUR => element.digest().ur_string(),
             }
@@ -162,13 +225,39 @@ pub fn format_path_opt(
                         String::new()
                     };
 
-                    let summary = envelope_summary(element);
-                    let content = truncate_with_ellipsis(&summary, max_length);
+                    let summary =
+                        envelope_summary(element, opts.summary_prefix_length);
+                    let content = truncate_summary(&summary, max_length);
 
                     lines.push(format!("{}{}", indent, content));
                 }
                 lines.join("\n")
             }
+            PathElementFormat::TreeFormat => {
+                // Summaries as above, except that the tree of the last
+                // element is indented at its level.
+                let mut lines = Vec::new();
+                for (index, element) in path.iter().enumerate() {
+                    let indent = if opts.indent {
+                        " ".repeat(index * 4)
+                    } else {
+                        String::new()
+                    };
+
+                    if index + 1 == path.len() {
+                        for line in element.tree_format().lines() {
+                            lines.push(format!("{}{}", indent, line));
+                        }
+                    } else {
+                        let summary = envelope_summary(
+                            element,
+                            opts.summary_prefix_length,
+                        );
+                        lines.push(format!("{}{}", indent, summary));
+                    }
+                }
+                lines.join("\n")
+            }
             PathElementFormat::EnvelopeUR => {
                 // Single-line, space-separated envelope URs.
                 path.iter()
@@ -210,6 +299,14 @@ pub fn format_paths_with_captures_opt(
 ) -> String {
     let opts = opts.as_ref();
     let mut result = Vec::new();
+    let ordered = |paths: &[Path]| {
+        let mut paths = paths.to_vec();
+        if opts.sorted {
+            paths.sort_by(compare_paths);
+        }
+        paths
+    };
+    let paths = &ordered(paths);
 
     // First, format all captures, sorted lexicographically by name
     let mut capture_names: Vec<&String> = captures.keys().collect();
@@ -218,7 +315,7 @@ pub fn format_paths_with_captures_opt(
     for capture_name in capture_names {
         if let Some(capture_paths) = captures.get(capture_name) {
             result.push(format!("@{}", capture_name));
-            for path in capture_paths {
+            for path in &ordered(capture_paths) {
                 let formatted_path = format_path_opt(path, opts);
                 // Add indentation to each line of the formatted path
                 for line in formatted_path.split('\n') {
@@ -245,8 +342,8 @@ pub fn format_paths_with_captures_opt(
                 }
             }
         }
-        PathElementFormat::Summary(_) => {
-            // For summary format, format each path separately
+        PathElementFormat::Summary(_) | PathElementFormat::TreeFormat => {
+            // For summary and tree formats, format each path separately
             for path in paths {
                 let formatted_path = format_path_opt(path, opts);
                 for line in formatted_path.split('\n') {
@@ -279,6 +376,26 @@ pub fn format_paths(paths: &[Path]) -> String {
     format_paths_opt(paths, FormatPathsOpts::default())
 }
 
+/// Returns the UR string of the given envelope.
+pub fn envelope_to_ur_string(envelope: &Envelope) -> String {
+    envelope.ur_string()
+}
+
+/// Returns the UR string of the given envelope's digest.
+pub fn digest_to_ur_string(envelope: &Envelope) -> String {
+    envelope.digest().ur_string()
+}
+
+/// Returns one envelope UR string per path, for the last element of each
+/// path. Empty paths are skipped.
+pub fn format_paths_as_ur_list(paths: &[Path]) -> Vec<String> {
+    paths
+        .iter()
+        .filter_map(|path| path.last())
+        .map(envelope_to_ur_string)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -505,11 +622,173 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_envelope_and_digest_to_ur_string() {
+        bc_components::register_tags();
+
+        let envelope = Envelope::new(42);
+        let ur = envelope_to_ur_string(&envelope);
+        assert!(ur.starts_with("ur:envelope/"));
+        assert_eq!(Envelope::from_ur_string(&ur).unwrap(), envelope);
+
+        let digest_ur = digest_to_ur_string(&envelope);
+        assert!(digest_ur.starts_with("ur:digest/"));
+        assert_eq!(
+            bc_components::Digest::from_ur_string(&digest_ur).unwrap(),
+            envelope.digest()
+        );
+    }
+
+    #[test]
+    fn test_format_paths_as_ur_list() {
+        bc_components::register_tags();
+
+        let path1 = vec![Envelope::new(1), Envelope::new(2)];
+        let path2 = vec![Envelope::new(3)];
+        let paths = vec![path1, vec![], path2];
+
+        let urs = format_paths_as_ur_list(&paths);
+        assert_eq!(urs.len(), 2);
+        assert_eq!(urs[0], Envelope::new(2).ur_string());
+        assert_eq!(urs[1], Envelope::new(3).ur_string());
+    }
+
+    #[test]
+    fn test_sorted_paths() {
+        let a = vec![Envelope::new(1)];
+        let b = vec![Envelope::new(2)];
+        let c = vec![Envelope::new(1), Envelope::new(3)];
+        let mut expected = vec![a.clone(), b.clone(), c.clone()];
+        expected.sort_by(compare_paths);
+
+        // Sorting is independent of the input order.
+        let opts = FormatPathsOpts::default().sorted(true);
+        let orders = [
+            vec![a.clone(), b.clone(), c.clone()],
+            vec![c.clone(), b.clone(), a.clone()],
+            vec![b.clone(), c.clone(), a.clone()],
+        ];
+        for paths in orders {
+            assert_eq!(
+                format_paths_opt(&paths, &opts),
+                format_paths(&expected)
+            );
+        }
+
+        // A path sorts after its own prefix.
+        assert_eq!(compare_paths(&a, &c), std::cmp::Ordering::Less);
+        assert_eq!(compare_paths(&a, &a), std::cmp::Ordering::Equal);
+
+        // Unsorted formatting preserves the given order.
+        let paths = vec![c.clone(), a.clone()];
+        assert_eq!(
+            format_paths(&paths),
+            format!("{}\n{}", format_path(&c), format_path(&a))
+        );
+    }
+
+    #[test]
+    fn test_summary_prefix_length() {
+        let path = create_test_path();
+
+        let opts = FormatPathsOpts::default().summary_prefix_length(4);
+        #[rustfmt::skip]
+        let expected = indoc! {r#"
+            7f83 LEAF 42
+                6fe3 LEAF "test"
+                    4abc LEAF [1, 2, 3]
+        "#}.trim();
+        assert_eq!(format_path_opt(&path, &opts), expected);
+
+        // Eight characters matches the default short digest.
+        let opts = FormatPathsOpts::default().summary_prefix_length(8);
+        assert_eq!(format_path_opt(&path, &opts), format_path(&path));
+
+        let opts = FormatPathsOpts::default()
+            .summary_prefix_length(16)
+            .last_element_only(true);
+        assert_eq!(
+            format_path_opt(&path, &opts),
+            "4abc31137ce738d9 LEAF [1, 2, 3]"
+        );
+
+        // Lengths beyond the full digest show the whole digest.
+        let opts = FormatPathsOpts::default()
+            .summary_prefix_length(100)
+            .last_element_only(true);
+        assert_eq!(
+            format_path_opt(&path, &opts),
+            format!("{} LEAF [1, 2, 3]", path[2].digest().hex())
+        );
+    }
+
     #[test]
     fn test_truncate_with_ellipsis() {
         assert_eq!(truncate_with_ellipsis("hello", None), "hello");
         assert_eq!(truncate_with_ellipsis("hello", Some(10)), "hello");
         assert_eq!(truncate_with_ellipsis("hello world", Some(5)), "hell…");
         assert_eq!(truncate_with_ellipsis("hello", Some(1)), "…");
+        assert_eq!(truncate_with_ellipsis("héllo wörld", Some(5)), "héll…");
+    }
+
+    #[test]
+    fn test_summary_truncation_keeps_digest() {
+        let envelope = Envelope::new("A fairly long text value for truncation");
+        let path = vec![envelope.clone()];
+        let digest = envelope.short_id(DigestDisplayFormat::Short);
+
+        let opts = FormatPathsOpts::default()
+            .element_format(PathElementFormat::Summary(Some(20)));
+        let line = format_path_opt(&path, &opts);
+        assert_eq!(line.chars().count(), 20);
+        assert!(line.starts_with(&format!("{} ", digest)));
+        assert!(line.ends_with('…'));
+        assert_eq!(line, format!("{} LEAF \"A fa…", digest));
+
+        // Even a tiny maximum never truncates the digest.
+        let opts = FormatPathsOpts::default()
+            .element_format(PathElementFormat::Summary(Some(4)));
+        let line = format_path_opt(&path, &opts);
+        assert_eq!(line, format!("{} …", digest));
+
+        // Summaries that fit are unchanged.
+        let opts = FormatPathsOpts::default()
+            .element_format(PathElementFormat::Summary(Some(200)));
+        assert_eq!(format_path_opt(&path, &opts), format_path(&path));
+    }
+
+    #[test]
+    fn test_tree_format() {
+        let inner = Envelope::new("Bob").add_assertion("age", 30);
+        let outer =
+            Envelope::new("Alice").add_assertion("knows", inner.clone());
+        let assertion = outer.assertions()[0].clone();
+        let path = vec![outer.clone(), assertion.clone(), inner.clone()];
+        let opts = FormatPathsOpts::default()
+            .element_format(PathElementFormat::TreeFormat);
+
+        // The tip is rendered exactly as `tree_format()` renders it.
+        let last_only = opts.clone().last_element_only(true);
+        assert_eq!(format_path_opt(&path, &last_only), inner.tree_format());
+
+        // Earlier elements are summaries, and the tree keeps the tip's
+        // indentation.
+        #[rustfmt::skip]
+        let expected = indoc! {r#"
+            77ab7d85 NODE "Alice" [ "knows": "Bob" [ "age": 30 ] ]
+                09f5edea ASSERTION "knows": "Bob" [ "age": 30 ]
+                    5c45bf53 NODE
+                        13b74194 subj "Bob"
+                        0eb5609b ASSERTION
+                            5943be12 pred "age"
+                            cf972730 obj 30
+        "#}.trim();
+        assert_eq!(format_path_opt(&path, &opts), expected);
+
+        // A single-element path is just the tree.
+        assert_eq!(
+            format_paths_opt(&[vec![inner.clone()]], &opts),
+            inner.tree_format()
+        );
     }
 }
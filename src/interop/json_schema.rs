@@ -0,0 +1,210 @@
+use serde_json::{Map, Value};
+
+use crate::{Error, Pattern, Result};
+
+impl Pattern {
+    /// Builds a pattern from a JSON Schema, so that data already described
+    /// by a schema can be matched when it is carried in an envelope.
+    ///
+    /// A JSON object corresponds to a node whose assertions have text
+    /// predicates naming its properties. The supported keywords are:
+    ///
+    /// - `type`: `"string"`, `"number"`, `"integer"`, `"boolean"`, `"null"`,
+    ///   `"array"`, or `"object"`, or an array of these. An `"integer"` matches
+    ///   any number.
+    /// - `enum` and `const`, with string, number, boolean, or null values.
+    /// - `minimum`, `maximum`, `exclusiveMinimum`, and `exclusiveMaximum`.
+    /// - `properties`: each assertion with a property's name as its predicate
+    ///   must have an object matching that property's schema.
+    /// - `required`: each named property must have an assertion.
+    /// - `additionalProperties: false`: every predicate must be a named
+    ///   property.
+    ///
+    /// Other keywords, such as `items` or `minLength`, are ignored, so the
+    /// pattern may accept values the schema would reject. A schema with
+    /// `properties` or `required` only matches nodes, so it does not match
+    /// an empty object, which is a leaf.
+    ///
+    /// Returns an error if `schema` is not valid JSON, or uses a supported
+    /// keyword with a value of the wrong shape.
+    pub fn from_json_schema(schema: &str) -> Result<Pattern> {
+        let schema: Value = serde_json::from_str(schema)
+            .map_err(|e| Error::InvalidJsonSchema(e.to_string()))?;
+        schema_to_pattern(&schema)
+    }
+}
+
+fn invalid(message: impl Into<String>) -> Error {
+    Error::InvalidJsonSchema(message.into())
+}
+
+fn schema_to_pattern(schema: &Value) -> Result<Pattern> {
+    let object = match schema {
+        Value::Bool(true) => return Ok(Pattern::any()),
+        Value::Bool(false) => return Ok(Pattern::not_matching(Pattern::any())),
+        Value::Object(object) => object,
+        _ => return Err(invalid("a schema must be an object or a boolean")),
+    };
+
+    let object_constraint = object_pattern(object)?;
+    let mut constraints = Vec::new();
+    match object.get("type") {
+        // The object constraint already requires a node.
+        Some(Value::String(name))
+            if name == "object" && object_constraint.is_some() => {}
+        Some(types) => constraints.push(type_pattern(types)?),
+        None => {}
+    }
+    if let Some(values) = object.get("enum") {
+        let values = values
+            .as_array()
+            .ok_or_else(|| invalid("`enum` must be an array"))?;
+        let patterns = values
+            .iter()
+            .map(value_pattern)
+            .collect::<Result<Vec<_>>>()?;
+        constraints.push(any_of(patterns));
+    }
+    if let Some(value) = object.get("const") {
+        constraints.push(value_pattern(value)?);
+    }
+    constraints.extend(number_bounds(object)?);
+    constraints.extend(object_constraint);
+    Ok(all_of(constraints))
+}
+
+fn type_pattern(types: &Value) -> Result<Pattern> {
+    match types {
+        Value::String(name) => match name.as_str() {
+            "string" => Ok(Pattern::any_text()),
+            "number" | "integer" => Ok(Pattern::any_number()),
+            "boolean" => Ok(Pattern::any_bool()),
+            "null" => Ok(Pattern::null()),
+            "array" => Ok(Pattern::any_array()),
+            "object" => Ok(Pattern::any_node()),
+            _ => Err(invalid(format!("unknown type {:?}", name))),
+        },
+        Value::Array(names) => Ok(any_of(
+            names.iter().map(type_pattern).collect::<Result<Vec<_>>>()?,
+        )),
+        _ => Err(invalid("`type` must be a string or an array of strings")),
+    }
+}
+
+fn value_pattern(value: &Value) -> Result<Pattern> {
+    match value {
+        Value::String(text) => Ok(Pattern::text(text)),
+        Value::Number(number) => number
+            .as_f64()
+            .map(Pattern::number)
+            .ok_or_else(|| invalid(format!("unsupported number {}", number))),
+        Value::Bool(value) => Ok(Pattern::bool(*value)),
+        Value::Null => Ok(Pattern::null()),
+        _ => Err(invalid(
+            "only scalar `enum` and `const` values are supported",
+        )),
+    }
+}
+
+fn number_bounds(object: &Map<String, Value>) -> Result<Vec<Pattern>> {
+    let bound = |keyword: &str| -> Result<Option<f64>> {
+        object
+            .get(keyword)
+            .map(|value| {
+                value.as_f64().ok_or_else(|| {
+                    invalid(format!("`{}` must be a number", keyword))
+                })
+            })
+            .transpose()
+    };
+    let mut patterns = Vec::new();
+    match (bound("minimum")?, bound("maximum")?) {
+        (Some(min), Some(max)) => {
+            patterns.push(Pattern::number_range(min..=max))
+        }
+        (Some(min), None) => {
+            patterns.push(Pattern::number_greater_than_or_equal(min))
+        }
+        (None, Some(max)) => {
+            patterns.push(Pattern::number_less_than_or_equal(max))
+        }
+        (None, None) => {}
+    }
+    if let Some(min) = bound("exclusiveMinimum")? {
+        patterns.push(Pattern::number_greater_than(min));
+    }
+    if let Some(max) = bound("exclusiveMaximum")? {
+        patterns.push(Pattern::number_less_than(max));
+    }
+    Ok(patterns)
+}
+
+/// Returns the pattern for the `properties`, `required`, and
+/// `additionalProperties` keywords, or `None` if none are present.
+fn object_pattern(object: &Map<String, Value>) -> Result<Option<Pattern>> {
+    let properties = match object.get("properties") {
+        Some(Value::Object(properties)) => Some(properties),
+        Some(_) => return Err(invalid("`properties` must be an object")),
+        None => None,
+    };
+    let required = match object.get("required") {
+        Some(Value::Array(names)) => names
+            .iter()
+            .map(|name| {
+                name.as_str()
+                    .ok_or_else(|| invalid("`required` must contain strings"))
+            })
+            .collect::<Result<Vec<_>>>()?,
+        Some(_) => return Err(invalid("`required` must be an array")),
+        None => Vec::new(),
+    };
+    let closed = match object.get("additionalProperties") {
+        Some(Value::Bool(false)) => true,
+        // Schemas for additional properties aren't supported, so any
+        // additional property is allowed.
+        Some(_) | None => false,
+    };
+    if properties.is_none() && required.is_empty() && !closed {
+        return Ok(None);
+    }
+
+    let mut constraints = Vec::new();
+    for name in &required {
+        constraints
+            .push(Pattern::assertion_with_predicate(Pattern::text(*name)));
+    }
+    for (name, schema) in properties.into_iter().flatten() {
+        // No assertion with this predicate may have a non-matching object.
+        constraints.push(Pattern::not_matching(
+            Pattern::assertion_with_predicate_and_object(
+                Pattern::text(name),
+                Pattern::not_matching(schema_to_pattern(schema)?),
+            ),
+        ));
+    }
+    if closed {
+        let names = properties.into_iter().flatten().map(|(name, _)| name);
+        constraints.push(Pattern::all_assertions_have_predicate_in(
+            names.map(Pattern::text).collect(),
+        ));
+    }
+    // The node comes last so that the matched path ends at the node itself.
+    constraints.push(Pattern::any_node());
+    Ok(Some(all_of(constraints)))
+}
+
+fn all_of(mut patterns: Vec<Pattern>) -> Pattern {
+    match patterns.len() {
+        0 => Pattern::any(),
+        1 => patterns.remove(0),
+        _ => Pattern::and(patterns),
+    }
+}
+
+fn any_of(mut patterns: Vec<Pattern>) -> Pattern {
+    match patterns.len() {
+        0 => Pattern::not_matching(Pattern::any()),
+        1 => patterns.remove(0),
+        _ => Pattern::or(patterns),
+    }
+}
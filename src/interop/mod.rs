@@ -0,0 +1,4 @@
+// Interoperability with other schema and pattern formats
+
+#[cfg(feature = "interop-json-schema")]
+mod json_schema;
@@ -1,5 +1,32 @@
+//! A pattern matcher and text syntax pattern parser for Gordian Envelope.
+//!
+//! Patterns can be built programmatically or parsed from the pattern
+//! expression syntax described in `docs/envelope_patex.md`:
+//!
+//! ```
+//! use bc_envelope::prelude::*;
+//! use bc_envelope_pattern::{Matcher, Pattern};
+//!
+//! let envelope = Envelope::new("Alice")
+//!     .add_assertion("knows", "Bob")
+//!     .add_assertion("age", 30);
+//!
+//! // Find "Bob" anywhere in the envelope.
+//! let pattern = Pattern::search_text("Bob");
+//! assert_eq!(pattern, Pattern::parse(r#"search("Bob")"#).unwrap());
+//! assert!(pattern.matches(&envelope));
+//!
+//! // Find any number between 18 and 65.
+//! let pattern = Pattern::search_number_range(18..=65);
+//! assert_eq!(pattern.paths(&envelope).len(), 1);
+//! ```
+
+mod captures;
 mod error;
+mod extract;
 mod format;
+mod interop;
+mod macros;
 mod parse;
 mod pattern;
 
@@ -7,10 +34,23 @@ pub use dcbor_pattern::{
     Interval, Matcher as DCBORMatcher, Pattern as DCBORPattern, Quantifier,
     Reluctance,
 };
+pub use captures::{coerce_captures_to_leaf_paths, coerce_to_root_paths};
 pub use error::{Error, Result};
+pub use extract::{
+    count_distinct_assertion_paths, count_distinct_objects_in_paths,
+    count_distinct_subjects_in_paths, search_all_dates,
+    search_all_known_values, search_all_numbers, search_all_text_values,
+    try_extract_bool, try_extract_date, try_extract_number, try_extract_text,
+};
 pub use format::{
-    FormatPathsOpts, PathElementFormat, format_path, format_path_opt,
-    format_paths, format_paths_opt, format_paths_with_captures,
+    FormatPathsOpts, PathElementFormat, digest_to_ur_string,
+    envelope_to_ur_string, format_path, format_path_opt, format_paths,
+    format_paths_as_ur_list, format_paths_opt, format_paths_with_captures,
     format_paths_with_captures_opt,
 };
-pub use pattern::{Matcher, Path, Pattern, dcbor_integration};
+pub use parse::parse_pattern_lenient;
+pub use pattern::{
+    Axis, Instr, Matcher, Path, Pattern, PatternKind, Program, TraversalOrder,
+    TraversalStats, compare_paths,
+    compile_as_atomic as compile_pattern_as_atomic, dcbor_integration,
+};
@@ -0,0 +1,77 @@
+/// Builds a [`Pattern`](crate::Pattern) from a compact description.
+///
+/// Each form expands to the corresponding `Pattern` constructor:
+///
+/// | Form                         | Expansion                                 |
+/// | :--------------------------- | :---------------------------------------- |
+/// | `text "Alice"`               | `Pattern::text("Alice")`                  |
+/// | `number 42`                  | `Pattern::number(42)`                     |
+/// | `and [p, p, ...]`            | `Pattern::and(vec![p, p, ...])`           |
+/// | `or [p, p, ...]`             | `Pattern::or(vec![p, p, ...])`            |
+/// | `traverse [p, p, ...]`       | `Pattern::traverse(vec![p, p, ...])`      |
+/// | `capture "name" p`           | `Pattern::capture("name", p)`             |
+/// | `search p`                   | `Pattern::search(p)`                      |
+/// | `not p`                      | `Pattern::not_matching(p)`                |
+/// | `name`                       | `Pattern::name()`, e.g. `any_subject`     |
+///
+/// Forms nest, so each `p` above may be any other form.
+///
+/// ```
+/// use bc_envelope_pattern::{Pattern, pattern};
+///
+/// let p = pattern!(or [text "Alice", and [any_number, capture "n" number 42]]);
+/// assert_eq!(
+///     p,
+///     Pattern::or(vec![
+///         Pattern::text("Alice"),
+///         Pattern::and(vec![
+///             Pattern::any_number(),
+///             Pattern::capture("n", Pattern::number(42)),
+///         ]),
+///     ])
+/// );
+/// ```
+#[macro_export]
+macro_rules! pattern {
+    // Comma-separated lists of forms, munched one token at a time.
+    (@list [$($done:expr,)*] []) => {
+        vec![$($done),*]
+    };
+    (@list [$($done:expr,)*] [$($cur:tt)+]) => {
+        vec![$($done,)* $crate::pattern!($($cur)+)]
+    };
+    (@list [$($done:expr,)*] [$($cur:tt)+] , $($rest:tt)*) => {
+        $crate::pattern!(@list [$($done,)* $crate::pattern!($($cur)+),] [] $($rest)*)
+    };
+    (@list [$($done:expr,)*] [$($cur:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::pattern!(@list [$($done,)*] [$($cur)* $next] $($rest)*)
+    };
+
+    (text $value:expr) => {
+        $crate::Pattern::text($value)
+    };
+    (number $value:expr) => {
+        $crate::Pattern::number($value)
+    };
+    (and [$($items:tt)*]) => {
+        $crate::Pattern::and($crate::pattern!(@list [] [] $($items)*))
+    };
+    (or [$($items:tt)*]) => {
+        $crate::Pattern::or($crate::pattern!(@list [] [] $($items)*))
+    };
+    (traverse [$($items:tt)*]) => {
+        $crate::Pattern::traverse($crate::pattern!(@list [] [] $($items)*))
+    };
+    (capture $name:literal $($inner:tt)+) => {
+        $crate::Pattern::capture($name, $crate::pattern!($($inner)+))
+    };
+    (search $($inner:tt)+) => {
+        $crate::Pattern::search($crate::pattern!($($inner)+))
+    };
+    (not $($inner:tt)+) => {
+        $crate::Pattern::not_matching($crate::pattern!($($inner)+))
+    };
+    ($constructor:ident) => {
+        $crate::Pattern::$constructor()
+    };
+}
@@ -1,9 +1,9 @@
 use crate::{
-    Error, Pattern, Result,
+    DCBORPattern, Error, Pattern, Result,
     parse::{Token, utils},
 };
 
-pub(crate) fn parse_array(lexer: &mut logos::Lexer<Token>) -> Result<Pattern> {
+pub(crate) fn parse_array(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
     // We're at the '[' token, now need to parse until ']'
     let src = lexer.remainder();
     let (pattern, consumed) = utils::parse_array_inner(src)?;
@@ -15,3 +15,51 @@ pub(crate) fn parse_array(lexer: &mut logos::Lexer<Token>) -> Result<Pattern> {
         None => Err(Error::ExpectedCloseBracket(lexer.span())),
     }
 }
+
+/// Parses `[ n : pattern ]`, after `arr`, where `pattern` is a dcbor-pattern.
+pub(crate) fn parse_array_at(
+    lexer: &mut crate::parse::Lexer,
+) -> Result<Pattern> {
+    match lexer.next() {
+        Some(Ok(Token::BracketOpen)) => {}
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::ExpectedOpenBracket(lexer.span())),
+    }
+    let index = match lexer.next() {
+        Some(Ok(Token::Integer(Ok(n)))) => usize::try_from(n).ok(),
+        Some(Ok(Token::UnsignedInteger(Ok(n)))) => Some(n),
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::UnexpectedEndOfInput),
+    }
+    .ok_or_else(|| Error::InvalidNumberFormat(lexer.span()))?;
+
+    let src = lexer.remainder();
+    let mut pos = 0;
+    utils::skip_ws(src, &mut pos);
+    if !src[pos..].starts_with(':') {
+        let end = lexer.span().end + pos;
+        return Err(Error::InvalidPattern(end..end));
+    }
+    pos += 1;
+    let close = pos
+        + utils::find_unnested(&src[pos..], b"]").ok_or_else(|| {
+            let end = lexer.span().end + src.len();
+            Error::ExpectedCloseBracket(end..end)
+        })?;
+    let element = DCBORPattern::parse(src[pos..close].trim())?;
+    lexer.bump(close);
+    match lexer.next() {
+        Some(Ok(Token::BracketClose)) => {
+            Ok(Pattern::cbor_array_at(index, element))
+        }
+        Some(Ok(t)) => Err(Error::UnexpectedToken(Box::new(t), lexer.span())),
+        Some(Err(e)) => Err(e),
+        None => Err(Error::ExpectedCloseBracket(lexer.span())),
+    }
+}
@@ -3,7 +3,7 @@ use crate::{
     parse::{Token, utils},
 };
 
-pub(crate) fn parse_cbor(lexer: &mut logos::Lexer<Token>) -> Result<Pattern> {
+pub(crate) fn parse_cbor(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
     let mut lookahead = lexer.clone();
     match lookahead.next() {
         Some(Ok(Token::ParenOpen)) => {
@@ -23,3 +23,34 @@ pub(crate) fn parse_cbor(lexer: &mut logos::Lexer<Token>) -> Result<Pattern> {
         _ => Ok(Pattern::any_cbor()),
     }
 }
+
+/// Parses `( h'/regex/' )` after `cbor_bytes`.
+pub(crate) fn parse_cbor_bytes(
+    lexer: &mut crate::parse::Lexer,
+) -> Result<Pattern> {
+    match lexer.next() {
+        Some(Ok(Token::ParenOpen)) => {}
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::ExpectedOpenParen(lexer.span())),
+    }
+    let regex = match lexer.next() {
+        Some(Ok(Token::HexBinaryRegex(res))) => {
+            regex::bytes::Regex::new(&res?)
+                .map_err(|_| Error::InvalidRegex(lexer.span()))?
+        }
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::UnexpectedEndOfInput),
+    };
+    match lexer.next() {
+        Some(Ok(Token::ParenClose)) => Ok(Pattern::cbor_bytes_pattern(regex)),
+        Some(Ok(t)) => Err(Error::UnexpectedToken(Box::new(t), lexer.span())),
+        Some(Err(e)) => Err(e),
+        None => Err(Error::ExpectedCloseParen(lexer.span())),
+    }
+}
@@ -13,6 +13,18 @@ pub(crate) fn parse_date_content(content: String) -> Result<Pattern> {
         return Ok(Pattern::date_regex(regex));
     }
 
+    // year:YYYY and month:MM shorthands
+    if let Some(year) = content.strip_prefix("year:") {
+        let year = parse_component(year, 1..=9999)
+            .ok_or_else(|| Error::InvalidDateFormat(0..content.len()))?;
+        return Ok(Pattern::date_in_year(year as i32));
+    }
+    if let Some(month) = content.strip_prefix("month:") {
+        let month = parse_component(month, 1..=12)
+            .ok_or_else(|| Error::InvalidDateFormat(0..content.len()))?;
+        return Ok(Pattern::date_in_month(month));
+    }
+
     // Check for range patterns
     if content.contains("...") {
         let parts: Vec<&str> = content.split("...").collect();
@@ -46,3 +58,15 @@ pub(crate) fn parse_date_content(content: String) -> Result<Pattern> {
         .map_err(|_| Error::InvalidDateFormat(0..content.len()))?;
     Ok(Pattern::date(date))
 }
+
+/// Parses a date component written in decimal digits, if it lies in
+/// `range`.
+fn parse_component(
+    digits: &str,
+    range: std::ops::RangeInclusive<u32>,
+) -> Option<u32> {
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok().filter(|n| range.contains(n))
+}
@@ -0,0 +1,56 @@
+use crate::{Error, Pattern, Result, parse::Token};
+
+/// Parses the optional `( ... )` after `integer`: `( n )`, `( n...m )`,
+/// `( >n )` or `( <n )`.
+pub(crate) fn parse_integer(
+    lexer: &mut crate::parse::Lexer,
+) -> Result<Pattern> {
+    let mut lookahead = lexer.clone();
+    if !matches!(lookahead.next(), Some(Ok(Token::ParenOpen))) {
+        return Ok(Pattern::any_integer());
+    }
+    lexer.next();
+    let pattern = match lexer.next() {
+        Some(Ok(Token::GreaterThan)) => {
+            Pattern::integer_greater_than(parse_value(lexer)?)
+        }
+        Some(Ok(Token::LessThan)) => {
+            Pattern::integer_less_than(parse_value(lexer)?)
+        }
+        Some(Ok(token)) => {
+            let start = integer_value(token, lexer)?;
+            let mut lookahead = lexer.clone();
+            if matches!(lookahead.next(), Some(Ok(Token::Ellipsis))) {
+                lexer.next();
+                Pattern::integer_range(start..=parse_value(lexer)?)
+            } else {
+                Pattern::integer(start)
+            }
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::UnexpectedEndOfInput),
+    };
+    match lexer.next() {
+        Some(Ok(Token::ParenClose)) => Ok(pattern),
+        Some(Ok(t)) => Err(Error::UnexpectedToken(Box::new(t), lexer.span())),
+        Some(Err(e)) => Err(e),
+        None => Err(Error::ExpectedCloseParen(lexer.span())),
+    }
+}
+
+fn parse_value(lexer: &mut crate::parse::Lexer) -> Result<i64> {
+    match lexer.next() {
+        Some(Ok(token)) => integer_value(token, lexer),
+        Some(Err(e)) => Err(e),
+        None => Err(Error::UnexpectedEndOfInput),
+    }
+}
+
+fn integer_value(token: Token, lexer: &crate::parse::Lexer) -> Result<i64> {
+    match token {
+        Token::Integer(res) => res,
+        Token::UnsignedInteger(res) => i64::try_from(res?)
+            .map_err(|_| Error::InvalidNumberFormat(lexer.span())),
+        t => Err(Error::UnexpectedToken(Box::new(t), lexer.span())),
+    }
+}
@@ -1,14 +1,48 @@
+use known_values::KnownValue;
+
 use crate::{Error, Pattern, Result, parse::Token};
 
-// This parser is no longer used with the new dcbor-pattern syntax.
-// The new syntax is handled directly in the primary parser.
-// Keeping for potential legacy support or reference.
+/// Parses the content of a single-quoted known value pattern such as `'1'` or
+/// `'isA'`.
+///
+/// Numeric content produces a pattern for that raw value. Anything else is
+/// kept as a name pattern, which is resolved against the global known values
+/// registry at match time, so names registered after parsing still match.
+/// Use `Pattern::parse_known_value_from_name` to require a registered name.
+pub(crate) fn parse_known_value(content: String) -> Result<Pattern> {
+    if let Ok(value) = content.parse::<u64>() {
+        Ok(Pattern::known_value(KnownValue::new(value)))
+    } else {
+        Ok(Pattern::known_value_named(content))
+    }
+}
 
-#[allow(dead_code)]
-pub(crate) fn parse_known_value(
-    _lexer: &mut logos::Lexer<Token>,
+/// Parses `kv_or_text ( "name" )`.
+pub(crate) fn parse_known_value_or_text(
+    lexer: &mut crate::parse::Lexer,
 ) -> Result<Pattern> {
-    // With the new dcbor-pattern syntax, this function should not be called
-    // as 'known' patterns are handled directly in primary_parser.rs
-    Err(Error::UnexpectedToken(Box::new(Token::Known), 0..0))
+    match lexer.next() {
+        Some(Ok(Token::ParenOpen)) => {}
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::ExpectedOpenParen(lexer.span())),
+    }
+    let name = match lexer.next() {
+        Some(Ok(Token::StringLiteral(res))) => res?,
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::UnexpectedEndOfInput),
+    };
+    match lexer.next() {
+        Some(Ok(Token::ParenClose)) => {
+            Ok(Pattern::known_value_or_text(&name))
+        }
+        Some(Ok(t)) => Err(Error::UnexpectedToken(Box::new(t), lexer.span())),
+        Some(Err(e)) => Err(e),
+        None => Err(Error::ExpectedCloseParen(lexer.span())),
+    }
 }
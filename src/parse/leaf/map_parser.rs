@@ -1,4 +1,7 @@
-use crate::{Error, Pattern, Result, parse::Token};
+use crate::{
+    DCBORPattern, Error, Pattern, Result,
+    parse::{Token, utils},
+};
 
 /// Legacy map parser - no longer used after migration to dcbor-pattern map
 /// syntax
@@ -6,7 +9,7 @@ use crate::{Error, Pattern, Result, parse::Token};
 #[deprecated(
     note = "Use dcbor-pattern map syntax instead: {*}, {{n}}, {{n,m}}, {{n,}}, {key: value}"
 )]
-pub(crate) fn parse_map(lexer: &mut logos::Lexer<Token>) -> Result<Pattern> {
+pub(crate) fn parse_map(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
     let mut lookahead = lexer.clone();
     match lookahead.next() {
         Some(Ok(Token::ParenOpen)) => {
@@ -70,3 +73,63 @@ pub(crate) fn parse_map(lexer: &mut logos::Lexer<Token>) -> Result<Pattern> {
         _ => Ok(Pattern::any_map()),
     }
 }
+
+/// Parses the optional `( key: pattern )` or `( key: pattern, val: pattern )`
+/// after `map`, where both patterns are dcbor-patterns.
+pub(crate) fn parse_map_keys(
+    lexer: &mut crate::parse::Lexer,
+) -> Result<Pattern> {
+    let mut lookahead = lexer.clone();
+    if !matches!(lookahead.next(), Some(Ok(Token::ParenOpen))) {
+        return Ok(Pattern::any_map());
+    }
+    lexer.next();
+    let key = parse_labeled_dcbor(lexer, "key", b",)")?;
+    match lexer.next() {
+        Some(Ok(Token::ParenClose)) => {
+            return Ok(Pattern::map_with_required_key(key));
+        }
+        Some(Ok(Token::Comma)) => {}
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::ExpectedCloseParen(lexer.span())),
+    }
+    let value = parse_labeled_dcbor(lexer, "val", b")")?;
+    match lexer.next() {
+        Some(Ok(Token::ParenClose)) => {
+            Ok(Pattern::map_with_required_key_value(key, value))
+        }
+        Some(Ok(t)) => Err(Error::UnexpectedToken(Box::new(t), lexer.span())),
+        Some(Err(e)) => Err(e),
+        None => Err(Error::ExpectedCloseParen(lexer.span())),
+    }
+}
+
+/// Parses `label: pattern`, where the dcbor-pattern runs up to the first
+/// unnested byte in `delimiters`.
+fn parse_labeled_dcbor(
+    lexer: &mut crate::parse::Lexer,
+    label: &str,
+    delimiters: &[u8],
+) -> Result<DCBORPattern> {
+    let Some(consumed) = utils::parse_label(lexer.remainder(), label) else {
+        return match lexer.next() {
+            Some(Ok(t)) => {
+                Err(Error::UnexpectedToken(Box::new(t), lexer.span()))
+            }
+            Some(Err(e)) => Err(e),
+            None => Err(Error::UnexpectedEndOfInput),
+        };
+    };
+    lexer.bump(consumed);
+    let src = lexer.remainder();
+    let end = utils::find_unnested(src, delimiters).ok_or_else(|| {
+        let end = lexer.span().end + src.len();
+        Error::ExpectedCloseParen(end..end)
+    })?;
+    let pattern = DCBORPattern::parse(src[..end].trim())?;
+    lexer.bump(end);
+    Ok(pattern)
+}
@@ -3,22 +3,27 @@
 mod array_parser;
 mod cbor_parser;
 mod date_parser;
+mod integer_parser;
 mod known_value_parser;
 mod map_parser;
 mod null_parser;
 mod number_parser;
 mod tag_parser;
+mod text_parser;
 
-pub(crate) use array_parser::parse_array;
-pub(crate) use cbor_parser::parse_cbor;
+pub(crate) use array_parser::{parse_array, parse_array_at};
+pub(crate) use cbor_parser::{parse_cbor, parse_cbor_bytes};
 pub(crate) use date_parser::parse_date_content;
-// Legacy parser - no longer used with new dcbor-pattern syntax
-#[allow(unused_imports)]
-pub(crate) use known_value_parser::parse_known_value;
+pub(crate) use integer_parser::parse_integer;
+pub(crate) use known_value_parser::{
+    parse_known_value, parse_known_value_or_text,
+};
 // parse_map is no longer used after migration to dcbor-pattern map syntax
 // pub(crate) use map_parser::parse_map;
+pub(crate) use map_parser::parse_map_keys;
 pub(crate) use null_parser::parse_null;
 pub(crate) use number_parser::{
-    parse_comparison_number, parse_number_range_or_comparison,
+    parse_comparison_number, parse_number, parse_number_range_or_comparison,
 };
 pub(crate) use tag_parser::parse_tag;
+pub(crate) use text_parser::{parse_text, parse_text_glob};
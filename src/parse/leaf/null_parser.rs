@@ -1,7 +1,5 @@
 use crate::{Pattern, Result};
 
-pub(crate) fn parse_null(
-    _lexer: &mut logos::Lexer<crate::parse::Token>,
-) -> Result<Pattern> {
+pub(crate) fn parse_null(_lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
     Ok(Pattern::null())
 }
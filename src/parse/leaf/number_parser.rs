@@ -1,7 +1,71 @@
-use crate::{Error, Pattern, Result, parse::Token};
+use crate::{
+    Error, Pattern, Result,
+    parse::{Token, utils},
+};
+
+pub(crate) fn parse_number(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
+    let mut lookahead = lexer.clone();
+    if !matches!(lookahead.next(), Some(Ok(Token::ParenOpen))) {
+        return Ok(Pattern::any_number());
+    }
+    lexer.next();
+    // number ( infinite ) or number ( finite )
+    let remainder = lexer.remainder();
+    for (keyword, pattern) in [
+        ("infinite", Pattern::number_is_infinite as fn() -> Pattern),
+        ("finite", Pattern::number_is_finite),
+    ] {
+        if let Some(rest) = remainder.trim_start().strip_prefix(keyword) {
+            let rest = rest.trim_start();
+            if rest.starts_with(')') {
+                lexer.bump(remainder.len() - rest.len() + 1);
+                return Ok(pattern());
+            }
+        }
+    }
+    // number ( div: n )
+    if let Some(consumed) = utils::parse_label(lexer.remainder(), "div") {
+        lexer.bump(consumed);
+        let divisor = match lexer.next() {
+            Some(Ok(Token::UnsignedInteger(res))) => res? as f64,
+            Some(Ok(Token::Integer(res))) => res? as f64,
+            Some(Ok(Token::Float(res))) => res?,
+            Some(Ok(Token::NaN)) => f64::NAN,
+            Some(Ok(Token::Infinity)) => f64::INFINITY,
+            Some(Ok(Token::NegativeInfinity)) => f64::NEG_INFINITY,
+            Some(Ok(t)) => {
+                return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+            }
+            Some(Err(e)) => return Err(e),
+            None => return Err(Error::UnexpectedEndOfInput),
+        };
+        let span = lexer.span();
+        let pattern = Pattern::number_divisible_by(divisor)
+            .map_err(|_| Error::InvalidDivisor(span))?;
+        return match lexer.next() {
+            Some(Ok(Token::ParenClose)) => Ok(pattern),
+            Some(Ok(t)) => {
+                Err(Error::UnexpectedToken(Box::new(t), lexer.span()))
+            }
+            Some(Err(e)) => Err(e),
+            None => Err(Error::ExpectedCloseParen(lexer.span())),
+        };
+    }
+    // number ( one_of: [ 1, 2.5, NaN, ... ] )
+    let values = utils::parse_one_of(lexer, |token| match token {
+        Token::UnsignedInteger(res) => res.map(|n| Some(n as f64)),
+        Token::Integer(res) => res.map(|i| Some(i as f64)),
+        Token::Float(res) => res.map(Some),
+        Token::NaN => Ok(Some(f64::NAN)),
+        Token::Infinity => Ok(Some(f64::INFINITY)),
+        Token::NegativeInfinity => Ok(Some(f64::NEG_INFINITY)),
+        _ => Ok(None),
+    })?;
+    Ok(Pattern::number_matches_any(values))
+}
 
 pub(crate) fn parse_number_range_or_comparison(
-    lexer: &mut logos::Lexer<Token>,
+    lexer: &mut crate::parse::Lexer,
     first_value: f64,
 ) -> Result<Pattern> {
     // Look ahead to see if this is a range or comparison
@@ -35,7 +99,7 @@ pub(crate) fn parse_number_range_or_comparison(
 }
 
 pub(crate) fn parse_comparison_number(
-    lexer: &mut logos::Lexer<Token>,
+    lexer: &mut crate::parse::Lexer,
     comparison: &str,
 ) -> Result<Pattern> {
     match lexer.next() {
@@ -3,7 +3,7 @@ use crate::{
     parse::{Token, utils},
 };
 
-pub(crate) fn parse_tag(lexer: &mut logos::Lexer<Token>) -> Result<Pattern> {
+pub(crate) fn parse_tag(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
     let mut lookahead = lexer.clone();
     match lookahead.next() {
         Some(Ok(Token::ParenOpen)) => {
@@ -0,0 +1,117 @@
+use super::super::{Token, utils};
+use crate::{Error, Pattern, Result};
+
+pub(crate) fn parse_text(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
+    let mut lookahead = lexer.clone();
+    if !matches!(lookahead.next(), Some(Ok(Token::ParenOpen))) {
+        return Ok(Pattern::any_text());
+    }
+    lexer.next();
+    // text ( ^"prefix" )
+    let remainder = lexer.remainder();
+    if let Some(rest) = remainder.trim_start().strip_prefix('^') {
+        lexer.bump(remainder.len() - rest.len());
+        let prefix = match lexer.next() {
+            Some(Ok(Token::StringLiteral(res))) => res?,
+            Some(Ok(t)) => {
+                return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+            }
+            Some(Err(e)) => return Err(e),
+            None => return Err(Error::UnexpectedEndOfInput),
+        };
+        return expect_close_paren(lexer, Pattern::text_starts_with(prefix));
+    }
+    // text ( "suffix"$ )
+    let mut lookahead = lexer.clone();
+    if let Some(Ok(Token::StringLiteral(res))) = lookahead.next() {
+        lexer.next();
+        let suffix = res?;
+        let remainder = lexer.remainder();
+        let Some(rest) = remainder.trim_start().strip_prefix('$') else {
+            let end = lexer.span().end;
+            return Err(Error::InvalidPattern(end..end));
+        };
+        lexer.bump(remainder.len() - rest.len());
+        return expect_close_paren(lexer, Pattern::text_ends_with(suffix));
+    }
+    // text ( len: n ) or text ( len: {n,m} )
+    if let Some(consumed) = utils::parse_label(lexer.remainder(), "len") {
+        lexer.bump(consumed);
+        return parse_text_length(lexer);
+    }
+    // text ( one_of: [ "a", "b", ... ] )
+    let values = utils::parse_one_of(lexer, |token| match token {
+        Token::StringLiteral(res) => res.map(Some),
+        _ => Ok(None),
+    })?;
+    Ok(Pattern::text_matches_any(values))
+}
+
+/// Parses `n )` or `{n,m} )`, after `text ( len:`.
+fn parse_text_length(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
+    let pattern = match lexer.next() {
+        Some(Ok(Token::UnsignedInteger(res))) => Pattern::text_length(res?),
+        Some(Ok(Token::Integer(res))) => {
+            let length = usize::try_from(res?)
+                .map_err(|_| Error::InvalidNumberFormat(lexer.span()))?;
+            Pattern::text_length(length)
+        }
+        Some(Ok(Token::Range(res))) => {
+            let range = res?;
+            match range.max() {
+                Some(max) => Pattern::text_length_range(range.min()..=max),
+                None => Pattern::text_length_range(range.min()..),
+            }
+            .map_err(|_| Error::InvalidRange(lexer.span()))?
+        }
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::UnexpectedEndOfInput),
+    };
+    expect_close_paren(lexer, pattern)
+}
+
+fn expect_close_paren(
+    lexer: &mut crate::parse::Lexer,
+    pattern: Pattern,
+) -> Result<Pattern> {
+    match lexer.next() {
+        Some(Ok(Token::ParenClose)) => Ok(pattern),
+        Some(Ok(t)) => Err(Error::UnexpectedToken(Box::new(t), lexer.span())),
+        Some(Err(e)) => Err(e),
+        None => Err(Error::ExpectedCloseParen(lexer.span())),
+    }
+}
+
+/// Parses `( "glob" )` after `text_glob`.
+pub(crate) fn parse_text_glob(
+    lexer: &mut crate::parse::Lexer,
+) -> Result<Pattern> {
+    match lexer.next() {
+        Some(Ok(Token::ParenOpen)) => {}
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::ExpectedOpenParen(lexer.span())),
+    }
+    let glob = match lexer.next() {
+        Some(Ok(Token::StringLiteral(res))) => res?,
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::UnexpectedEndOfInput),
+    };
+    let span = lexer.span();
+    let pattern = Pattern::text_matches_glob(&glob)
+        .map_err(|_| Error::InvalidRegex(span))?;
+    match lexer.next() {
+        Some(Ok(Token::ParenClose)) => Ok(pattern),
+        Some(Ok(t)) => Err(Error::UnexpectedToken(Box::new(t), lexer.span())),
+        Some(Err(e)) => Err(e),
+        None => Err(Error::ExpectedCloseParen(lexer.span())),
+    }
+}
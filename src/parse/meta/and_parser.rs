@@ -1,7 +1,7 @@
 use super::{super::Token, primary_parser::parse_primary};
 use crate::{Pattern, Result};
 
-pub(crate) fn parse_and(lexer: &mut logos::Lexer<Token>) -> Result<Pattern> {
+pub(crate) fn parse_and(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
     let mut patterns = vec![parse_primary(lexer)?];
 
     loop {
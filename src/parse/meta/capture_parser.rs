@@ -2,7 +2,7 @@ use super::{super::Token, or_parser::parse_or};
 use crate::{Error, Pattern, Result};
 
 pub(crate) fn parse_capture(
-    lexer: &mut logos::Lexer<Token>,
+    lexer: &mut crate::parse::Lexer,
     name: String,
 ) -> Result<Pattern> {
     match lexer.next() {
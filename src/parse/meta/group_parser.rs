@@ -1,7 +1,7 @@
 use super::{super::Token, or_parser::parse_or};
 use crate::{Error, Pattern, Reluctance, Result};
 
-pub(crate) fn parse_group(lexer: &mut logos::Lexer<Token>) -> Result<Pattern> {
+pub(crate) fn parse_group(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
     let pat = parse_or(lexer)?;
     match lexer.next() {
         Some(Ok(Token::ParenClose)) => {
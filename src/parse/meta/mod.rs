@@ -10,3 +10,4 @@ mod search_parser;
 mod traverse_parser;
 
 pub(crate) use or_parser::parse_or;
+pub(crate) use primary_parser::parse_primary;
@@ -1,7 +1,7 @@
 use super::{super::Token, and_parser::parse_and};
 use crate::{Pattern, Result};
 
-pub(crate) fn parse_not(lexer: &mut logos::Lexer<Token>) -> Result<Pattern> {
+pub(crate) fn parse_not(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
     let mut lookahead = lexer.clone();
     match lookahead.next() {
         Some(Ok(Token::Not)) => {
@@ -1,7 +1,7 @@
 use super::{super::Token, traverse_parser::parse_traverse};
-use crate::{Pattern, Result};
+use crate::{Error, Pattern, Result};
 
-pub(crate) fn parse_or(lexer: &mut logos::Lexer<Token>) -> Result<Pattern> {
+pub(crate) fn parse_or(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
     let mut patterns = vec![parse_traverse(lexer)?];
 
     loop {
@@ -17,7 +17,60 @@ pub(crate) fn parse_or(lexer: &mut logos::Lexer<Token>) -> Result<Pattern> {
 
     if patterns.len() == 1 {
         Ok(patterns.remove(0))
+    } else if patterns.iter().any(|p| p.priority_branches().is_some()) {
+        // Any `prio(n, p)` alternative makes the whole alternation
+        // prioritized; alternatives without a priority get priority 0.
+        let branches = patterns
+            .into_iter()
+            .flat_map(|pattern| match pattern.priority_branches() {
+                Some(branches) => branches.to_vec(),
+                None => vec![(0, pattern)],
+            })
+            .collect();
+        Ok(Pattern::or_with_priority(branches))
     } else {
         Ok(Pattern::or(patterns))
     }
 }
+
+/// Parses `prio(n, pattern)`, after the `prio` keyword, as a prioritized
+/// alternation with a single branch.
+pub(crate) fn parse_priority(
+    lexer: &mut crate::parse::Lexer,
+) -> Result<Pattern> {
+    match lexer.next() {
+        Some(Ok(Token::ParenOpen)) => {}
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::ExpectedOpenParen(lexer.span())),
+    }
+    let priority = match lexer.next() {
+        Some(Ok(Token::Integer(Ok(n)))) => u32::try_from(n).ok(),
+        Some(Ok(Token::UnsignedInteger(Ok(n)))) => u32::try_from(n).ok(),
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::UnexpectedEndOfInput),
+    }
+    .ok_or_else(|| Error::InvalidNumberFormat(lexer.span()))?;
+    match lexer.next() {
+        Some(Ok(Token::Comma)) => {}
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::UnexpectedEndOfInput),
+    }
+    let pattern = parse_or(lexer)?;
+    match lexer.next() {
+        Some(Ok(Token::ParenClose)) => {
+            Ok(Pattern::or_with_priority(vec![(priority, pattern)]))
+        }
+        Some(Ok(t)) => Err(Error::UnexpectedToken(Box::new(t), lexer.span())),
+        Some(Err(e)) => Err(e),
+        None => Err(Error::ExpectedCloseParen(lexer.span())),
+    }
+}
@@ -1,15 +1,15 @@
-use known_values::KnownValue;
-
 use super::{
     super::{Token, leaf, structure},
     capture_parser::parse_capture,
     group_parser::parse_group,
-    search_parser::parse_search,
+    or_parser::parse_priority,
+    search_parser::{parse_count, parse_dedup, parse_search, parse_unique},
+    traverse_parser::{parse_bfs, parse_while},
 };
 use crate::{Error, Pattern, Result};
 
 pub(crate) fn parse_primary(
-    lexer: &mut logos::Lexer<Token>,
+    lexer: &mut crate::parse::Lexer,
 ) -> Result<Pattern> {
     let token = match lexer.next() {
         Some(Ok(tok)) => tok,
@@ -28,6 +28,12 @@ pub(crate) fn parse_primary(
     match token {
         // Envelope-specific patterns first (these take precedence)
         Token::Search => parse_search(lexer),
+        Token::Dedup => parse_dedup(lexer),
+        Token::Unique => parse_unique(lexer),
+        Token::Prio => parse_priority(lexer),
+        Token::While => parse_while(lexer),
+        Token::Bfs => parse_bfs(lexer),
+        Token::Count => parse_count(lexer),
         Token::Node => structure::parse_node(lexer),
         Token::Assertion => structure::parse_assertion(lexer),
         Token::AssertionPred => structure::parse_assertion_pred(lexer),
@@ -39,15 +45,19 @@ pub(crate) fn parse_primary(
         Token::Encrypted => structure::parse_encrypted(lexer),
         Token::Compressed => structure::parse_compressed(lexer),
         Token::Pred => structure::parse_predicate(lexer),
+        Token::PredOrSubject => structure::parse_predicate_or_subject(lexer),
         Token::Wrapped => structure::parse_wrapped(lexer),
         Token::Unwrap => structure::parse_unwrap(lexer),
+        Token::Untag => structure::parse_untag(lexer),
         Token::Subject => structure::parse_subject(lexer),
         Token::GroupName(name) => parse_capture(lexer, name),
         Token::ParenOpen => parse_group(lexer),
-        Token::Leaf => Ok(Pattern::leaf()),
+        Token::Leaf => structure::parse_leaf(lexer),
+        Token::Case => structure::parse_case(lexer),
 
         // Patterns that might have envelope-specific extensions
         Token::Cbor => leaf::parse_cbor(lexer),
+        Token::CborBytes => leaf::parse_cbor_bytes(lexer),
 
         // For simple leaf patterns, try dcbor-pattern first
         Token::RepeatZeroOrMore => Ok(Pattern::any()), /* dcbor-pattern's * */
@@ -55,8 +65,10 @@ pub(crate) fn parse_primary(
         Token::BoolKeyword => Ok(Pattern::any_bool()),
         Token::BoolTrue => Ok(Pattern::bool(true)),
         Token::BoolFalse => Ok(Pattern::bool(false)),
-        Token::NumberKeyword => Ok(Pattern::any_number()),
-        Token::TextKeyword => Ok(Pattern::any_text()),
+        Token::NumberKeyword => leaf::parse_number(lexer),
+        Token::IntegerKeyword => leaf::parse_integer(lexer),
+        Token::TextKeyword => leaf::parse_text(lexer),
+        Token::TextGlob => leaf::parse_text_glob(lexer),
         Token::StringLiteral(Ok(s)) => Ok(Pattern::text(s)),
         Token::StringLiteral(Err(e)) => Err(e),
         Token::UnsignedInteger(Ok(n)) => {
@@ -79,8 +91,8 @@ pub(crate) fn parse_primary(
         Token::GreaterThan => leaf::parse_comparison_number(lexer, ">"),
         Token::LessThan => leaf::parse_comparison_number(lexer, "<"),
         Token::NaN => Ok(Pattern::number_nan()),
-        Token::Infinity => Ok(Pattern::number(f64::INFINITY)),
-        Token::NegativeInfinity => Ok(Pattern::number(f64::NEG_INFINITY)),
+        Token::Infinity => Ok(Pattern::number_positive_infinity()),
+        Token::NegativeInfinity => Ok(Pattern::number_negative_infinity()),
         Token::Regex(Ok(regex_str)) => {
             let regex = regex::Regex::new(&regex_str)
                 .map_err(|_| Error::InvalidRegex(lexer.span()))?;
@@ -88,6 +100,8 @@ pub(crate) fn parse_primary(
         }
         Token::Regex(Err(e)) => Err(e),
         Token::BracketOpen => leaf::parse_array(lexer),
+        Token::Arr => leaf::parse_array_at(lexer),
+        Token::Map => leaf::parse_map_keys(lexer),
         Token::ByteString => Ok(Pattern::any_byte_string()),
         Token::HexPattern(Ok(bytes)) => Ok(Pattern::byte_string(bytes)),
         Token::HexPattern(Err(e)) => Err(e),
@@ -102,12 +116,9 @@ pub(crate) fn parse_primary(
         Token::DatePattern(Err(e)) => Err(e),
         Token::Tagged => leaf::parse_tag(lexer),
         Token::Known => Ok(Pattern::any_known_value()),
+        Token::KnownValueOrText => leaf::parse_known_value_or_text(lexer),
         Token::SingleQuotedPattern(Ok(content)) => {
-            if let Ok(value) = content.parse::<u64>() {
-                Ok(Pattern::known_value(KnownValue::new(value)))
-            } else {
-                Ok(Pattern::known_value_named(content))
-            }
+            leaf::parse_known_value(content)
         }
         Token::SingleQuotedPattern(Err(e)) => Err(e),
         Token::SingleQuotedRegex(Ok(regex_str)) => {
@@ -1,12 +1,97 @@
-use super::{super::Token, or_parser::parse_or};
+use super::{
+    super::{Token, utils},
+    or_parser::parse_or,
+};
 use crate::{Error, Pattern, Result};
 
-pub(crate) fn parse_search(lexer: &mut logos::Lexer<Token>) -> Result<Pattern> {
+pub(crate) fn parse_search(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
     match lexer.next() {
         Some(Ok(Token::ParenOpen)) => {
             let pat = parse_or(lexer)?;
             match lexer.next() {
                 Some(Ok(Token::ParenClose)) => Ok(Pattern::search(pat)),
+                Some(Ok(Token::Comma)) => {
+                    // search ( pattern, limit: n )
+                    let Some(consumed) =
+                        utils::parse_label(lexer.remainder(), "limit")
+                    else {
+                        return match lexer.next() {
+                            Some(Ok(t)) => Err(Error::UnexpectedToken(
+                                Box::new(t),
+                                lexer.span(),
+                            )),
+                            Some(Err(e)) => Err(e),
+                            None => Err(Error::UnexpectedEndOfInput),
+                        };
+                    };
+                    lexer.bump(consumed);
+                    let limit = match lexer.next() {
+                        Some(Ok(Token::UnsignedInteger(res))) => res?,
+                        Some(Ok(Token::Integer(res))) => usize::try_from(res?)
+                            .map_err(|_| {
+                                Error::InvalidNumberFormat(lexer.span())
+                            })?,
+                        Some(Ok(t)) => {
+                            return Err(Error::UnexpectedToken(
+                                Box::new(t),
+                                lexer.span(),
+                            ));
+                        }
+                        Some(Err(e)) => return Err(e),
+                        None => return Err(Error::UnexpectedEndOfInput),
+                    };
+                    match lexer.next() {
+                        Some(Ok(Token::ParenClose)) => {
+                            Ok(Pattern::limited_search(pat, limit))
+                        }
+                        Some(Ok(t)) => Err(Error::UnexpectedToken(
+                            Box::new(t),
+                            lexer.span(),
+                        )),
+                        Some(Err(e)) => Err(e),
+                        None => Err(Error::ExpectedCloseParen(lexer.span())),
+                    }
+                }
+                Some(Ok(t)) => {
+                    Err(Error::UnexpectedToken(Box::new(t), lexer.span()))
+                }
+                Some(Err(e)) => Err(e),
+                None => Err(Error::ExpectedCloseParen(lexer.span())),
+            }
+        }
+        Some(Ok(t)) => Err(Error::UnexpectedToken(Box::new(t), lexer.span())),
+        Some(Err(e)) => Err(e),
+        None => Err(Error::UnexpectedEndOfInput),
+    }
+}
+
+pub(crate) fn parse_dedup(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
+    match lexer.next() {
+        Some(Ok(Token::ParenOpen)) => {
+            let pat = parse_or(lexer)?;
+            match lexer.next() {
+                Some(Ok(Token::ParenClose)) => Ok(Pattern::dedup(pat)),
+                Some(Ok(t)) => {
+                    Err(Error::UnexpectedToken(Box::new(t), lexer.span()))
+                }
+                Some(Err(e)) => Err(e),
+                None => Err(Error::ExpectedCloseParen(lexer.span())),
+            }
+        }
+        Some(Ok(t)) => Err(Error::UnexpectedToken(Box::new(t), lexer.span())),
+        Some(Err(e)) => Err(e),
+        None => Err(Error::UnexpectedEndOfInput),
+    }
+}
+
+pub(crate) fn parse_unique(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
+    match lexer.next() {
+        Some(Ok(Token::ParenOpen)) => {
+            let pat = parse_or(lexer)?;
+            match lexer.next() {
+                Some(Ok(Token::ParenClose)) => {
+                    Ok(Pattern::unique_by_digest(pat))
+                }
                 Some(Ok(t)) => {
                     Err(Error::UnexpectedToken(Box::new(t), lexer.span()))
                 }
@@ -19,3 +104,50 @@ pub(crate) fn parse_search(lexer: &mut logos::Lexer<Token>) -> Result<Pattern> {
         None => Err(Error::UnexpectedEndOfInput),
     }
 }
+
+/// Parses `( n, pattern )` or `( {n,m}, pattern )`, after `count`.
+pub(crate) fn parse_count(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
+    match lexer.next() {
+        Some(Ok(Token::ParenOpen)) => {}
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::UnexpectedEndOfInput),
+    }
+    let (min, max) = match lexer.next() {
+        Some(Ok(Token::UnsignedInteger(res))) => {
+            let n = res?;
+            (n, Some(n))
+        }
+        Some(Ok(Token::Integer(res))) => {
+            let n = usize::try_from(res?)
+                .map_err(|_| Error::InvalidNumberFormat(lexer.span()))?;
+            (n, Some(n))
+        }
+        Some(Ok(Token::Range(res))) => {
+            let range = res?;
+            (range.min(), range.max())
+        }
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::UnexpectedEndOfInput),
+    };
+    match lexer.next() {
+        Some(Ok(Token::Comma)) => {}
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::UnexpectedEndOfInput),
+    }
+    let pat = parse_or(lexer)?;
+    match lexer.next() {
+        Some(Ok(Token::ParenClose)) => Ok(Pattern::search_count(pat, min, max)),
+        Some(Ok(t)) => Err(Error::UnexpectedToken(Box::new(t), lexer.span())),
+        Some(Err(e)) => Err(e),
+        None => Err(Error::ExpectedCloseParen(lexer.span())),
+    }
+}
@@ -1,8 +1,8 @@
-use super::{super::Token, not_parser::parse_not};
-use crate::{Pattern, Result};
+use super::{super::Token, not_parser::parse_not, or_parser::parse_or};
+use crate::{Error, Pattern, Result, TraversalOrder};
 
 pub(crate) fn parse_traverse(
-    lexer: &mut logos::Lexer<Token>,
+    lexer: &mut crate::parse::Lexer,
 ) -> Result<Pattern> {
     let mut patterns = vec![parse_not(lexer)?];
 
@@ -13,13 +13,74 @@ pub(crate) fn parse_traverse(
                 lexer.next();
                 patterns.push(parse_not(lexer)?);
             }
+            Some(Ok(Token::TraverseBack)) => {
+                // `<-` binds to everything on its left.
+                lexer.next();
+                let child = combine(std::mem::take(&mut patterns));
+                let parent = parse_not(lexer)?;
+                patterns.push(Pattern::traverse_back(child, parent));
+            }
             _ => break,
         }
     }
 
+    Ok(combine(patterns))
+}
+
+fn combine(mut patterns: Vec<Pattern>) -> Pattern {
     if patterns.len() == 1 {
-        Ok(patterns.remove(0))
+        patterns.remove(0)
     } else {
-        Ok(Pattern::traverse(patterns))
+        Pattern::traverse(patterns)
+    }
+}
+
+/// Parses `( a -> b )` after `bfs`.
+pub(crate) fn parse_bfs(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
+    match lexer.next() {
+        Some(Ok(Token::ParenOpen)) => {}
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::ExpectedOpenParen(lexer.span())),
+    }
+    let pattern = parse_or(lexer)?;
+    match lexer.next() {
+        Some(Ok(Token::ParenClose)) => {
+            Ok(pattern.into_ordered_traversal(TraversalOrder::BreadthFirst))
+        }
+        Some(Ok(t)) => Err(Error::UnexpectedToken(Box::new(t), lexer.span())),
+        Some(Err(e)) => Err(e),
+        None => Err(Error::ExpectedCloseParen(lexer.span())),
+    }
+}
+
+pub(crate) fn parse_while(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
+    match lexer.next() {
+        Some(Ok(Token::ParenOpen)) => {}
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::ExpectedOpenParen(lexer.span())),
+    }
+    let condition = parse_or(lexer)?;
+    match lexer.next() {
+        Some(Ok(Token::Comma)) => {}
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::UnexpectedEndOfInput),
+    }
+    let body = parse_or(lexer)?;
+    match lexer.next() {
+        Some(Ok(Token::ParenClose)) => {
+            Ok(Pattern::traverse_while(condition, body))
+        }
+        Some(Ok(t)) => Err(Error::UnexpectedToken(Box::new(t), lexer.span())),
+        Some(Err(e)) => Err(e),
+        None => Err(Error::ExpectedCloseParen(lexer.span())),
     }
 }
@@ -1,12 +1,41 @@
 mod leaf;
 mod meta;
+mod parse_pattern;
+mod path_spec;
 mod structure;
 mod token;
+#[cfg_attr(
+    all(feature = "logos", not(feature = "no-proc-macros")),
+    allow(dead_code)
+)]
+mod token_manual;
 mod utils;
 
-use logos::Logos;
+pub use parse_pattern::parse_pattern_lenient;
 pub use token::Token;
 
+/// The lexer the parser runs on: generated by Logos by default, or
+/// hand-written when the `no-proc-macros` feature is enabled.
+#[cfg(all(feature = "logos", not(feature = "no-proc-macros")))]
+pub(crate) type Lexer<'s> = logos::Lexer<'s, Token>;
+
+/// The lexer the parser runs on: generated by Logos by default, or
+/// hand-written when the `no-proc-macros` feature is enabled.
+#[cfg(not(all(feature = "logos", not(feature = "no-proc-macros"))))]
+pub(crate) type Lexer<'s> = token_manual::Lexer<'s>;
+
+/// Returns a lexer positioned before the first token of `src`.
+pub(crate) fn lexer(src: &str) -> Lexer<'_> {
+    #[cfg(all(feature = "logos", not(feature = "no-proc-macros")))]
+    {
+        logos::Logos::lexer(src)
+    }
+    #[cfg(not(all(feature = "logos", not(feature = "no-proc-macros"))))]
+    {
+        token_manual::Lexer::new(src)
+    }
+}
+
 use crate::{
     DCBORPattern, Error, Pattern, Result,
     dcbor_integration::convert_dcbor_pattern_to_envelope_pattern,
@@ -16,7 +45,7 @@ impl Pattern {
     /// Parse a pattern expression.
     pub fn parse(input: impl AsRef<str>) -> Result<Pattern> {
         let input_str = input.as_ref();
-        let mut lexer = Token::lexer(input_str);
+        let mut lexer = lexer(input_str);
 
         // Try envelope-pattern parsing first
         match meta::parse_or(&mut lexer) {
@@ -48,6 +77,35 @@ impl Pattern {
     }
 }
 
+impl Pattern {
+    /// Parse a pattern expression, falling back to [`Pattern::any`] if it is
+    /// invalid.
+    ///
+    /// Intended for configuration where a bad pattern should degrade to
+    /// matching everything rather than fail. With the `log` feature enabled,
+    /// the parse error is logged as a warning.
+    pub fn try_parse_or_any(src: &str) -> Pattern {
+        parse_or_fallback(src, Pattern::any)
+    }
+
+    /// Parse a pattern expression, falling back to a pattern that never
+    /// matches (`!*`) if it is invalid.
+    ///
+    /// With the `log` feature enabled, the parse error is logged as a
+    /// warning.
+    pub fn try_parse_or_none(src: &str) -> Pattern {
+        parse_or_fallback(src, || Pattern::not_matching(Pattern::any()))
+    }
+}
+
+fn parse_or_fallback(src: &str, fallback: impl FnOnce() -> Pattern) -> Pattern {
+    Pattern::parse(src).unwrap_or_else(|_error| {
+        #[cfg(feature = "log")]
+        log::warn!("invalid pattern {:?}: {}", src, _error);
+        fallback()
+    })
+}
+
 impl TryFrom<&str> for Pattern {
     type Error = Error;
 
@@ -0,0 +1,190 @@
+use super::{Token, lexer, meta};
+use crate::{Error, Pattern, Result};
+
+impl Pattern {
+    /// Parses each of `patterns`, returning one result per input in the same
+    /// order.
+    pub fn parse_batch(patterns: &[&str]) -> Vec<Result<Pattern>> {
+        patterns.iter().map(Pattern::parse).collect()
+    }
+
+    /// Parses each of `patterns`, stopping at the first that fails to parse
+    /// and returning its error.
+    pub fn parse_batch_all_ok(patterns: &[&str]) -> Result<Vec<Pattern>> {
+        patterns.iter().map(Pattern::parse).collect()
+    }
+}
+
+/// Parse a pattern expression, recovering from errors where possible.
+///
+/// Well-formed input is parsed exactly as by [`Pattern::parse`]. On
+/// malformed input, each operand of an `&`, `|` or `->` list that fails to
+/// parse is reported and skipped, and parsing continues with the next
+/// operand. Returns the pattern built from the operands that did parse,
+/// or `None` if nothing could be recovered, along with every error found.
+pub fn parse_pattern_lenient(src: &str) -> (Option<Pattern>, Vec<Error>) {
+    if let Ok(pattern) = Pattern::parse(src) {
+        return (Some(pattern), Vec::new());
+    }
+
+    let mut lexer = lexer(src);
+    let mut errors = Vec::new();
+    let pattern = parse_or_lenient(&mut lexer, &mut errors);
+    if lexer.next().is_some() {
+        errors.push(Error::ExtraData(lexer.span()));
+    }
+    if pattern.is_none() && errors.is_empty() {
+        errors.push(Error::EmptyInput);
+    }
+    (pattern, errors)
+}
+
+fn parse_or_lenient(
+    lexer: &mut crate::parse::Lexer,
+    errors: &mut Vec<Error>,
+) -> Option<Pattern> {
+    let mut patterns: Vec<Pattern> =
+        parse_traverse_lenient(lexer, errors).into_iter().collect();
+
+    loop {
+        let mut lookahead = lexer.clone();
+        match lookahead.next() {
+            Some(Ok(Token::Or)) => {
+                lexer.next();
+                patterns.extend(parse_traverse_lenient(lexer, errors));
+            }
+            _ => break,
+        }
+    }
+
+    match patterns.len() {
+        0 => None,
+        1 => Some(patterns.remove(0)),
+        _ => Some(Pattern::or(patterns)),
+    }
+}
+
+fn parse_traverse_lenient(
+    lexer: &mut crate::parse::Lexer,
+    errors: &mut Vec<Error>,
+) -> Option<Pattern> {
+    let mut patterns: Vec<Pattern> =
+        parse_not_lenient(lexer, errors).into_iter().collect();
+
+    loop {
+        let mut lookahead = lexer.clone();
+        match lookahead.next() {
+            Some(Ok(Token::Traverse)) => {
+                lexer.next();
+                patterns.extend(parse_not_lenient(lexer, errors));
+            }
+            Some(Ok(Token::TraverseBack)) => {
+                lexer.next();
+                let child = combine_traversal(std::mem::take(&mut patterns));
+                let parent = parse_not_lenient(lexer, errors);
+                patterns.extend(match (child, parent) {
+                    (Some(child), Some(parent)) => {
+                        Some(Pattern::traverse_back(child, parent))
+                    }
+                    (child, parent) => child.or(parent),
+                });
+            }
+            _ => break,
+        }
+    }
+
+    combine_traversal(patterns)
+}
+
+fn combine_traversal(mut patterns: Vec<Pattern>) -> Option<Pattern> {
+    match patterns.len() {
+        0 => None,
+        1 => Some(patterns.remove(0)),
+        _ => Some(Pattern::traverse(patterns)),
+    }
+}
+
+fn parse_not_lenient(
+    lexer: &mut crate::parse::Lexer,
+    errors: &mut Vec<Error>,
+) -> Option<Pattern> {
+    let mut lookahead = lexer.clone();
+    match lookahead.next() {
+        Some(Ok(Token::Not)) => {
+            lexer.next();
+            parse_not_lenient(lexer, errors).map(Pattern::not_matching)
+        }
+        _ => parse_and_lenient(lexer, errors),
+    }
+}
+
+fn parse_and_lenient(
+    lexer: &mut crate::parse::Lexer,
+    errors: &mut Vec<Error>,
+) -> Option<Pattern> {
+    let mut patterns = Vec::new();
+
+    loop {
+        let mut attempt = lexer.clone();
+        match meta::parse_primary(&mut attempt) {
+            Ok(pattern) => {
+                *lexer = attempt;
+                patterns.push(pattern);
+            }
+            Err(e) => {
+                errors.push(e);
+                skip_operand(lexer);
+            }
+        }
+
+        let mut lookahead = lexer.clone();
+        match lookahead.next() {
+            Some(Ok(Token::And)) => {
+                lexer.next();
+            }
+            _ => break,
+        }
+    }
+
+    match patterns.len() {
+        0 => None,
+        1 => Some(patterns.remove(0)),
+        _ => Some(Pattern::and(patterns)),
+    }
+}
+
+/// Skips the tokens of a malformed operand, stopping before the next
+/// top-level operator or at the end of input. Always consumes at least one
+/// token so that parsing makes progress.
+fn skip_operand(lexer: &mut crate::parse::Lexer) {
+    let mut depth = 0usize;
+    let mut first = true;
+    loop {
+        let mut lookahead = lexer.clone();
+        let token = match lookahead.next() {
+            None => return,
+            Some(token) => token,
+        };
+        if !first
+            && depth == 0
+            && matches!(
+                token,
+                Ok(Token::And
+                    | Token::Or
+                    | Token::Traverse
+                    | Token::TraverseBack)
+            )
+        {
+            return;
+        }
+        match token {
+            Ok(Token::ParenOpen | Token::BracketOpen) => depth += 1,
+            Ok(Token::ParenClose | Token::BracketClose) => {
+                depth = depth.saturating_sub(1)
+            }
+            _ => {}
+        }
+        lexer.next();
+        first = false;
+    }
+}
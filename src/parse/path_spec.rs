@@ -0,0 +1,153 @@
+//! A minimal XPath-like path specification language.
+
+use crate::{Error, Pattern, Result};
+
+impl Pattern {
+    /// Builds a traversal from a path specification written as steps
+    /// separated by `/`, for users familiar with XPath or JSONPath. For
+    /// example, `subject/assertion[pred='age']/object` is equivalent to
+    /// `subj -> assertpred("age") -> obj`.
+    ///
+    /// The steps are:
+    ///
+    /// - `subject`, `predicate`, `object`: `subj`, `pred`, and `obj`.
+    /// - `assertion`: `assert`, optionally followed by `[pred=value]`,
+    ///   `[obj=value]`, or both, to match the assertion's predicate or object.
+    /// - `wrapped`, `unwrap`: `wrapped` and `unwrap`.
+    ///
+    /// A value in single or double quotes matches that text exactly. Any
+    /// other value is parsed as a pattern expression, as in
+    /// `assertion[obj=>=18]`. A specification with a single step returns
+    /// that step's pattern rather than a traversal.
+    ///
+    /// Returns [`Error::InvalidPathSpec`] if the specification is malformed,
+    /// or the error from parsing a value as a pattern expression.
+    pub fn at_path(path_spec: &str) -> Result<Pattern> {
+        let mut patterns = split_outside_quotes(path_spec, '/')?
+            .into_iter()
+            .map(|step| parse_step(step.trim()))
+            .collect::<Result<Vec<_>>>()?;
+        if patterns.len() == 1 {
+            Ok(patterns.remove(0))
+        } else {
+            Ok(Pattern::traverse(patterns))
+        }
+    }
+}
+
+fn invalid(message: impl Into<String>) -> Error {
+    Error::InvalidPathSpec(message.into())
+}
+
+/// Splits `s` at each `separator` that is outside quotes and brackets.
+fn split_outside_quotes(s: &str, separator: char) -> Result<Vec<&str>> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut quote = None;
+    let mut depth = 0usize;
+    for (i, c) in s.char_indices() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, _) if c == separator && depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + c.len_utf8();
+            }
+            (None, '[') => depth += 1,
+            (None, ']') => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or_else(|| invalid("unmatched `]`"))?
+            }
+            (None, _) => {}
+        }
+    }
+    if quote.is_some() {
+        return Err(invalid("unterminated quote"));
+    }
+    if depth > 0 {
+        return Err(invalid("unmatched `[`"));
+    }
+    parts.push(&s[start..]);
+    Ok(parts)
+}
+
+fn parse_step(step: &str) -> Result<Pattern> {
+    let (name, filters) = match step.find('[') {
+        Some(i) => (step[..i].trim(), parse_filters(&step[i..])?),
+        None => (step, Vec::new()),
+    };
+    if name != "assertion" && !filters.is_empty() {
+        return Err(invalid(format!("`{}` does not take filters", name)));
+    }
+    match name {
+        "subject" => Ok(Pattern::any_subject()),
+        "predicate" => Ok(Pattern::any_predicate()),
+        "object" => Ok(Pattern::any_object()),
+        "wrapped" => Ok(Pattern::wrapped()),
+        "unwrap" => Ok(Pattern::unwrap()),
+        "assertion" => {
+            let mut pred = None;
+            let mut obj = None;
+            for (key, value) in filters {
+                let slot = match key {
+                    "pred" => &mut pred,
+                    "obj" => &mut obj,
+                    _ => {
+                        return Err(invalid(format!(
+                            "unknown filter `{}`",
+                            key
+                        )));
+                    }
+                };
+                if slot.replace(value).is_some() {
+                    return Err(invalid(format!("duplicate filter `{}`", key)));
+                }
+            }
+            Ok(match (pred, obj) {
+                (None, None) => Pattern::any_assertion(),
+                (Some(pred), None) => Pattern::assertion_with_predicate(pred),
+                (None, Some(obj)) => Pattern::assertion_with_object(obj),
+                (Some(pred), Some(obj)) => {
+                    Pattern::assertion_with_predicate_and_object(pred, obj)
+                }
+            })
+        }
+        "" => Err(invalid("empty step")),
+        _ => Err(invalid(format!("unknown step `{}`", name))),
+    }
+}
+
+/// Parses a sequence of `[key=value]` filters.
+fn parse_filters(mut s: &str) -> Result<Vec<(&str, Pattern)>> {
+    let mut filters = Vec::new();
+    while !s.is_empty() {
+        let rest = s
+            .strip_prefix('[')
+            .ok_or_else(|| invalid(format!("expected `[` at `{}`", s)))?;
+        let parts = split_outside_quotes(rest, ']')?;
+        if parts.len() < 2 {
+            return Err(invalid("unmatched `[`"));
+        }
+        let inner = parts[0];
+        s = rest[inner.len() + 1..].trim_start();
+        let (key, value) = inner
+            .split_once('=')
+            .ok_or_else(|| invalid(format!("expected `=` in `[{}]`", inner)))?;
+        filters.push((key.trim(), parse_value(value.trim())?));
+    }
+    Ok(filters)
+}
+
+fn parse_value(value: &str) -> Result<Pattern> {
+    for quote in ['\'', '"'] {
+        if let Some(text) = value
+            .strip_prefix(quote)
+            .and_then(|rest| rest.strip_suffix(quote))
+        {
+            return Ok(Pattern::text(text));
+        }
+    }
+    Pattern::parse(value)
+}
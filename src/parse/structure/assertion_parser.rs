@@ -1,8 +1,86 @@
-use super::super::Token;
-use crate::{Pattern, Result};
+use super::super::{Token, meta, utils};
+use crate::{Error, Pattern, Result};
 
 pub(crate) fn parse_assertion(
-    _lexer: &mut logos::Lexer<Token>,
+    lexer: &mut crate::parse::Lexer,
 ) -> Result<Pattern> {
-    Ok(Pattern::any_assertion())
+    let mut lookahead = lexer.clone();
+    match lookahead.next() {
+        Some(Ok(Token::ParenOpen)) => {}
+        Some(Ok(Token::BracketOpen)) => {
+            lexer.next();
+            return parse_nth_assertion(lexer);
+        }
+        _ => return Ok(Pattern::any_assertion()),
+    }
+    // assert ( pred: pattern, obj: pattern )
+    lexer.next();
+    let predicate = parse_labeled(lexer, "pred")?;
+    match lexer.next() {
+        Some(Ok(Token::Comma)) => {}
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::UnexpectedEndOfInput),
+    }
+    let object = parse_labeled(lexer, "obj")?;
+    match lexer.next() {
+        Some(Ok(Token::ParenClose)) => Ok(
+            Pattern::assertion_with_predicate_and_object(predicate, object),
+        ),
+        Some(Ok(t)) => Err(Error::UnexpectedToken(Box::new(t), lexer.span())),
+        Some(Err(e)) => Err(e),
+        None => Err(Error::ExpectedCloseParen(lexer.span())),
+    }
+}
+
+/// Parses `n ] ( pattern )`, after `assert [`.
+fn parse_nth_assertion(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
+    let n = match lexer.next() {
+        Some(Ok(Token::Integer(Ok(n)))) => usize::try_from(n).ok(),
+        Some(Ok(Token::UnsignedInteger(Ok(n)))) => Some(n),
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::UnexpectedEndOfInput),
+    }
+    .ok_or_else(|| Error::InvalidNumberFormat(lexer.span()))?;
+    for expected in [Token::BracketClose, Token::ParenOpen] {
+        match lexer.next() {
+            Some(Ok(t)) if t == expected => {}
+            Some(Ok(t)) => {
+                return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+            }
+            Some(Err(e)) => return Err(e),
+            None => return Err(Error::UnexpectedEndOfInput),
+        }
+    }
+    let pattern = meta::parse_or(lexer)?;
+    match lexer.next() {
+        Some(Ok(Token::ParenClose)) => Ok(Pattern::nth_assertion(n, pattern)),
+        Some(Ok(t)) => Err(Error::UnexpectedToken(Box::new(t), lexer.span())),
+        Some(Err(e)) => Err(e),
+        None => Err(Error::ExpectedCloseParen(lexer.span())),
+    }
+}
+
+fn parse_labeled(
+    lexer: &mut crate::parse::Lexer,
+    label: &str,
+) -> Result<Pattern> {
+    match utils::parse_label(lexer.remainder(), label) {
+        Some(consumed) => {
+            lexer.bump(consumed);
+            meta::parse_or(lexer)
+        }
+        None => match lexer.next() {
+            Some(Ok(t)) => {
+                Err(Error::UnexpectedToken(Box::new(t), lexer.span()))
+            }
+            Some(Err(e)) => Err(e),
+            None => Err(Error::UnexpectedEndOfInput),
+        },
+    }
 }
@@ -2,7 +2,7 @@ use super::super::{Token, meta};
 use crate::{Error, Pattern, Result};
 
 pub(crate) fn parse_assertion_pred(
-    lexer: &mut logos::Lexer<Token>,
+    lexer: &mut crate::parse::Lexer,
 ) -> Result<Pattern> {
     match lexer.next() {
         Some(Ok(Token::ParenOpen)) => {
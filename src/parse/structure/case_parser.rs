@@ -0,0 +1,30 @@
+use super::super::Token;
+use crate::{Error, Pattern, Result};
+
+pub(crate) fn parse_case(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
+    match lexer.next() {
+        Some(Ok(Token::ParenOpen)) => {}
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::ExpectedOpenParen(lexer.span())),
+    }
+    let pattern = match lexer.next() {
+        Some(Ok(Token::StringLiteral(Ok(name)))) => {
+            Pattern::matches_case(&name)?
+        }
+        Some(Ok(Token::StringLiteral(Err(e)))) => return Err(e),
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::UnexpectedEndOfInput),
+    };
+    match lexer.next() {
+        Some(Ok(Token::ParenClose)) => Ok(pattern),
+        Some(Ok(t)) => Err(Error::UnexpectedToken(Box::new(t), lexer.span())),
+        Some(Err(e)) => Err(e),
+        None => Err(Error::ExpectedCloseParen(lexer.span())),
+    }
+}
@@ -1,8 +1,6 @@
-use super::super::Token;
+use super::super::Lexer;
 use crate::{Pattern, Result};
 
-pub(crate) fn parse_compressed(
-    _lexer: &mut logos::Lexer<Token>,
-) -> Result<Pattern> {
+pub(crate) fn parse_compressed(_lexer: &mut Lexer) -> Result<Pattern> {
     Ok(Pattern::compressed())
 }
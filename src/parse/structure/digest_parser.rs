@@ -4,7 +4,7 @@ use bc_envelope::prelude::*;
 use super::super::Token;
 use crate::{Error, Pattern, Result};
 
-pub(crate) fn parse_digest(lexer: &mut logos::Lexer<Token>) -> Result<Pattern> {
+pub(crate) fn parse_digest(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
     match lexer.next() {
         Some(Ok(Token::ParenOpen)) => {
             let src = lexer.remainder();
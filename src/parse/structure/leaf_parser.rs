@@ -0,0 +1,25 @@
+use super::super::{Token, utils};
+use crate::{Error, Pattern, Result};
+
+pub(crate) fn parse_leaf(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
+    let mut lookahead = lexer.clone();
+    match lookahead.next() {
+        Some(Ok(Token::ParenOpen)) => {
+            // leaf ( value )
+            lexer.next();
+            let (value, consumed) = utils::parse_cbor_inner(lexer.remainder())?;
+            lexer.bump(consumed);
+            match lexer.next() {
+                Some(Ok(Token::ParenClose)) => {
+                    Ok(Pattern::and(vec![Pattern::leaf(), value]))
+                }
+                Some(Ok(t)) => {
+                    Err(Error::UnexpectedToken(Box::new(t), lexer.span()))
+                }
+                Some(Err(e)) => Err(e),
+                None => Err(Error::ExpectedCloseParen(lexer.span())),
+            }
+        }
+        _ => Ok(Pattern::leaf()),
+    }
+}
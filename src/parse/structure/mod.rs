@@ -3,27 +3,35 @@
 mod assertion_obj_parser;
 mod assertion_parser;
 mod assertion_pred_parser;
+mod case_parser;
 mod compressed_parser;
 mod digest_parser;
 mod elided_parser;
 mod encrypted_parser;
+mod leaf_parser;
 mod node_parser;
 mod object_parser;
 mod obscured_parser;
 mod predicate_parser;
 mod subject_parser;
+mod untag_parser;
 mod wrapped_parser;
 
 pub(crate) use assertion_obj_parser::parse_assertion_obj;
 pub(crate) use assertion_parser::parse_assertion;
 pub(crate) use assertion_pred_parser::parse_assertion_pred;
+pub(crate) use case_parser::parse_case;
 pub(crate) use compressed_parser::parse_compressed;
 pub(crate) use digest_parser::parse_digest;
 pub(crate) use elided_parser::parse_elided;
 pub(crate) use encrypted_parser::parse_encrypted;
+pub(crate) use leaf_parser::parse_leaf;
 pub(crate) use node_parser::parse_node;
 pub(crate) use object_parser::parse_object;
 pub(crate) use obscured_parser::parse_obscured;
-pub(crate) use predicate_parser::parse_predicate;
+pub(crate) use predicate_parser::{
+    parse_predicate, parse_predicate_or_subject,
+};
 pub(crate) use subject_parser::parse_subject;
+pub(crate) use untag_parser::parse_untag;
 pub(crate) use wrapped_parser::{parse_unwrap, parse_wrapped};
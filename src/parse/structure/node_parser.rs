@@ -1,11 +1,66 @@
-use super::super::Token;
+use super::super::{Token, meta, utils};
 use crate::{Error, Pattern, Result};
 
-pub(crate) fn parse_node(lexer: &mut logos::Lexer<Token>) -> Result<Pattern> {
+pub(crate) fn parse_node(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
     let mut lookahead = lexer.clone();
     match lookahead.next() {
         Some(Ok(Token::ParenOpen)) => {
             lexer.next();
+            if let Some(consumed) = utils::parse_label(lexer.remainder(), "all")
+            {
+                lexer.bump(consumed);
+                let pattern = meta::parse_or(lexer)?;
+                return match lexer.next() {
+                    Some(Ok(Token::ParenClose)) => {
+                        Ok(Pattern::node_matching_all_assertions(pattern))
+                    }
+                    Some(Ok(t)) => {
+                        Err(Error::UnexpectedToken(Box::new(t), lexer.span()))
+                    }
+                    Some(Err(e)) => Err(e),
+                    None => Err(Error::ExpectedCloseParen(lexer.span())),
+                };
+            }
+            if let Some(consumed) =
+                utils::parse_label(lexer.remainder(), "preds_in")
+            {
+                lexer.bump(consumed);
+                let predicates = parse_pattern_list(lexer)?;
+                return match lexer.next() {
+                    Some(Ok(Token::ParenClose)) => Ok(
+                        Pattern::all_assertions_have_predicate_in(predicates),
+                    ),
+                    Some(Ok(t)) => {
+                        Err(Error::UnexpectedToken(Box::new(t), lexer.span()))
+                    }
+                    Some(Err(e)) => Err(e),
+                    None => Err(Error::ExpectedCloseParen(lexer.span())),
+                };
+            }
+            if let Some(consumed) =
+                utils::parse_label(lexer.remainder(), "exactly")
+            {
+                lexer.bump(consumed);
+                let patterns = parse_pattern_list(lexer)?;
+                return match lexer.next() {
+                    Some(Ok(Token::ParenClose)) => {
+                        Ok(Pattern::node_with_exact_assertions(patterns))
+                    }
+                    Some(Ok(t)) => {
+                        Err(Error::UnexpectedToken(Box::new(t), lexer.span()))
+                    }
+                    Some(Err(e)) => Err(e),
+                    None => Err(Error::ExpectedCloseParen(lexer.span())),
+                };
+            }
+            // node ( count ( pattern ) : {n,m} )
+            let remainder = lexer.remainder();
+            if let Some(rest) = remainder.trim_start().strip_prefix("count")
+                && rest.trim_start().starts_with('(')
+            {
+                lexer.bump(remainder.len() - rest.len());
+                return parse_predicate_count(lexer);
+            }
             match lexer.next() {
                 Some(Ok(Token::Range(res))) => {
                     let range = res?;
@@ -34,3 +89,80 @@ pub(crate) fn parse_node(lexer: &mut logos::Lexer<Token>) -> Result<Pattern> {
         _ => Ok(Pattern::any_node()),
     }
 }
+
+/// Parses `( pattern ) : {n,m} )`, after `node ( count`.
+fn parse_predicate_count(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
+    match lexer.next() {
+        Some(Ok(Token::ParenOpen)) => {}
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::UnexpectedEndOfInput),
+    }
+    let pred = meta::parse_or(lexer)?;
+    match lexer.next() {
+        Some(Ok(Token::ParenClose)) => {}
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::ExpectedCloseParen(lexer.span())),
+    }
+    let remainder = lexer.remainder();
+    let Some(rest) = remainder.trim_start().strip_prefix(':') else {
+        let end = lexer.span().end;
+        return Err(Error::InvalidPattern(end..end));
+    };
+    lexer.bump(remainder.len() - rest.len());
+    let range = match lexer.next() {
+        Some(Ok(Token::Range(res))) => res?,
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::UnexpectedEndOfInput),
+    };
+    match lexer.next() {
+        Some(Ok(Token::ParenClose)) => {
+            Ok(Pattern::node_assertion_predicate_count(
+                pred,
+                range.min(),
+                range.max(),
+            ))
+        }
+        Some(Ok(t)) => Err(Error::UnexpectedToken(Box::new(t), lexer.span())),
+        Some(Err(e)) => Err(e),
+        None => Err(Error::ExpectedCloseParen(lexer.span())),
+    }
+}
+
+/// Parses `[ pattern, pattern, ... ]`.
+fn parse_pattern_list(lexer: &mut crate::parse::Lexer) -> Result<Vec<Pattern>> {
+    match lexer.next() {
+        Some(Ok(Token::BracketOpen)) => {}
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::UnexpectedEndOfInput),
+    }
+    let mut patterns = Vec::new();
+    let mut lookahead = lexer.clone();
+    if matches!(lookahead.next(), Some(Ok(Token::BracketClose))) {
+        lexer.next();
+        return Ok(patterns);
+    }
+    loop {
+        patterns.push(meta::parse_or(lexer)?);
+        match lexer.next() {
+            Some(Ok(Token::Comma)) => {}
+            Some(Ok(Token::BracketClose)) => return Ok(patterns),
+            Some(Ok(t)) => {
+                return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+            }
+            Some(Err(e)) => return Err(e),
+            None => return Err(Error::ExpectedCloseBracket(lexer.span())),
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use super::super::{Token, meta};
 use crate::{Error, Pattern, Result};
 
-pub(crate) fn parse_object(lexer: &mut logos::Lexer<Token>) -> Result<Pattern> {
+pub(crate) fn parse_object(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
     let mut lookahead = lexer.clone();
     match lookahead.next() {
         Some(Ok(Token::ParenOpen)) => {
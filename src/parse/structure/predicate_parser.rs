@@ -2,7 +2,7 @@ use super::super::{Token, meta};
 use crate::{Error, Pattern, Result};
 
 pub(crate) fn parse_predicate(
-    lexer: &mut logos::Lexer<Token>,
+    lexer: &mut crate::parse::Lexer,
 ) -> Result<Pattern> {
     let mut lookahead = lexer.clone();
     match lookahead.next() {
@@ -21,3 +21,23 @@ pub(crate) fn parse_predicate(
         _ => Ok(Pattern::any_predicate()),
     }
 }
+
+pub(crate) fn parse_predicate_or_subject(
+    lexer: &mut crate::parse::Lexer,
+) -> Result<Pattern> {
+    match lexer.next() {
+        Some(Ok(Token::ParenOpen)) => {}
+        Some(Ok(t)) => {
+            return Err(Error::UnexpectedToken(Box::new(t), lexer.span()));
+        }
+        Some(Err(e)) => return Err(e),
+        None => return Err(Error::ExpectedOpenParen(lexer.span())),
+    }
+    let pat = meta::parse_or(lexer)?;
+    match lexer.next() {
+        Some(Ok(Token::ParenClose)) => Ok(Pattern::predicate_or_subject(pat)),
+        Some(Ok(t)) => Err(Error::UnexpectedToken(Box::new(t), lexer.span())),
+        Some(Err(e)) => Err(e),
+        None => Err(Error::ExpectedCloseParen(lexer.span())),
+    }
+}
@@ -2,7 +2,7 @@ use super::super::{Token, meta};
 use crate::{Error, Pattern, Result};
 
 pub(crate) fn parse_subject(
-    lexer: &mut logos::Lexer<Token>,
+    lexer: &mut crate::parse::Lexer,
 ) -> Result<Pattern> {
     let mut lookahead = lexer.clone();
     match lookahead.next() {
@@ -1,14 +1,53 @@
-use super::super::{Token, meta};
+use super::super::{Token, meta, utils};
 use crate::{Error, Pattern, Result};
 
 pub(crate) fn parse_wrapped(
-    _lexer: &mut logos::Lexer<Token>,
+    lexer: &mut crate::parse::Lexer,
 ) -> Result<Pattern> {
-    // Simple WRAPPED pattern without arguments
-    Ok(Pattern::wrapped())
+    let mut lookahead = lexer.clone();
+    match lookahead.next() {
+        Some(Ok(Token::ParenOpen)) => {
+            // wrapped ( content: pattern ) | wrapped ( match: pattern )
+            lexer.next();
+            let remainder = lexer.remainder();
+            let (consumed, descends) = if let Some(n) =
+                utils::parse_label(remainder, "content")
+            {
+                (n, true)
+            } else if let Some(n) = utils::parse_label(remainder, "match") {
+                (n, false)
+            } else {
+                return match lexer.next() {
+                    Some(Ok(t)) => {
+                        Err(Error::UnexpectedToken(Box::new(t), lexer.span()))
+                    }
+                    Some(Err(e)) => Err(e),
+                    None => Err(Error::UnexpectedEndOfInput),
+                };
+            };
+            lexer.bump(consumed);
+            let pat = meta::parse_or(lexer)?;
+            match lexer.next() {
+                Some(Ok(Token::ParenClose)) => Ok(if descends {
+                    Pattern::unwrap_matching(pat)
+                } else {
+                    Pattern::wrapped_content_matching(pat)
+                }),
+                Some(Ok(t)) => {
+                    Err(Error::UnexpectedToken(Box::new(t), lexer.span()))
+                }
+                Some(Err(e)) => Err(e),
+                None => Err(Error::ExpectedCloseParen(lexer.span())),
+            }
+        }
+        _ => {
+            // Simple WRAPPED pattern without arguments
+            Ok(Pattern::wrapped())
+        }
+    }
 }
 
-pub(crate) fn parse_unwrap(lexer: &mut logos::Lexer<Token>) -> Result<Pattern> {
+pub(crate) fn parse_unwrap(lexer: &mut crate::parse::Lexer) -> Result<Pattern> {
     let mut lookahead = lexer.clone();
     match lookahead.next() {
         Some(Ok(Token::ParenOpen)) => {
@@ -1,226 +1,316 @@
-use logos::{Lexer, Logos};
+#[cfg(feature = "logos")]
+use logos::Logos;
 
 use crate::{Error, Quantifier, Reluctance, Result};
 
+/// The lexer state the token callbacks need, so that they can be shared by
+/// the Logos lexer and the hand-written one.
+pub(crate) trait Cursor<'s> {
+    /// The source text after the current token.
+    fn remainder(&self) -> &'s str;
+
+    /// Extends the current token by `n` bytes.
+    fn bump(&mut self, n: usize);
+
+    /// The byte range of the current token.
+    fn span(&self) -> std::ops::Range<usize>;
+}
+
+#[cfg(feature = "logos")]
+impl<'s> Cursor<'s> for logos::Lexer<'s, Token> {
+    fn remainder(&self) -> &'s str { logos::Lexer::remainder(self) }
+
+    fn bump(&mut self, n: usize) { logos::Lexer::bump(self, n) }
+
+    fn span(&self) -> std::ops::Range<usize> { logos::Lexer::span(self) }
+}
+
 /// Tokens for the Gordian Envelope pattern syntax.
-#[derive(Debug, Clone, Logos, PartialEq)]
+///
+/// The lexer is generated by Logos, unless the `no-proc-macros` feature is
+/// enabled, in which case the hand-written lexer in `token_manual` is used
+/// instead. The callbacks below are shared by both.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "logos", derive(Logos))]
 #[rustfmt::skip]
-#[logos(error = Error)]
-#[logos(skip r"[ \t\r\n\f]+")]
+#[cfg_attr(feature = "logos", logos(error = Error))]
+#[cfg_attr(feature = "logos", logos(skip r"[ \t\r\n\f]+"))]
 pub enum Token {
     // Meta Pattern Operators
-    #[token("&")]
+    #[cfg_attr(feature = "logos", token("&"))]
     And,
 
-    #[token("|")]
+    #[cfg_attr(feature = "logos", token("|"))]
     Or,
 
-    #[token("!")]
+    #[cfg_attr(feature = "logos", token("!"))]
     Not,
 
-    #[token("->", priority = 2)]
+    #[cfg_attr(feature = "logos", token("->", priority = 2))]
     Traverse,
 
-    #[token("*")]
+    /// `<-`, produced by the `LessThan` callback.
+    TraverseBack,
+
+    #[cfg_attr(feature = "logos", token("*"))]
     RepeatZeroOrMore,
 
-    #[token("*?")]
+    #[cfg_attr(feature = "logos", token("*?"))]
     RepeatZeroOrMoreLazy,
 
-    #[token("*+")]
+    #[cfg_attr(feature = "logos", token("*+"))]
     RepeatZeroOrMorePossessive,
 
-    #[token("+")]
+    #[cfg_attr(feature = "logos", token("+"))]
     RepeatOneOrMore,
 
-    #[token("+?")]
+    #[cfg_attr(feature = "logos", token("+?"))]
     RepeatOneOrMoreLazy,
 
-    #[token("++")]
+    #[cfg_attr(feature = "logos", token("++"))]
     RepeatOneOrMorePossessive,
 
-    #[token("?")]
+    #[cfg_attr(feature = "logos", token("?"))]
     RepeatZeroOrOne,
 
-    #[token("??")]
+    #[cfg_attr(feature = "logos", token("??"))]
     RepeatZeroOrOneLazy,
 
-    #[token("?+")]
+    #[cfg_attr(feature = "logos", token("?+"))]
     RepeatZeroOrOnePossessive,
 
     // Structure Pattern Keywords
-    #[token("assert")]
+    #[cfg_attr(feature = "logos", token("assert"))]
     Assertion,
 
-    #[token("assertpred")]
+    #[cfg_attr(feature = "logos", token("assertpred"))]
     AssertionPred,
 
-    #[token("assertobj")]
+    #[cfg_attr(feature = "logos", token("assertobj"))]
     AssertionObj,
 
-    #[token("digest")]
+    #[cfg_attr(feature = "logos", token("digest"))]
     Digest,
 
-    #[token("node")]
+    #[cfg_attr(feature = "logos", token("node"))]
     Node,
 
-    #[token("obj")]
+    #[cfg_attr(feature = "logos", token("obj"))]
     Obj,
 
-    #[token("obscured")]
+    #[cfg_attr(feature = "logos", token("obscured"))]
     Obscured,
 
-    #[token("elided")]
+    #[cfg_attr(feature = "logos", token("elided"))]
     Elided,
 
-    #[token("encrypted")]
+    #[cfg_attr(feature = "logos", token("encrypted"))]
     Encrypted,
 
-    #[token("compressed")]
+    #[cfg_attr(feature = "logos", token("compressed"))]
     Compressed,
 
-    #[token("pred")]
+    #[cfg_attr(feature = "logos", token("pred"))]
     Pred,
 
-    #[token("subj")]
+    #[cfg_attr(feature = "logos", token("pred_or_subj"))]
+    PredOrSubject,
+
+    #[cfg_attr(feature = "logos", token("subj"))]
     Subject,
 
-    #[token("wrapped")]
+    #[cfg_attr(feature = "logos", token("wrapped"))]
     Wrapped,
 
-    #[token("unwrap")]
+    #[cfg_attr(feature = "logos", token("unwrap"))]
     Unwrap,
 
-    #[token("search")]
+    #[cfg_attr(feature = "logos", token("untag"))]
+    Untag,
+
+    #[cfg_attr(feature = "logos", token("search"))]
     Search,
 
+    #[cfg_attr(feature = "logos", token("dedup"))]
+    Dedup,
+
+    #[cfg_attr(feature = "logos", token("unique"))]
+    Unique,
+
+    #[cfg_attr(feature = "logos", token("case"))]
+    Case,
+
+    #[cfg_attr(feature = "logos", token("prio"))]
+    Prio,
+
+    #[cfg_attr(feature = "logos", token("bfs"))]
+    Bfs,
+
+    #[cfg_attr(feature = "logos", token("while"))]
+    While,
+
+    #[cfg_attr(feature = "logos", token("count"))]
+    Count,
+
     // Leaf Pattern Keywords
-    #[token("bstr")]
+    #[cfg_attr(feature = "logos", token("arr"))]
+    Arr,
+
+    #[cfg_attr(feature = "logos", token("bstr"))]
     ByteString,
 
-    #[token("leaf")]
+    #[cfg_attr(feature = "logos", token("map"))]
+    Map,
+
+    #[cfg_attr(feature = "logos", token("leaf"))]
     Leaf,
 
-    #[token("cbor")]
+    #[cfg_attr(feature = "logos", token("cbor"))]
     Cbor,
 
-    #[token("date")]
+    #[cfg_attr(feature = "logos", token("cbor_bytes"))]
+    CborBytes,
+
+    #[cfg_attr(feature = "logos", token("date"))]
     DateKeyword,
 
-    #[token("known")]
+    #[cfg_attr(feature = "logos", token("known"))]
     Known,
 
-    #[token("null")]
+    #[cfg_attr(feature = "logos", token("kv_or_text"))]
+    KnownValueOrText,
+
+    #[cfg_attr(feature = "logos", token("null"))]
     Null,
 
-    #[token("number")]
+    #[cfg_attr(feature = "logos", token("number"))]
     NumberKeyword,
 
-    #[token("tagged")]
+    #[cfg_attr(feature = "logos", token("integer"))]
+    IntegerKeyword,
+
+    #[cfg_attr(feature = "logos", token("tagged"))]
     Tagged,
 
     // Meta Pattern Keywords
 
     // Special literals
-    #[token("bool")]
+    #[cfg_attr(feature = "logos", token("bool"))]
     BoolKeyword,
 
-    #[token("true")]
+    #[cfg_attr(feature = "logos", token("true"))]
     BoolTrue,
 
-    #[token("false")]
+    #[cfg_attr(feature = "logos", token("false"))]
     BoolFalse,
 
-    #[token("text")]
+    #[cfg_attr(feature = "logos", token("text"))]
     TextKeyword,
 
-    #[token("NaN")]
+    #[cfg_attr(feature = "logos", token("text_glob"))]
+    TextGlob,
+
+    #[cfg_attr(feature = "logos", token("NaN"))]
     NaN,
 
-    #[token("\"", parse_string_literal_token)]
+    #[cfg_attr(feature = "logos", token("\"", parse_string_literal_token))]
     StringLiteral(Result<String>),
 
     // Grouping and Range delimiters
-    #[token("(")]
+    #[cfg_attr(feature = "logos", token("("))]
     ParenOpen,
 
-    #[token(")")]
+    #[cfg_attr(feature = "logos", token(")"))]
     ParenClose,
 
-    #[token("[")]
+    #[cfg_attr(feature = "logos", token("["))]
     BracketOpen,
 
-    #[token("]")]
+    #[cfg_attr(feature = "logos", token("]"))]
     BracketClose,
 
-    #[token(",")]
+    #[cfg_attr(feature = "logos", token(","))]
     Comma,
 
-    #[token("...")]
+    #[cfg_attr(feature = "logos", token("..."))]
     Ellipsis,
 
-    #[token(">=")]
+    #[cfg_attr(feature = "logos", token(">="))]
     GreaterThanOrEqual,
 
-    #[token("<=")]
+    #[cfg_attr(feature = "logos", token("<="))]
     LessThanOrEqual,
 
-    #[token(">", priority = 1)]
+    #[cfg_attr(feature = "logos", token(">", priority = 1))]
     GreaterThan,
 
-    #[token("<")]
+    #[cfg_attr(feature = "logos", token("<", parse_less_than))]
     LessThan,
 
-    #[regex(r"-?(?:[1-9]\d*|0)", priority = 4, callback = |lex|
+    #[cfg_attr(feature = "logos", regex(r"-?(?:[1-9]\d*|0)", priority = 4, callback = |lex|
         lex.slice().parse::<i64>().map_err(|_| Error::InvalidNumberFormat(lex.span()))
-    )]
+    ))]
     Integer(Result<i64>),
 
-    #[regex(r"[1-9]\d*|0", priority = 3, callback = |lex|
+    #[cfg_attr(feature = "logos", regex(r"[1-9]\d*|0", priority = 3, callback = |lex|
         lex.slice().parse::<usize>().map_err(|_| Error::InvalidNumberFormat(lex.span()))
-    )]
+    ))]
     UnsignedInteger(Result<usize>),
 
-    #[regex(r"-?(?:[1-9]\d*|0)\.\d+(?:[eE][+-]?\d+)?", priority = 2, callback = |lex|
+    #[cfg_attr(feature = "logos", regex(r"-?(?:[1-9]\d*|0)\.\d+(?:[eE][+-]?\d+)?", priority = 2, callback = |lex|
         lex.slice().parse::<f64>().map_err(|_| Error::InvalidNumberFormat(lex.span()))
-    )]
+    ))]
     Float(Result<f64>),
 
-    #[token("Infinity")]
+    #[cfg_attr(feature = "logos", token("Infinity"))]
     Infinity,
 
-    #[token("-Infinity")]
+    #[cfg_attr(feature = "logos", token("-Infinity"))]
     NegativeInfinity,
 
-    #[regex(r"@[a-zA-Z_][a-zA-Z0-9_]*", |lex|
+    #[cfg_attr(feature = "logos", regex(r"@[a-zA-Z_][a-zA-Z0-9_]*", |lex|
         lex.slice()[1..].to_string()
-    )]
+    ))]
     GroupName(String),
 
-    #[token("/", parse_regex)]
+    #[cfg_attr(feature = "logos", token("/", parse_regex))]
     Regex(Result<String>),
 
-    #[token("h'", parse_hex_pattern)]
+    #[cfg_attr(feature = "logos", token("h'", parse_hex_pattern))]
     HexPattern(Result<Vec<u8>>),
 
-    #[token("h'/", parse_hex_binary_regex)]
+    #[cfg_attr(feature = "logos", token("h'/", parse_hex_binary_regex))]
     HexBinaryRegex(Result<String>),
 
-    #[token("date'", parse_date_pattern)]
+    #[cfg_attr(feature = "logos", token("date'", parse_date_pattern))]
     DatePattern(Result<String>),
 
-    #[token("{", parse_range)]
+    #[cfg_attr(feature = "logos", token("{", parse_range))]
     Range(Result<Quantifier>),
 
-    #[token("'", parse_single_quoted_pattern)]
+    #[cfg_attr(feature = "logos", token("'", parse_single_quoted_pattern))]
     SingleQuotedPattern(Result<String>),
 
-    #[token("'/", parse_single_quoted_regex)]
+    #[cfg_attr(feature = "logos", token("'/", parse_single_quoted_regex))]
     SingleQuotedRegex(Result<String>),
 }
 
+/// Callback used by the `LessThan` variant above.
+///
+/// `<-` is a back traversal unless a digit follows it, in which case it is a
+/// comparison with a negative number, so `<-5` still means `< -5`.
+pub(crate) fn parse_less_than<'s>(lex: &mut impl Cursor<'s>) -> Token {
+    match lex.remainder().strip_prefix('-') {
+        Some(rest) if !rest.starts_with(|c: char| c.is_ascii_digit()) => {
+            lex.bump(1);
+            Token::TraverseBack
+        }
+        _ => Token::LessThan,
+    }
+}
+
 /// Callback used by the `Regex` variant above.
-fn parse_regex(lex: &mut Lexer<Token>) -> Result<String> {
+pub(crate) fn parse_regex<'s>(lex: &mut impl Cursor<'s>) -> Result<String> {
     let src = lex.remainder(); // everything after the first '/'
     let mut escape = false;
 
@@ -245,7 +335,9 @@ fn parse_regex(lex: &mut Lexer<Token>) -> Result<String> {
 }
 
 /// Callback used by the `HexPattern` variant above.
-fn parse_hex_pattern(lex: &mut Lexer<Token>) -> Result<Vec<u8>> {
+pub(crate) fn parse_hex_pattern<'s>(
+    lex: &mut impl Cursor<'s>,
+) -> Result<Vec<u8>> {
     let src = lex.remainder(); // everything after the first h'
 
     // Parse hex digits until we find the closing '
@@ -267,7 +359,9 @@ fn parse_hex_pattern(lex: &mut Lexer<Token>) -> Result<Vec<u8>> {
 }
 
 /// Callback used by the `HexBinaryRegex` variant above.
-fn parse_hex_binary_regex(lex: &mut Lexer<Token>) -> Result<String> {
+pub(crate) fn parse_hex_binary_regex<'s>(
+    lex: &mut impl Cursor<'s>,
+) -> Result<String> {
     let src = lex.remainder(); // everything after the first h'/
     let mut escape = false;
 
@@ -295,7 +389,9 @@ fn parse_hex_binary_regex(lex: &mut Lexer<Token>) -> Result<String> {
 }
 
 /// Callback used by the `DatePattern` variant above.
-fn parse_date_pattern(lex: &mut Lexer<Token>) -> Result<String> {
+pub(crate) fn parse_date_pattern<'s>(
+    lex: &mut impl Cursor<'s>,
+) -> Result<String> {
     let src = lex.remainder(); // everything after the first date'
 
     // Parse content until we find the closing '
@@ -312,7 +408,7 @@ fn parse_date_pattern(lex: &mut Lexer<Token>) -> Result<String> {
     Err(Error::UnterminatedRegex(lex.span()))
 }
 
-fn parse_range(lex: &mut Lexer<Token>) -> Result<Quantifier> {
+pub(crate) fn parse_range<'s>(lex: &mut impl Cursor<'s>) -> Result<Quantifier> {
     let src = lex.remainder(); // everything after the first '{'
 
     // Helper to skip whitespace inside the range specification
@@ -420,7 +516,9 @@ fn parse_range(lex: &mut Lexer<Token>) -> Result<Quantifier> {
 }
 
 /// Callback used by the `StringLiteral` variant above.
-fn parse_string_literal_token(lex: &mut Lexer<Token>) -> Result<String> {
+pub(crate) fn parse_string_literal_token<'s>(
+    lex: &mut impl Cursor<'s>,
+) -> Result<String> {
     let src = lex.remainder(); // everything after the first '"'
     let mut escape = false;
     let mut content = String::new();
@@ -472,7 +570,9 @@ fn parse_string_literal_token(lex: &mut Lexer<Token>) -> Result<String> {
 }
 
 /// Callback used by the `SingleQuotedPattern` variant above.
-fn parse_single_quoted_pattern(lex: &mut Lexer<Token>) -> Result<String> {
+pub(crate) fn parse_single_quoted_pattern<'s>(
+    lex: &mut impl Cursor<'s>,
+) -> Result<String> {
     let src = lex.remainder(); // everything after the first '
 
     // Parse content until we find the closing '
@@ -490,7 +590,9 @@ fn parse_single_quoted_pattern(lex: &mut Lexer<Token>) -> Result<String> {
 }
 
 /// Callback used by the `SingleQuotedRegex` variant above.
-fn parse_single_quoted_regex(lex: &mut Lexer<Token>) -> Result<String> {
+pub(crate) fn parse_single_quoted_regex<'s>(
+    lex: &mut impl Cursor<'s>,
+) -> Result<String> {
     let src = lex.remainder(); // everything after the first '/
     let mut escape = false;
 
@@ -523,40 +625,126 @@ mod tests {
     #[test]
     fn test_basic_tokens() {
         // Test meta pattern operators
-        assert_eq!(Token::lexer("&").next(), Some(Ok(Token::And)));
-        assert_eq!(Token::lexer("|").next(), Some(Ok(Token::Or)));
-        assert_eq!(Token::lexer("!").next(), Some(Ok(Token::Not)));
-        assert_eq!(Token::lexer("->").next(), Some(Ok(Token::Traverse)));
-        assert_eq!(Token::lexer("*").next(), Some(Ok(Token::RepeatZeroOrMore)));
-        assert_eq!(Token::lexer("+").next(), Some(Ok(Token::RepeatOneOrMore)));
-        assert_eq!(Token::lexer("?").next(), Some(Ok(Token::RepeatZeroOrOne)));
+        assert_eq!(crate::parse::lexer("&").next(), Some(Ok(Token::And)));
+        assert_eq!(crate::parse::lexer("|").next(), Some(Ok(Token::Or)));
+        assert_eq!(crate::parse::lexer("!").next(), Some(Ok(Token::Not)));
+        assert_eq!(crate::parse::lexer("->").next(), Some(Ok(Token::Traverse)));
+        assert_eq!(
+            crate::parse::lexer("<-").next(),
+            Some(Ok(Token::TraverseBack))
+        );
+        assert_eq!(
+            crate::parse::lexer("<-node").next(),
+            Some(Ok(Token::TraverseBack))
+        );
+        let tokens: Vec<_> = crate::parse::lexer("<-5").collect();
+        assert_eq!(
+            tokens,
+            vec![Ok(Token::LessThan), Ok(Token::Integer(Ok(-5)))]
+        );
+        assert_eq!(
+            crate::parse::lexer("*").next(),
+            Some(Ok(Token::RepeatZeroOrMore))
+        );
+        assert_eq!(
+            crate::parse::lexer("+").next(),
+            Some(Ok(Token::RepeatOneOrMore))
+        );
+        assert_eq!(
+            crate::parse::lexer("?").next(),
+            Some(Ok(Token::RepeatZeroOrOne))
+        );
 
         // Test structure pattern keywords
-        assert_eq!(Token::lexer("assert").next(), Some(Ok(Token::Assertion)));
-        assert_eq!(Token::lexer("node").next(), Some(Ok(Token::Node)));
-        assert_eq!(Token::lexer("subj").next(), Some(Ok(Token::Subject)));
-        assert_eq!(Token::lexer("wrapped").next(), Some(Ok(Token::Wrapped)));
-        assert_eq!(Token::lexer("unwrap").next(), Some(Ok(Token::Unwrap)));
+        assert_eq!(
+            crate::parse::lexer("assert").next(),
+            Some(Ok(Token::Assertion))
+        );
+        assert_eq!(crate::parse::lexer("node").next(), Some(Ok(Token::Node)));
+        assert_eq!(
+            crate::parse::lexer("subj").next(),
+            Some(Ok(Token::Subject))
+        );
+        assert_eq!(
+            crate::parse::lexer("wrapped").next(),
+            Some(Ok(Token::Wrapped))
+        );
+        assert_eq!(
+            crate::parse::lexer("unwrap").next(),
+            Some(Ok(Token::Unwrap))
+        );
+        assert_eq!(
+            crate::parse::lexer("untag").next(),
+            Some(Ok(Token::Untag))
+        );
+        assert_eq!(crate::parse::lexer("case").next(), Some(Ok(Token::Case)));
+        assert_eq!(crate::parse::lexer("prio").next(), Some(Ok(Token::Prio)));
+        assert_eq!(
+            crate::parse::lexer("while").next(),
+            Some(Ok(Token::While))
+        );
+        assert_eq!(crate::parse::lexer("bfs").next(), Some(Ok(Token::Bfs)));
+        assert_eq!(
+            crate::parse::lexer("count").next(),
+            Some(Ok(Token::Count))
+        );
+        assert_eq!(
+            crate::parse::lexer("pred_or_subj").next(),
+            Some(Ok(Token::PredOrSubject))
+        );
+        assert_eq!(
+            crate::parse::lexer("kv_or_text").next(),
+            Some(Ok(Token::KnownValueOrText))
+        );
 
         // Test leaf pattern keywords
-        assert_eq!(Token::lexer("[").next(), Some(Ok(Token::BracketOpen)));
-        assert_eq!(Token::lexer("]").next(), Some(Ok(Token::BracketClose)));
+        assert_eq!(crate::parse::lexer("arr").next(), Some(Ok(Token::Arr)));
+        assert_eq!(crate::parse::lexer("map").next(), Some(Ok(Token::Map)));
+        assert_eq!(
+            crate::parse::lexer("text_glob").next(),
+            Some(Ok(Token::TextGlob))
+        );
+        assert_eq!(
+            crate::parse::lexer("cbor_bytes").next(),
+            Some(Ok(Token::CborBytes))
+        );
+        assert_eq!(
+            crate::parse::lexer("[").next(),
+            Some(Ok(Token::BracketOpen))
+        );
+        assert_eq!(
+            crate::parse::lexer("]").next(),
+            Some(Ok(Token::BracketClose))
+        );
         assert_eq!(
-            Token::lexer("number").next(),
+            crate::parse::lexer("number").next(),
             Some(Ok(Token::NumberKeyword))
         );
+        assert_eq!(
+            crate::parse::lexer("integer").next(),
+            Some(Ok(Token::IntegerKeyword))
+        );
 
         // Test literals
-        assert_eq!(Token::lexer("bool").next(), Some(Ok(Token::BoolKeyword)));
-        assert_eq!(Token::lexer("true").next(), Some(Ok(Token::BoolTrue)));
-        assert_eq!(Token::lexer("false").next(), Some(Ok(Token::BoolFalse)));
-        assert_eq!(Token::lexer("NaN").next(), Some(Ok(Token::NaN)));
+        assert_eq!(
+            crate::parse::lexer("bool").next(),
+            Some(Ok(Token::BoolKeyword))
+        );
+        assert_eq!(
+            crate::parse::lexer("true").next(),
+            Some(Ok(Token::BoolTrue))
+        );
+        assert_eq!(
+            crate::parse::lexer("false").next(),
+            Some(Ok(Token::BoolFalse))
+        );
+        assert_eq!(crate::parse::lexer("NaN").next(), Some(Ok(Token::NaN)));
     }
 
     #[test]
     fn test_complex_tokens() {
         // Group name
-        let mut lexer = Token::lexer("@name");
+        let mut lexer = crate::parse::lexer("@name");
         if let Some(Ok(Token::GroupName(name))) = lexer.next() {
             assert_eq!(name, "name");
         } else {
@@ -564,14 +752,14 @@ mod tests {
         }
 
         // Test regex
-        let mut lexer = Token::lexer("/[a-z]+/");
+        let mut lexer = crate::parse::lexer("/[a-z]+/");
         if let Some(Ok(Token::Regex(Ok(regex)))) = lexer.next() {
             assert_eq!(regex, "[a-z]+");
         } else {
             panic!("Failed to parse regex");
         }
 
-        let mut lx = Token::lexer(r"/abc\/def/  / /  //  /a\//");
+        let mut lx = crate::parse::lexer(r"/abc\/def/  / /  //  /a\//");
         assert_eq!(
             lx.next(),
             Some(Ok(Token::Regex(Ok("abc\\/def".to_string()))))
@@ -584,7 +772,7 @@ mod tests {
 
     #[test]
     fn test_unsigned_integer() {
-        let mut lexer = Token::lexer("42");
+        let mut lexer = crate::parse::lexer("42");
         let token = lexer.next();
         println!("Token for '42': {:?}", token);
 
@@ -603,7 +791,7 @@ mod tests {
         }
 
         // Test unsigned integer
-        let mut lexer = Token::lexer("0");
+        let mut lexer = crate::parse::lexer("0");
         let token = lexer.next();
         match token {
             Some(Ok(Token::UnsignedInteger(Ok(0)))) => {
@@ -667,7 +855,7 @@ mod tests {
         let mut failed_cases = vec![];
 
         for test_case in test_cases {
-            let mut lexer = Token::lexer(test_case.input);
+            let mut lexer = crate::parse::lexer(test_case.input);
             if let Some(Ok(Token::Range(Ok(range)))) = lexer.next() {
                 assert_eq!(range, test_case.expected);
             } else {
@@ -0,0 +1,373 @@
+//! A hand-written lexer for [`Token`], used in place of the Logos-generated
+//! one when the `no-proc-macros` feature is enabled.
+//!
+//! It follows the same rules as Logos: whitespace is skipped, the longest
+//! match wins, and ties between the number patterns go to the one with the
+//! higher Logos priority.
+
+use super::token::{
+    Cursor, Token, parse_date_pattern, parse_hex_binary_regex,
+    parse_hex_pattern, parse_less_than, parse_range, parse_regex,
+    parse_single_quoted_pattern, parse_single_quoted_regex,
+    parse_string_literal_token,
+};
+use crate::{Error, Result};
+
+/// Builds the `Token` for a fixed token, lexing any trailing content.
+type MakeToken = fn(&mut Lexer<'_>) -> Token;
+
+/// Fixed tokens, with the `Token` each one produces.
+const FIXED_TOKENS: &[(&str, MakeToken)] = &[
+    ("&", |_| Token::And),
+    ("|", |_| Token::Or),
+    ("!", |_| Token::Not),
+    ("->", |_| Token::Traverse),
+    ("*", |_| Token::RepeatZeroOrMore),
+    ("*?", |_| Token::RepeatZeroOrMoreLazy),
+    ("*+", |_| Token::RepeatZeroOrMorePossessive),
+    ("+", |_| Token::RepeatOneOrMore),
+    ("+?", |_| Token::RepeatOneOrMoreLazy),
+    ("++", |_| Token::RepeatOneOrMorePossessive),
+    ("?", |_| Token::RepeatZeroOrOne),
+    ("??", |_| Token::RepeatZeroOrOneLazy),
+    ("?+", |_| Token::RepeatZeroOrOnePossessive),
+    ("assert", |_| Token::Assertion),
+    ("assertpred", |_| Token::AssertionPred),
+    ("assertobj", |_| Token::AssertionObj),
+    ("digest", |_| Token::Digest),
+    ("node", |_| Token::Node),
+    ("obj", |_| Token::Obj),
+    ("obscured", |_| Token::Obscured),
+    ("elided", |_| Token::Elided),
+    ("encrypted", |_| Token::Encrypted),
+    ("compressed", |_| Token::Compressed),
+    ("pred", |_| Token::Pred),
+    ("pred_or_subj", |_| Token::PredOrSubject),
+    ("subj", |_| Token::Subject),
+    ("wrapped", |_| Token::Wrapped),
+    ("unwrap", |_| Token::Unwrap),
+    ("untag", |_| Token::Untag),
+    ("search", |_| Token::Search),
+    ("dedup", |_| Token::Dedup),
+    ("unique", |_| Token::Unique),
+    ("case", |_| Token::Case),
+    ("prio", |_| Token::Prio),
+    ("while", |_| Token::While),
+    ("bfs", |_| Token::Bfs),
+    ("count", |_| Token::Count),
+    ("arr", |_| Token::Arr),
+    ("bstr", |_| Token::ByteString),
+    ("map", |_| Token::Map),
+    ("leaf", |_| Token::Leaf),
+    ("cbor", |_| Token::Cbor),
+    ("cbor_bytes", |_| Token::CborBytes),
+    ("date", |_| Token::DateKeyword),
+    ("known", |_| Token::Known),
+    ("kv_or_text", |_| Token::KnownValueOrText),
+    ("null", |_| Token::Null),
+    ("number", |_| Token::NumberKeyword),
+    ("integer", |_| Token::IntegerKeyword),
+    ("tagged", |_| Token::Tagged),
+    ("bool", |_| Token::BoolKeyword),
+    ("true", |_| Token::BoolTrue),
+    ("false", |_| Token::BoolFalse),
+    ("text", |_| Token::TextKeyword),
+    ("text_glob", |_| Token::TextGlob),
+    ("NaN", |_| Token::NaN),
+    ("\"", |lex| {
+        Token::StringLiteral(parse_string_literal_token(lex))
+    }),
+    ("(", |_| Token::ParenOpen),
+    (")", |_| Token::ParenClose),
+    ("[", |_| Token::BracketOpen),
+    ("]", |_| Token::BracketClose),
+    (",", |_| Token::Comma),
+    ("...", |_| Token::Ellipsis),
+    (">=", |_| Token::GreaterThanOrEqual),
+    ("<=", |_| Token::LessThanOrEqual),
+    (">", |_| Token::GreaterThan),
+    ("<", |lex| parse_less_than(lex)),
+    ("Infinity", |_| Token::Infinity),
+    ("-Infinity", |_| Token::NegativeInfinity),
+    ("/", |lex| Token::Regex(parse_regex(lex))),
+    ("h'", |lex| Token::HexPattern(parse_hex_pattern(lex))),
+    ("h'/", |lex| {
+        Token::HexBinaryRegex(parse_hex_binary_regex(lex))
+    }),
+    ("date'", |lex| Token::DatePattern(parse_date_pattern(lex))),
+    ("{", |lex| Token::Range(parse_range(lex))),
+    ("'", |lex| {
+        Token::SingleQuotedPattern(parse_single_quoted_pattern(lex))
+    }),
+    ("'/", |lex| {
+        Token::SingleQuotedRegex(parse_single_quoted_regex(lex))
+    }),
+];
+
+/// A lexer over pattern source text, producing the same tokens and spans as
+/// the Logos-generated lexer.
+#[derive(Debug, Clone)]
+pub struct Lexer<'s> {
+    source: &'s str,
+    start: usize,
+    end: usize,
+}
+
+impl<'s> Lexer<'s> {
+    /// Creates a lexer positioned before the first token of `source`.
+    pub fn new(source: &'s str) -> Self { Self { source, start: 0, end: 0 } }
+
+    /// The source text of the current token.
+    pub fn slice(&self) -> &'s str { &self.source[self.start..self.end] }
+
+    /// The source text after the current token.
+    pub fn remainder(&self) -> &'s str { &self.source[self.end..] }
+
+    /// Extends the current token by `n` bytes.
+    pub fn bump(&mut self, n: usize) {
+        self.end += n;
+        assert!(
+            self.source.is_char_boundary(self.end),
+            "bumped into the middle of a character"
+        );
+    }
+
+    /// The byte range of the current token.
+    pub fn span(&self) -> std::ops::Range<usize> { self.start..self.end }
+}
+
+impl<'s> Cursor<'s> for Lexer<'s> {
+    fn remainder(&self) -> &'s str { Lexer::remainder(self) }
+
+    fn bump(&mut self, n: usize) { Lexer::bump(self, n) }
+
+    fn span(&self) -> std::ops::Range<usize> { Lexer::span(self) }
+}
+
+impl Iterator for Lexer<'_> {
+    type Item = Result<Token>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = &self.source[self.end..];
+        let skipped = rest.len()
+            - rest
+                .trim_start_matches([' ', '\t', '\r', '\n', '\u{0c}'])
+                .len();
+        self.start = self.end + skipped;
+        self.end = self.start;
+        let rest = &self.source[self.start..];
+        if rest.is_empty() {
+            return None;
+        }
+
+        let fixed = FIXED_TOKENS
+            .iter()
+            .filter(|(text, _)| rest.starts_with(text))
+            .max_by_key(|(text, _)| text.len());
+        let fixed_len = fixed.map_or(0, |(text, _)| text.len());
+        let number_len = match_number(rest);
+        let group_len = match_group_name(rest);
+
+        let token = if number_len.is_some_and(|(len, _)| len > fixed_len) {
+            let (len, is_float) = number_len.unwrap();
+            self.bump(len);
+            let slice = self.slice();
+            if is_float {
+                Token::Float(
+                    slice
+                        .parse::<f64>()
+                        .map_err(|_| Error::InvalidNumberFormat(self.span())),
+                )
+            } else {
+                Token::Integer(
+                    slice
+                        .parse::<i64>()
+                        .map_err(|_| Error::InvalidNumberFormat(self.span())),
+                )
+            }
+        } else if group_len > fixed_len {
+            self.bump(group_len);
+            Token::GroupName(self.slice()[1..].to_string())
+        } else if let Some((text, make)) = fixed {
+            self.bump(text.len());
+            make(self)
+        } else {
+            let len = rest.chars().next().map_or(0, char::len_utf8);
+            self.bump(len);
+            return Some(Err(Error::default()));
+        };
+        Some(Ok(token))
+    }
+}
+
+/// Matches `-?(?:[1-9]\d*|0)`, optionally followed by a fraction and
+/// exponent, at the start of `s`. Returns the length of the match and
+/// whether it is a float.
+///
+/// The unsigned integer pattern matches a subset of what the signed one
+/// does, with a lower priority, so it never wins.
+fn match_number(s: &str) -> Option<(usize, bool)> {
+    let bytes = s.as_bytes();
+    let digits = |from: usize| {
+        bytes[from..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count()
+    };
+
+    let mut pos = usize::from(bytes.first() == Some(&b'-'));
+    match bytes.get(pos) {
+        Some(b'0') => pos += 1,
+        Some(b'1'..=b'9') => pos += digits(pos),
+        _ => return None,
+    }
+    let integer_len = pos;
+
+    // Fraction, required for a float.
+    if bytes.get(pos) != Some(&b'.') {
+        return Some((integer_len, false));
+    }
+    let fraction = digits(pos + 1);
+    if fraction == 0 {
+        return Some((integer_len, false));
+    }
+    pos += 1 + fraction;
+
+    // Optional exponent.
+    if matches!(bytes.get(pos), Some(b'e' | b'E')) {
+        let mut exp = pos + 1;
+        if matches!(bytes.get(exp), Some(b'+' | b'-')) {
+            exp += 1;
+        }
+        let exp_digits = digits(exp);
+        if exp_digits > 0 {
+            pos = exp + exp_digits;
+        }
+    }
+    Some((pos, true))
+}
+
+/// Matches `@[a-zA-Z_][a-zA-Z0-9_]*` at the start of `s`, returning the
+/// length of the match or zero.
+fn match_group_name(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    if bytes.first() != Some(&b'@')
+        || !bytes
+            .get(1)
+            .is_some_and(|b| b.is_ascii_alphabetic() || *b == b'_')
+    {
+        return 0;
+    }
+    1 + bytes[1..]
+        .iter()
+        .take_while(|b| b.is_ascii_alphanumeric() || **b == b'_')
+        .count()
+}
+
+#[cfg(all(test, feature = "logos"))]
+mod tests {
+    use logos::Logos;
+
+    use super::*;
+
+    /// Lexes `src` with both lexers and asserts that they agree on every
+    /// token and span.
+    fn assert_same_tokens(src: &str) {
+        let mut logos = Token::lexer(src);
+        let mut manual = Lexer::new(src);
+        loop {
+            let expected = logos.next();
+            let actual = manual.next();
+            assert_eq!(actual, expected, "token mismatch in {src:?}");
+            if expected.is_none() {
+                break;
+            }
+            assert_eq!(manual.span(), logos.span(), "span mismatch in {src:?}");
+        }
+    }
+
+    #[test]
+    fn test_manual_lexer_matches_logos() {
+        let corpus = [
+            "*",
+            "leaf",
+            "node",
+            "node({1,3})",
+            "node(all: assert)",
+            r#"node(preds_in: ["age", text])"#,
+            "subj",
+            r#"subj("Alice")"#,
+            "assert",
+            r#"assertpred("knows")"#,
+            "assertobj(number)",
+            r#"assert(pred: "name", obj: text)"#,
+            r#"assert[0](pred("age"))"#,
+            "pred(known)",
+            r#"pred_or_subj("Alice")"#,
+            r#"kv_or_text("isA")"#,
+            "obj(>=18)",
+            "wrapped",
+            "unwrap(node)",
+            r#"untag(100, "Alice")"#,
+            r#"case("leaf") | case("elided")"#,
+            "prio(10, text) | prio(1, number)",
+            "while(wrapped, unwrap)",
+            "obscured | elided | encrypted | compressed",
+            "digest(a1b2c3)",
+            "digest(ur:digest/hdcxlkahssqzwfvslofzoxwkrewngotktbmwjkwdcmnefsaaehrlolkskncnktiaplnbgrjs)",
+            r#"search("Bob")"#,
+            "search(text, limit: 2)",
+            "dedup(search(number))",
+            "unique(search(number))",
+            r#"text(one_of: ["a", "b\"c"])"#,
+            "/^h.*o$/",
+            "text",
+            "number(one_of: [1, -2, 2.5, NaN, Infinity, -Infinity])",
+            "1...10",
+            "-3.5...4e10",
+            "1.5e-3",
+            ">5 & <10",
+            "<=-1 | >=1",
+            "<-5",
+            "number(<-5)",
+            "text <-node",
+            r#"search("Bob") <- *"#,
+            "bool | true | false",
+            "null",
+            "bstr",
+            "h'0102ff'",
+            "h'/^\\x00/'",
+            "date",
+            "date'2023-12-25'",
+            "date'2023-01-01...2023-12-31'",
+            "date'/2023-.*/'",
+            "known",
+            "'isA'",
+            "'/is.*/'",
+            "'1'",
+            "tagged",
+            "tagged(1, number)",
+            "cbor",
+            "cbor([1, 2, {3: 4}])",
+            "cbor(/@num(number)/)",
+            "@name(text) & !\"Alice\"",
+            "(unwrap)? -> node",
+            "(node)* -> (subj)+? -> (obj)*+ -> (pred)++",
+            "(wrapped)?? -> (text)?+",
+            "(assert){2,}?",
+            "(assert){1, 5}+",
+            "[number, text]",
+            "arr[0: text]",
+            r#"map(key: "age", val: number)"#,
+            r#"text_glob("Al?ce*")"#,
+            r"cbor_bytes(h'/^\x18/')",
+            "bfs(unwrap -> node)",
+            "count({1,3}, search(text))",
+            "integer(<-3) | integer(-5...5)",
+            "  node  \t\n  ->   subj  ",
+        ];
+        for src in corpus {
+            assert_same_tokens(src);
+        }
+    }
+}
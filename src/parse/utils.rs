@@ -2,6 +2,7 @@
 
 use dcbor_parse::parse_dcbor_item_partial;
 
+use super::Token;
 use crate::{DCBORPattern, Error, Pattern, Result};
 
 pub(crate) fn skip_ws(src: &str, pos: &mut usize) {
@@ -14,6 +15,133 @@ pub(crate) fn skip_ws(src: &str, pos: &mut usize) {
     }
 }
 
+/// Returns the offset of the first of `delimiters` in `src` that is not
+/// nested inside brackets, parentheses, braces, or a literal quoted with
+/// `"`, `'` or `/`. Single quotes cover known values, `h'...'` and
+/// `date'...'`; slashes cover regexes.
+pub(crate) fn find_unnested(src: &str, delimiters: &[u8]) -> Option<usize> {
+    let bytes = src.as_bytes();
+    let mut depth = 0usize;
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let ch = bytes[pos];
+        if depth == 0 && delimiters.contains(&ch) {
+            return Some(pos);
+        }
+        match ch {
+            b'[' | b'(' | b'{' => depth += 1,
+            b']' | b')' | b'}' => depth = depth.checked_sub(1)?,
+            b'"' | b'\'' | b'/' => {
+                pos += 1;
+                while pos < bytes.len() && bytes[pos] != ch {
+                    if bytes[pos] == b'\\' {
+                        pos += 1;
+                    }
+                    pos += 1;
+                }
+            }
+            _ => {}
+        }
+        pos += 1;
+    }
+    None
+}
+
+/// If `src` starts (after optional whitespace) with `label` followed by a
+/// colon, returns the number of bytes consumed including any whitespace after
+/// the colon.
+pub(crate) fn parse_label(src: &str, label: &str) -> Option<usize> {
+    let mut pos = 0;
+    skip_ws(src, &mut pos);
+    if !src[pos..].starts_with(label) {
+        return None;
+    }
+    pos += label.len();
+    skip_ws(src, &mut pos);
+    if !src[pos..].starts_with(':') {
+        return None;
+    }
+    pos += 1;
+    skip_ws(src, &mut pos);
+    Some(pos)
+}
+
+/// Parses the remainder of `( one_of: [ value, value, ... ] )` after the
+/// opening parenthesis, converting each value token with `value`. The
+/// brackets are optional when at least one value is given. `value` returns
+/// `Ok(None)` for tokens that are not valid values.
+pub(crate) fn parse_one_of<T>(
+    lexer: &mut crate::parse::Lexer,
+    value: impl Fn(Token) -> Result<Option<T>>,
+) -> Result<Vec<T>> {
+    fn unexpected<T>(
+        lexer: &mut crate::parse::Lexer,
+        token: Option<std::result::Result<Token, Error>>,
+    ) -> Result<T> {
+        match token {
+            Some(Ok(t)) => {
+                Err(Error::UnexpectedToken(Box::new(t), lexer.span()))
+            }
+            Some(Err(e)) => Err(e),
+            None => Err(Error::UnexpectedEndOfInput),
+        }
+    }
+
+    let Some(consumed) = parse_label(lexer.remainder(), "one_of") else {
+        let token = lexer.next();
+        return unexpected(lexer, token);
+    };
+    lexer.bump(consumed);
+
+    let mut lookahead = lexer.clone();
+    let bracketed = matches!(lookahead.next(), Some(Ok(Token::BracketOpen)));
+    let mut values = Vec::new();
+    if bracketed {
+        lexer.next();
+        let mut lookahead = lexer.clone();
+        if matches!(lookahead.next(), Some(Ok(Token::BracketClose))) {
+            lexer.next();
+            return match lexer.next() {
+                Some(Ok(Token::ParenClose)) => Ok(values),
+                Some(Ok(t)) => {
+                    Err(Error::UnexpectedToken(Box::new(t), lexer.span()))
+                }
+                Some(Err(e)) => Err(e),
+                None => Err(Error::ExpectedCloseParen(lexer.span())),
+            };
+        }
+    }
+
+    loop {
+        match lexer.next() {
+            Some(Ok(token)) => match value(token.clone())? {
+                Some(v) => values.push(v),
+                None => return unexpected(lexer, Some(Ok(token))),
+            },
+            other => return unexpected(lexer, other),
+        }
+        match lexer.next() {
+            Some(Ok(Token::Comma)) => {}
+            Some(Ok(Token::BracketClose)) if bracketed => {
+                return match lexer.next() {
+                    Some(Ok(Token::ParenClose)) => Ok(values),
+                    Some(Ok(t)) => {
+                        Err(Error::UnexpectedToken(Box::new(t), lexer.span()))
+                    }
+                    Some(Err(e)) => Err(e),
+                    None => Err(Error::ExpectedCloseParen(lexer.span())),
+                };
+            }
+            Some(Ok(Token::ParenClose)) if !bracketed => return Ok(values),
+            None if bracketed => {
+                return Err(Error::ExpectedCloseBracket(lexer.span()));
+            }
+            None => return Err(Error::ExpectedCloseParen(lexer.span())),
+            other => return unexpected(lexer, other),
+        }
+    }
+}
+
 pub(crate) fn parse_text_regex(src: &str) -> Result<(regex::Regex, usize)> {
     let mut pos = 0;
     skip_ws(src, &mut pos);
@@ -68,10 +196,12 @@ pub(crate) fn parse_cbor_inner(src: &str) -> Result<(Pattern, usize)> {
                 continue;
             }
             if b == b'/' {
-                let pattern_str = &src[start..pos - 1];
+                // A `/` inside the expression, such as a regex delimiter,
+                // is escaped as `\/`.
+                let pattern_str = src[start..pos - 1].replace("\\/", "/");
 
                 // Parse the dcbor-pattern expression
-                let dcbor_pattern = DCBORPattern::parse(pattern_str)
+                let dcbor_pattern = DCBORPattern::parse(&pattern_str)
                     .map_err(|_| Error::InvalidPattern(start..pos - 1))?;
 
                 skip_ws(src, &mut pos);
@@ -0,0 +1,293 @@
+//! Best-effort static analysis of patterns.
+
+use super::{
+    Pattern,
+    leaf::LeafPattern,
+    meta::{MetaPattern, TraversalOrder},
+    structure::{NodePattern, StructurePattern},
+};
+use crate::{Error, Result};
+
+impl Pattern {
+    /// Returns `false` if this pattern provably can never match any
+    /// envelope, and `true` otherwise.
+    ///
+    /// The check is conservative: a `true` result does not guarantee that a
+    /// matching envelope exists. Cases recognized as unsatisfiable include:
+    ///
+    /// - `!*`, the pattern that never matches.
+    /// - An `and` of two leaf patterns of disjoint types, such as a number and
+    ///   a text value.
+    /// - An `and` or traversal with an unsatisfiable element.
+    /// - An `or` whose alternatives are all unsatisfiable.
+    /// - Structure patterns whose inner pattern is unsatisfiable.
+    pub fn is_satisfiable(&self) -> bool {
+        match self {
+            Pattern::Leaf(_) => true,
+            Pattern::Structure(structure) => match structure {
+                StructurePattern::Node(NodePattern::AssertionsInterval(
+                    interval,
+                )) => interval.max() != Some(0),
+                // Envelope never produces a node without assertions.
+                StructurePattern::Node(NodePattern::Exact(patterns))
+                    if patterns.is_empty() =>
+                {
+                    false
+                }
+                _ => structure
+                    .child_patterns()
+                    .iter()
+                    .all(|child| child.is_satisfiable()),
+            },
+            Pattern::Meta(meta) => match meta {
+                MetaPattern::Any(_) => true,
+                MetaPattern::Not(not) => {
+                    !matches!(not.pattern(), Pattern::Meta(MetaPattern::Any(_)))
+                }
+                MetaPattern::And(and) => {
+                    let patterns = and.patterns();
+                    if !patterns.iter().all(|p| p.is_satisfiable()) {
+                        return false;
+                    }
+                    let leaves: Vec<&LeafPattern> =
+                        patterns.iter().filter_map(leaf_of).collect();
+                    leaves.iter().enumerate().all(|(i, a)| {
+                        leaves[i + 1..]
+                            .iter()
+                            .all(|b| !leaf_types_disjoint(a, b))
+                    })
+                }
+                MetaPattern::Or(or) => {
+                    or.patterns().iter().any(|p| p.is_satisfiable())
+                }
+                MetaPattern::PriorityOr(_) => meta
+                    .child_patterns()
+                    .iter()
+                    .any(|child| child.is_satisfiable()),
+                MetaPattern::Group(group) => {
+                    group.quantifier().min() == 0
+                        || group.pattern().is_satisfiable()
+                }
+                // Taking no steps always matches.
+                MetaPattern::While(_) => true,
+                MetaPattern::SearchCount(count) => {
+                    count.interval().min() == 0
+                        || count.pattern().is_satisfiable()
+                }
+                MetaPattern::Search(_)
+                | MetaPattern::Traverse(_)
+                | MetaPattern::TraverseBack(_)
+                | MetaPattern::Capture(_)
+                | MetaPattern::Dedup(_)
+                | MetaPattern::Unique(_)
+                | MetaPattern::Explained(_)
+                | MetaPattern::PathFilter(_) => meta
+                    .child_patterns()
+                    .iter()
+                    .all(|child| child.is_satisfiable()),
+            },
+        }
+    }
+}
+
+impl Pattern {
+    /// Creates a new `or` pattern whose alternatives are checked to be
+    /// mutually exclusive.
+    ///
+    /// Two alternatives are exclusive if their `and` is provably
+    /// unsatisfiable; see [`Pattern::is_satisfiable`]. As that check is
+    /// conservative, only alternatives of disjoint leaf types, such as a
+    /// number and a text value, are recognized as exclusive.
+    ///
+    /// Returns [`Error::OverlappingAlternatives`] naming the first pair of
+    /// alternatives that may both match the same envelope.
+    pub fn disjoint_or(patterns: Vec<Pattern>) -> Result<Pattern> {
+        for (i, a) in patterns.iter().enumerate() {
+            for b in &patterns[i + 1..] {
+                if Pattern::and(vec![a.clone(), b.clone()]).is_satisfiable() {
+                    return Err(Error::OverlappingAlternatives(
+                        a.to_string(),
+                        b.to_string(),
+                    ));
+                }
+            }
+        }
+        Ok(Pattern::or(patterns))
+    }
+}
+
+impl Pattern {
+    /// Returns `true` if this pattern provably never matches, such as `!*` or
+    /// an `and` of disjoint leaf types. See [`Pattern::is_satisfiable`].
+    pub fn is_empty_pattern(&self) -> bool { !self.is_satisfiable() }
+
+    /// Returns `true` if this pattern provably matches every envelope: `*`,
+    /// or an `or` with a universal alternative.
+    pub fn is_universal_pattern(&self) -> bool {
+        match self {
+            Pattern::Meta(MetaPattern::Any(_)) => true,
+            Pattern::Meta(MetaPattern::Or(or)) => {
+                or.patterns().iter().any(|p| p.is_universal_pattern())
+            }
+            Pattern::Meta(MetaPattern::PriorityOr(or)) => {
+                or.branches().iter().any(|(_, p)| p.is_universal_pattern())
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns this pattern with every traversal nested directly in another
+    /// traversal of the same order merged into it, and every single-step
+    /// depth-first traversal replaced by its only step. `a -> (b -> c)`
+    /// becomes `a -> b -> c`, and the result matches the same paths.
+    pub fn flatten_traversal(&self) -> Pattern {
+        match self
+            .clone()
+            .map_child_patterns(&mut |child| child.flatten_traversal())
+        {
+            Pattern::Meta(MetaPattern::Traverse(traverse)) => {
+                flatten_steps(traverse.patterns(), traverse.order())
+            }
+            other => other,
+        }
+    }
+
+    /// Returns a simplified pattern that matches the same paths as this one.
+    ///
+    /// Empty alternatives are removed from `or` patterns, an `and` or
+    /// traversal containing an empty pattern is replaced by `!*`, nested
+    /// traversals are flattened as by [`Pattern::flatten_traversal`], and
+    /// `*` is removed from `and` patterns where other patterns remain,
+    /// except in last place after a pattern that is not a leaf. A
+    /// double negation `!!p` becomes `p` where `p` is a leaf, and the
+    /// negation of an empty pattern becomes `*`.
+    /// Universal `or` patterns are kept as they are, because their
+    /// alternatives may produce different paths.
+    pub fn optimize(self) -> Pattern {
+        let never = Pattern::none;
+        let pattern = self.map_child_patterns(&mut |child| child.optimize());
+        match pattern {
+            Pattern::Meta(MetaPattern::Or(or)) => {
+                let mut patterns: Vec<Pattern> = or
+                    .patterns()
+                    .iter()
+                    .filter(|p| !p.is_empty_pattern())
+                    .cloned()
+                    .collect();
+                match patterns.len() {
+                    0 => never(),
+                    1 => patterns.remove(0),
+                    _ => Pattern::or(patterns),
+                }
+            }
+            Pattern::Meta(MetaPattern::And(and)) => {
+                if and.patterns().iter().any(|p| p.is_empty_pattern()) {
+                    return never();
+                }
+                let is_any = |p: &Pattern| {
+                    matches!(p, Pattern::Meta(MetaPattern::Any(_)))
+                };
+                // The last operand decides the paths, so a trailing `*`
+                // can only go if the operand before it also yields the
+                // haystack itself.
+                let Some((last, rest)) = and.patterns().split_last() else {
+                    return Pattern::any();
+                };
+                let mut patterns: Vec<Pattern> =
+                    rest.iter().filter(|p| !is_any(p)).cloned().collect();
+                if !is_any(last)
+                    || !patterns.last().is_none_or(Pattern::yields_haystack)
+                {
+                    patterns.push(last.clone());
+                }
+                match patterns.len() {
+                    0 => Pattern::any(),
+                    1 => patterns.remove(0),
+                    _ => Pattern::and(patterns),
+                }
+            }
+            Pattern::Meta(MetaPattern::Traverse(ref traverse))
+                if traverse.patterns().iter().any(|p| p.is_empty_pattern()) =>
+            {
+                never()
+            }
+            Pattern::Meta(MetaPattern::Traverse(traverse)) => {
+                flatten_steps(traverse.patterns(), traverse.order())
+            }
+            Pattern::Meta(MetaPattern::Not(not)) => {
+                if not.pattern().is_empty_pattern() {
+                    Pattern::any()
+                } else {
+                    Pattern::not_matching(not.pattern().clone())
+                }
+            }
+            other => other,
+        }
+    }
+
+    /// Returns `true` if this pattern matches with the haystack itself as
+    /// its only path, as `*`, a negation, and every leaf pattern except
+    /// `cbor(...)` do.
+    pub(crate) fn yields_haystack(&self) -> bool {
+        match self {
+            Pattern::Leaf(leaf) => !matches!(leaf, LeafPattern::Cbor(_)),
+            Pattern::Meta(MetaPattern::Any(_) | MetaPattern::Not(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Builds a traversal of `steps` in `order`, splicing in the steps of any
+/// nested traversal in the same order. A depth-first traversal of a single
+/// step is replaced by that step.
+fn flatten_steps(steps: Vec<Pattern>, order: TraversalOrder) -> Pattern {
+    let mut flattened = Vec::new();
+    for step in steps {
+        match step {
+            Pattern::Meta(MetaPattern::Traverse(inner))
+                if inner.order() == order =>
+            {
+                flattened.extend(inner.patterns())
+            }
+            other => flattened.push(other),
+        }
+    }
+    if flattened.len() == 1 && order == TraversalOrder::DepthFirst {
+        flattened.remove(0)
+    } else {
+        Pattern::traverse_ordered(flattened, order)
+    }
+}
+
+/// Returns the leaf pattern that `pattern` matches with, looking through
+/// captures and single-occurrence groups.
+fn leaf_of(pattern: &Pattern) -> Option<&LeafPattern> {
+    match pattern {
+        Pattern::Leaf(leaf) => Some(leaf),
+        Pattern::Meta(MetaPattern::Capture(capture)) => {
+            leaf_of(capture.pattern())
+        }
+        Pattern::Meta(MetaPattern::Group(group))
+            if group.quantifier().min() == 1
+                && group.quantifier().max() == Some(1) =>
+        {
+            leaf_of(group.pattern())
+        }
+        _ => None,
+    }
+}
+
+/// Returns `true` if no envelope can match both leaf patterns because they
+/// match different kinds of value.
+fn leaf_types_disjoint(a: &LeafPattern, b: &LeafPattern) -> bool {
+    use LeafPattern::*;
+    match (a, b) {
+        // A CBOR pattern can match a value of any type.
+        (Cbor(_), _) | (_, Cbor(_)) => false,
+        // Dates are tagged values.
+        (Date(_), Tag(_)) | (Tag(_), Date(_)) => false,
+        // Integers are numbers.
+        (Integer(_), Number(_)) | (Number(_), Integer(_)) => false,
+        _ => std::mem::discriminant(a) != std::mem::discriminant(b),
+    }
+}
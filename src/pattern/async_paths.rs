@@ -0,0 +1,42 @@
+//! Matching from async code without blocking the executor.
+
+use std::collections::HashMap;
+
+use bc_envelope::prelude::*;
+
+use super::{Matcher, Path, Pattern};
+
+impl Pattern {
+    /// Returns the paths in `haystack` that match this pattern, running the
+    /// match on Tokio's blocking thread pool so that a large envelope or an
+    /// expensive pattern does not stall the async executor.
+    ///
+    /// Must be called from within a Tokio runtime. If matching panics, the
+    /// panic is resumed in the calling task.
+    pub async fn async_paths(&self, haystack: Envelope) -> Vec<Path> {
+        let pattern = self.clone();
+        spawn_match(move || pattern.paths(&haystack)).await
+    }
+
+    /// Like [`Pattern::async_paths`], but also returns the captured paths, as
+    /// [`Matcher::paths_with_captures`] does.
+    pub async fn async_paths_with_captures(
+        &self,
+        haystack: Envelope,
+    ) -> (Vec<Path>, HashMap<String, Vec<Path>>) {
+        let pattern = self.clone();
+        spawn_match(move || pattern.paths_with_captures(&haystack)).await
+    }
+}
+
+async fn spawn_match<T: Send + 'static>(
+    f: impl FnOnce() -> T + Send + 'static,
+) -> T {
+    match tokio::task::spawn_blocking(f).await {
+        Ok(result) => result,
+        Err(error) if error.is_panic() => {
+            std::panic::resume_unwind(error.into_panic())
+        }
+        Err(error) => panic!("pattern matching task failed: {}", error),
+    }
+}
@@ -0,0 +1,54 @@
+//! CBOR serialization of patterns.
+//!
+//! A pattern is encoded as its expression syntax, not as a tree of tagged
+//! variants. Many leaf patterns wrap `dcbor-pattern` values that can only
+//! be taken apart through their text, so the text is the one form every
+//! variant already has.
+
+use dcbor::prelude::*;
+
+use super::Pattern;
+use crate::{Error, Result};
+
+impl Pattern {
+    /// Encodes this pattern as CBOR, so it can be stored or sent alongside
+    /// other CBOR data.
+    ///
+    /// The encoding is the pattern's expression syntax as an untagged text
+    /// string. Callers that need to distinguish patterns from other text
+    /// should wrap it in a tag of their own.
+    ///
+    /// Only patterns whose display text parses back to an equal pattern can
+    /// be encoded. That covers every pattern written in the expression
+    /// syntax, and patterns built in code from constructors that have a
+    /// syntax, including the [`Pattern::known_value_or_text`] and
+    /// [`Pattern::predicate_or_subject`] shorthands in any position. Returns
+    /// `Error::UnencodablePattern` for any other pattern, including:
+    ///
+    /// - [`Pattern::path_filter`], whose predicate is a closure.
+    /// - [`Pattern::with_explanation`], whose explanation is not part of the
+    ///   syntax.
+    /// - [`Pattern::has_same_digest_as`] and [`Pattern::deep_equal`], which
+    ///   match an exact digest that does not display in a form the parser
+    ///   reads back.
+    /// - [`Pattern::or_default`], which displays as `a | !a & b` and parses
+    ///   as `a | !(a & b)`.
+    pub fn to_cbor(&self) -> Result<CBOR> {
+        let text = self.to_string();
+        match Pattern::parse(&text) {
+            Ok(decoded) if decoded == *self => Ok(CBOR::from(text)),
+            _ => Err(Error::UnencodablePattern(text)),
+        }
+    }
+
+    /// Decodes a pattern encoded with [`Pattern::to_cbor`].
+    ///
+    /// Returns an error if `cbor` is not a text string, or if the text does
+    /// not parse as a pattern.
+    pub fn from_cbor(cbor: CBOR) -> Result<Pattern> {
+        let text = cbor.try_into_text().map_err(|_| {
+            Error::InvalidPatternCBOR("expected a text string".to_string())
+        })?;
+        Pattern::parse(text)
+    }
+}
@@ -0,0 +1,122 @@
+//! Convenience patterns for common envelope domains, such as dates and
+//! verifiable credentials.
+
+use bc_envelope::prelude::*;
+
+use crate::Pattern;
+
+/// The length of a year in seconds, averaging over leap years.
+const SECONDS_PER_YEAR: f64 = 365.25 * 24.0 * 60.0 * 60.0;
+
+fn years_before(now: Date, years: f64) -> Date {
+    now - years * SECONDS_PER_YEAR
+}
+
+impl Pattern {
+    /// Creates a new `Pattern` that matches dates at least `min_years` and at
+    /// most `max_years` before now, such as an issue date no more than three
+    /// years ago (`date_between_ages(0.0, 3.0)`).
+    ///
+    /// Years are 365.25 days. The range is computed when the pattern is
+    /// created, not when it is matched.
+    pub fn date_between_ages(min_years: f64, max_years: f64) -> Self {
+        Pattern::date_between_ages_at(min_years, max_years, Date::now())
+    }
+
+    /// Like [`Pattern::date_between_ages`], with ages measured from `now`.
+    pub fn date_between_ages_at(
+        min_years: f64,
+        max_years: f64,
+        now: Date,
+    ) -> Self {
+        Pattern::date_range(
+            years_before(now, max_years)..=years_before(now, min_years),
+        )
+    }
+
+    /// Creates a new `Pattern` that matches dates no more than
+    /// `max_age_years` before now. Dates in the future also match.
+    pub fn date_not_expired(max_age_years: f64) -> Self {
+        Pattern::date_not_expired_at(max_age_years, Date::now())
+    }
+
+    /// Like [`Pattern::date_not_expired`], with the age measured from `now`.
+    pub fn date_not_expired_at(max_age_years: f64, now: Date) -> Self {
+        Pattern::date_earliest(years_before(now, max_age_years))
+    }
+
+    /// Creates a new `Pattern` that matches dates that are not after now.
+    pub fn date_not_future() -> Self {
+        Pattern::date_not_future_at(Date::now())
+    }
+
+    /// Like [`Pattern::date_not_future`], with `now` given explicitly.
+    pub fn date_not_future_at(now: Date) -> Self { Pattern::date_latest(now) }
+}
+
+impl Pattern {
+    /// Creates a new `Pattern` that matches the `id` assertions of a
+    /// credential, which identify its subject.
+    pub fn vc_subject_id() -> Self {
+        Pattern::assertion_with_predicate(Pattern::known_value_named("id"))
+    }
+
+    /// Creates a new `Pattern` that matches the `issuer` assertions of a
+    /// credential.
+    pub fn vc_issuer() -> Self {
+        Pattern::assertion_with_predicate(Pattern::known_value_named("issuer"))
+    }
+
+    /// Creates a new `Pattern` that matches the `validFrom` assertions of a
+    /// credential, which give its issuance date.
+    pub fn vc_issuance_date() -> Self {
+        Pattern::assertion_with_predicate(Pattern::known_value_named(
+            "validFrom",
+        ))
+    }
+
+    /// Creates a new `Pattern` that matches the `validUntil` assertions of a
+    /// credential, which give its expiration date.
+    pub fn vc_expiration_date() -> Self {
+        Pattern::assertion_with_predicate(Pattern::known_value_named(
+            "validUntil",
+        ))
+    }
+
+    /// Creates a new `Pattern` that matches a credential whose `validFrom`
+    /// date is not after now and whose `validUntil` date is not before now.
+    /// Both assertions must be present.
+    pub fn vc_is_valid_today() -> Self { Pattern::vc_is_valid_at(Date::now()) }
+
+    /// Like [`Pattern::vc_is_valid_today`], checking validity at `now`.
+    pub fn vc_is_valid_at(now: Date) -> Self {
+        Pattern::and(vec![
+            Pattern::assertion_with_predicate_and_object(
+                Pattern::known_value_named("validFrom"),
+                Pattern::date_not_future_at(now),
+            ),
+            Pattern::assertion_with_predicate_and_object(
+                Pattern::known_value_named("validUntil"),
+                Pattern::date_earliest(now),
+            ),
+        ])
+    }
+}
+
+impl Pattern {
+    /// Creates a new `Pattern` that matches a node with at least one
+    /// `'signed'` assertion. The signature is not verified.
+    pub fn any_signed() -> Self {
+        Pattern::has_known_value_assertion(known_values::SIGNED)
+    }
+
+    /// Creates a new `Pattern` that matches a node with a `'signed'`
+    /// assertion whose object, the signature, matches `verifier_pattern`.
+    /// The signature is not verified.
+    pub fn signed_by(verifier_pattern: Pattern) -> Self {
+        Pattern::has_known_value_assertion_with_object(
+            known_values::SIGNED,
+            verifier_pattern,
+        )
+    }
+}
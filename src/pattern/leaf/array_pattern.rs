@@ -8,43 +8,74 @@ use crate::{
 };
 
 /// Pattern for matching arrays.
-/// This delegates directly to dcbor-pattern for array matching.
+/// Except for `AtIndex`, this delegates directly to dcbor-pattern for array
+/// matching.
 #[derive(Debug, Clone, Eq, PartialEq)]
-pub struct ArrayPattern(dcbor_pattern::ArrayPattern);
+pub enum ArrayPattern {
+    /// Matches arrays as described by a dcbor-pattern.
+    Dcbor(dcbor_pattern::ArrayPattern),
+    /// Matches arrays whose element at the 0-based `index` matches
+    /// `element`. Arrays with no element at `index` do not match.
+    AtIndex { index: usize, element: DCBORPattern },
+}
 
 impl ArrayPattern {
     /// Creates a new `ArrayPattern` that matches any array.
-    pub fn any() -> Self { ArrayPattern(dcbor_pattern::ArrayPattern::any()) }
+    pub fn any() -> Self {
+        ArrayPattern::Dcbor(dcbor_pattern::ArrayPattern::any())
+    }
 
     /// Creates a new `ArrayPattern` that matches arrays with a count
     /// of elements in the specified range.
     pub fn interval(interval: impl RangeBounds<usize>) -> Self {
-        ArrayPattern(dcbor_pattern::ArrayPattern::with_length_range(interval))
+        ArrayPattern::Dcbor(dcbor_pattern::ArrayPattern::with_length_range(
+            interval,
+        ))
     }
 
     /// Creates a new `ArrayPattern` that matches arrays with exact count.
     pub fn count(n: usize) -> Self {
-        ArrayPattern(dcbor_pattern::ArrayPattern::with_length_range(n..=n))
+        ArrayPattern::Dcbor(dcbor_pattern::ArrayPattern::with_length_range(
+            n..=n,
+        ))
+    }
+
+    /// Creates a new `ArrayPattern` that matches arrays whose element at
+    /// the 0-based `index` matches `element`.
+    pub fn at_index(index: usize, element: DCBORPattern) -> Self {
+        ArrayPattern::AtIndex { index, element }
     }
 
     /// Creates a new `ArrayPattern` from a dcbor-pattern.
     pub fn from_dcbor_pattern(pattern: DCBORPattern) -> Self {
-        ArrayPattern(dcbor_pattern::ArrayPattern::with_elements(pattern))
+        ArrayPattern::Dcbor(dcbor_pattern::ArrayPattern::with_elements(pattern))
     }
 
     /// Creates a new `ArrayPattern` from a dcbor-pattern ArrayPattern.
     pub fn from_dcbor_array_pattern(
         array_pattern: dcbor_pattern::ArrayPattern,
     ) -> Self {
-        ArrayPattern(array_pattern)
+        ArrayPattern::Dcbor(array_pattern)
+    }
+
+    fn matches_cbor(&self, cbor: &CBOR) -> bool {
+        match self {
+            ArrayPattern::Dcbor(pattern) => pattern.matches(cbor),
+            ArrayPattern::AtIndex { index, element } => match cbor.as_case() {
+                CBORCase::Array(items) => {
+                    items.get(*index).is_some_and(|item| element.matches(item))
+                }
+                _ => false,
+            },
+        }
     }
 }
 
 impl std::hash::Hash for ArrayPattern {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        // Hash the string representation since dcbor_pattern::ArrayPattern
-        // doesn't implement Hash
-        self.0.to_string().hash(state);
+        // Hash the string representation since dcbor-pattern's patterns
+        // don't implement Hash
+        self.to_string().hash(state);
     }
 }
 
@@ -55,7 +86,7 @@ impl Matcher for ArrayPattern {
     ) -> (Vec<Path>, HashMap<String, Vec<Path>>) {
         let paths = if let Some(cbor_value) = haystack.subject().as_leaf() {
             // Use dcbor-pattern to match against the CBOR value directly
-            if self.0.matches(&cbor_value) {
+            if self.matches_cbor(&cbor_value) {
                 vec![vec![haystack.clone()]]
             } else {
                 vec![]
@@ -86,8 +117,13 @@ impl Matcher for ArrayPattern {
 
 impl std::fmt::Display for ArrayPattern {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        // Delegate to dcbor-pattern's Display implementation
-        write!(f, "{}", self.0)
+        match self {
+            // Delegate to dcbor-pattern's Display implementation
+            ArrayPattern::Dcbor(pattern) => write!(f, "{}", pattern),
+            ArrayPattern::AtIndex { index, element } => {
+                write!(f, "arr[{}:{}]", index, element)
+            }
+        }
     }
 }
 
@@ -151,5 +187,24 @@ mod tests {
         assert_eq!(ArrayPattern::count(3).to_string(), "[{3}]");
         assert_eq!(ArrayPattern::interval(2..=5).to_string(), "[{2,5}]");
         assert_eq!(ArrayPattern::interval(3..).to_string(), "[{3,}]");
+        assert_eq!(
+            ArrayPattern::at_index(0, DCBORPattern::any_text()).to_string(),
+            "arr[0:text]"
+        );
+    }
+
+    #[test]
+    fn test_array_pattern_at_index() {
+        let envelope = Envelope::new(vec![1, 2, 3].to_cbor());
+
+        let pattern = ArrayPattern::at_index(1, DCBORPattern::number(2));
+        assert_eq!(pattern.paths(&envelope), vec![vec![envelope.clone()]]);
+
+        let pattern = ArrayPattern::at_index(0, DCBORPattern::number(2));
+        assert!(pattern.paths(&envelope).is_empty());
+
+        // Out of bounds
+        let pattern = ArrayPattern::at_index(3, DCBORPattern::any());
+        assert!(pattern.paths(&envelope).is_empty());
     }
 }
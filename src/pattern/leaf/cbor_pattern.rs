@@ -7,7 +7,7 @@ use crate::{
 
 /// Pattern for matching CBOR values with support for exact values and advanced
 /// pattern matching.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub enum CBORPattern {
     /// Matches any CBOR value.
     Any,
@@ -15,8 +15,26 @@ pub enum CBORPattern {
     Value(CBOR),
     /// Matches CBOR values using dcbor-pattern expressions.
     Pattern(DCBORPattern),
+    /// Matches the encoded bytes of CBOR values against a binary regex.
+    BytesRegex(regex::bytes::Regex),
 }
 
+impl PartialEq for CBORPattern {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (CBORPattern::Any, CBORPattern::Any) => true,
+            (CBORPattern::Value(a), CBORPattern::Value(b)) => a == b,
+            (CBORPattern::Pattern(a), CBORPattern::Pattern(b)) => a == b,
+            (CBORPattern::BytesRegex(a), CBORPattern::BytesRegex(b)) => {
+                a.as_str() == b.as_str()
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for CBORPattern {}
+
 impl CBORPattern {
     /// Creates a new `CBORPattern` that matches any CBOR value.
     pub fn any() -> Self { CBORPattern::Any }
@@ -32,6 +50,12 @@ impl CBORPattern {
         CBORPattern::Pattern(pattern)
     }
 
+    /// Creates a new `CBORPattern` that matches CBOR values whose encoded
+    /// bytes match a binary regex.
+    pub fn bytes_regex(regex: regex::bytes::Regex) -> Self {
+        CBORPattern::BytesRegex(regex)
+    }
+
     /// Creates a new `CBORPattern` from a dcbor-pattern Pattern.
     pub fn from_dcbor_pattern(dcbor_pattern: DCBORPattern) -> Self {
         CBORPattern::Pattern(dcbor_pattern)
@@ -120,6 +144,40 @@ impl CBORPattern {
             }
         }
     }
+
+    /// Returns the maximum nesting depth of the captures in a dcbor pattern,
+    /// found the same way as `collect_dcbor_capture_names`.
+    pub(crate) fn dcbor_capture_depth(&self) -> usize {
+        let CBORPattern::Pattern(dcbor_pattern) = self else {
+            return 0;
+        };
+        let pattern_str = dcbor_pattern.to_string();
+
+        // The parenthesis depth at which each enclosing capture opened.
+        let mut open = Vec::new();
+        let mut depth = 0usize;
+        let mut max_depth = 0;
+        let mut chars = pattern_str.chars().peekable();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '@' => {
+                    // Skip the name; its '(' is counted below.
+                    while chars.next_if(|&c| c != '(').is_some() {}
+                    open.push(depth);
+                    max_depth = max_depth.max(open.len());
+                }
+                '(' => depth += 1,
+                ')' => {
+                    depth = depth.saturating_sub(1);
+                    if open.last() == Some(&depth) {
+                        open.pop();
+                    }
+                }
+                _ => {}
+            }
+        }
+        max_depth
+    }
 }
 
 impl std::hash::Hash for CBORPattern {
@@ -138,6 +196,11 @@ impl std::hash::Hash for CBORPattern {
                 // implement Hash
                 pattern.to_string().hash(state);
             }
+            CBORPattern::BytesRegex(regex) => {
+                3u8.hash(state);
+                // Regex does not implement Hash, so we hash its pattern string.
+                regex.as_str().hash(state);
+            }
         }
     }
 }
@@ -208,6 +271,16 @@ impl Matcher for CBORPattern {
                         (vec![], std::collections::HashMap::new())
                     }
                 }
+                CBORPattern::BytesRegex(regex) => {
+                    if regex.is_match(&known_value.to_cbor_data()) {
+                        (
+                            vec![vec![haystack.clone()]],
+                            std::collections::HashMap::new(),
+                        )
+                    } else {
+                        (vec![], std::collections::HashMap::new())
+                    }
+                }
             };
         }
 
@@ -280,6 +353,16 @@ impl Matcher for CBORPattern {
                     (vec![], std::collections::HashMap::new())
                 }
             }
+            CBORPattern::BytesRegex(regex) => {
+                if regex.is_match(&subject_cbor.to_cbor_data()) {
+                    (
+                        vec![vec![haystack.clone()]],
+                        std::collections::HashMap::new(),
+                    )
+                } else {
+                    (vec![], std::collections::HashMap::new())
+                }
+            }
         }
     }
 
@@ -318,7 +401,11 @@ impl std::fmt::Display for CBORPattern {
                 write!(f, "cbor({})", cbor.diagnostic_flat())
             }
             CBORPattern::Pattern(pattern) => {
-                write!(f, "cbor(/{}/)", pattern)
+                // Escape `/` so it does not end the expression.
+                write!(f, "cbor(/{}/)", pattern.to_string().replace('/', "\\/"))
+            }
+            CBORPattern::BytesRegex(regex) => {
+                write!(f, "cbor_bytes(h'/{}/')", regex)
             }
         }
     }
@@ -0,0 +1,180 @@
+use std::{collections::HashMap, ops::RangeInclusive};
+
+use bc_envelope::prelude::*;
+
+use crate::{
+    Pattern,
+    pattern::{Matcher, Path, compile_as_atomic, leaf::LeafPattern, vm::Instr},
+};
+
+/// Pattern for matching numbers encoded as CBOR integers.
+///
+/// Unlike [`NumberPattern`](super::NumberPattern), which compares values as
+/// `f64`, this only matches CBOR major types 0 and 1, and compares values
+/// exactly. Note that dCBOR encodes a float with no fractional part, such as
+/// `42.0`, as the integer `42`, so it matches too.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub enum IntegerPattern {
+    /// Matches any integer.
+    Any,
+    /// Matches the exact integer.
+    Value(i64),
+    /// Matches integers within the inclusive range.
+    Range(RangeInclusive<i64>),
+    /// Matches integers greater than the value.
+    GreaterThan(i64),
+    /// Matches integers less than the value.
+    LessThan(i64),
+}
+
+impl IntegerPattern {
+    /// Creates a new `IntegerPattern` that matches any integer.
+    pub fn any() -> Self { IntegerPattern::Any }
+
+    /// Creates a new `IntegerPattern` that matches the exact integer.
+    pub fn value(value: i64) -> Self { IntegerPattern::Value(value) }
+
+    /// Creates a new `IntegerPattern` that matches integers within the
+    /// specified range.
+    pub fn range(range: RangeInclusive<i64>) -> Self {
+        IntegerPattern::Range(range)
+    }
+
+    /// Creates a new `IntegerPattern` that matches integers greater than the
+    /// specified value.
+    pub fn greater_than(value: i64) -> Self {
+        IntegerPattern::GreaterThan(value)
+    }
+
+    /// Creates a new `IntegerPattern` that matches integers less than the
+    /// specified value.
+    pub fn less_than(value: i64) -> Self { IntegerPattern::LessThan(value) }
+
+    fn matches_cbor(&self, cbor: &CBOR) -> bool {
+        // Widen to i128 so that every CBOR integer, including those outside
+        // the range of i64, compares exactly.
+        let value = match cbor.as_case() {
+            CBORCase::Unsigned(n) => *n as i128,
+            CBORCase::Negative(n) => -1 - *n as i128,
+            _ => return false,
+        };
+        match self {
+            IntegerPattern::Any => true,
+            IntegerPattern::Value(v) => value == *v as i128,
+            IntegerPattern::Range(range) => {
+                (*range.start() as i128..=*range.end() as i128).contains(&value)
+            }
+            IntegerPattern::GreaterThan(v) => value > *v as i128,
+            IntegerPattern::LessThan(v) => value < *v as i128,
+        }
+    }
+}
+
+impl Matcher for IntegerPattern {
+    fn paths_with_captures(
+        &self,
+        haystack: &Envelope,
+    ) -> (Vec<Path>, HashMap<String, Vec<Path>>) {
+        let paths = match haystack.subject().as_leaf() {
+            Some(cbor) if self.matches_cbor(&cbor) => {
+                vec![vec![haystack.clone()]]
+            }
+            _ => vec![],
+        };
+        (paths, HashMap::new())
+    }
+
+    fn compile(
+        &self,
+        code: &mut Vec<Instr>,
+        literals: &mut Vec<Pattern>,
+        captures: &mut Vec<String>,
+    ) {
+        compile_as_atomic(
+            &Pattern::Leaf(LeafPattern::Integer(self.clone())),
+            code,
+            literals,
+            captures,
+        );
+    }
+}
+
+impl std::fmt::Display for IntegerPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IntegerPattern::Any => write!(f, "integer"),
+            IntegerPattern::Value(value) => write!(f, "integer({})", value),
+            IntegerPattern::Range(range) => {
+                write!(f, "integer({}...{})", range.start(), range.end())
+            }
+            IntegerPattern::GreaterThan(value) => {
+                write!(f, "integer(>{})", value)
+            }
+            IntegerPattern::LessThan(value) => write!(f, "integer(<{})", value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integer_pattern_display() {
+        assert_eq!(IntegerPattern::any().to_string(), "integer");
+        assert_eq!(IntegerPattern::value(42).to_string(), "integer(42)");
+        assert_eq!(IntegerPattern::value(-7).to_string(), "integer(-7)");
+        assert_eq!(
+            IntegerPattern::range(-5..=5).to_string(),
+            "integer(-5...5)"
+        );
+        assert_eq!(IntegerPattern::greater_than(0).to_string(), "integer(>0)");
+        assert_eq!(IntegerPattern::less_than(-3).to_string(), "integer(<-3)");
+    }
+
+    #[test]
+    fn test_integer_pattern_matching() {
+        let positive = Envelope::new(42);
+        let negative = Envelope::new(-10);
+        let float = Envelope::new(3.5);
+        let text = Envelope::new("42");
+
+        let any = IntegerPattern::any();
+        assert!(any.matches(&positive));
+        assert!(any.matches(&negative));
+        assert!(!any.matches(&float));
+        assert!(!any.matches(&text));
+
+        assert!(IntegerPattern::value(42).matches(&positive));
+        assert!(!IntegerPattern::value(43).matches(&positive));
+        assert!(IntegerPattern::value(-10).matches(&negative));
+
+        let range = IntegerPattern::range(-10..=0);
+        assert!(range.matches(&negative));
+        assert!(!range.matches(&positive));
+
+        assert!(IntegerPattern::greater_than(41).matches(&positive));
+        assert!(!IntegerPattern::greater_than(42).matches(&positive));
+        assert!(IntegerPattern::less_than(-9).matches(&negative));
+        assert!(!IntegerPattern::less_than(-10).matches(&negative));
+    }
+
+    #[test]
+    fn test_integer_pattern_extremes() {
+        let max = Envelope::new(u64::MAX);
+        let min = Envelope::new(i64::MIN);
+
+        assert!(IntegerPattern::any().matches(&max));
+        assert!(IntegerPattern::greater_than(i64::MAX).matches(&max));
+        assert!(!IntegerPattern::value(-1).matches(&max));
+        assert!(IntegerPattern::value(i64::MIN).matches(&min));
+        assert!(!IntegerPattern::less_than(i64::MIN).matches(&min));
+    }
+
+    #[test]
+    fn test_integral_floats_are_integers() {
+        // dCBOR reduces floats with no fractional part to integers.
+        assert!(IntegerPattern::value(42).matches(&Envelope::new(42.0)));
+        assert!(!IntegerPattern::any().matches(&Envelope::new(42.5)));
+    }
+}
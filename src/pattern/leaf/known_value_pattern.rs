@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use bc_envelope::prelude::*;
-use known_values::KnownValue;
+use known_values::{KnownValue, KnownValuesStore};
 
 use crate::{
     Pattern,
@@ -45,6 +45,13 @@ impl KnownValuePattern {
     }
 }
 
+/// Looks up a known value by its assigned name in the global known values
+/// registry.
+pub(crate) fn registered_known_value(name: &str) -> Option<KnownValue> {
+    let store = known_values::KNOWN_VALUES.get();
+    KnownValuesStore::known_value_for_name(name, store.as_ref())
+}
+
 impl PartialEq for KnownValuePattern {
     fn eq(&self, other: &Self) -> bool { self.0 == other.0 }
 }
@@ -3,28 +3,68 @@ use std::{collections::HashMap, ops::RangeBounds};
 use bc_envelope::prelude::*;
 
 use crate::{
-    DCBORMatcher, Pattern,
+    DCBORMatcher, DCBORPattern, Pattern,
     pattern::{Matcher, Path, compile_as_atomic, leaf::LeafPattern, vm::Instr},
 };
 
 /// Pattern for matching maps.
-/// This delegates directly to dcbor-pattern for map matching.
+/// Except for the key patterns, this delegates directly to dcbor-pattern for
+/// map matching.
 #[derive(Debug, Clone, Eq, PartialEq)]
-pub struct MapPattern(dcbor_pattern::MapPattern);
+pub enum MapPattern {
+    /// Matches maps as described by a dcbor-pattern.
+    Dcbor(dcbor_pattern::MapPattern),
+    /// Matches maps with at least one key matching the pattern.
+    RequiredKey(DCBORPattern),
+    /// Matches maps with at least one entry whose key and value match the
+    /// patterns.
+    RequiredKeyValue { key: DCBORPattern, value: DCBORPattern },
+}
 
 impl MapPattern {
     /// Creates a new `MapPattern` that matches any map.
-    pub fn any() -> Self { MapPattern(dcbor_pattern::MapPattern::any()) }
+    pub fn any() -> Self {
+        MapPattern::Dcbor(dcbor_pattern::MapPattern::any())
+    }
 
     /// Creates a new `MapPattern` that matches maps with a specific count of
     /// entries.
     pub fn interval(interval: impl RangeBounds<usize>) -> Self {
-        MapPattern(dcbor_pattern::MapPattern::with_length_range(interval))
+        MapPattern::Dcbor(dcbor_pattern::MapPattern::with_length_range(
+            interval,
+        ))
+    }
+
+    /// Creates a new `MapPattern` that matches maps with at least one key
+    /// matching `key`.
+    pub fn required_key(key: DCBORPattern) -> Self {
+        MapPattern::RequiredKey(key)
+    }
+
+    /// Creates a new `MapPattern` that matches maps with at least one entry
+    /// whose key matches `key` and whose value matches `value`.
+    pub fn required_key_value(key: DCBORPattern, value: DCBORPattern) -> Self {
+        MapPattern::RequiredKeyValue { key, value }
     }
 
     /// Creates a new `MapPattern` from a dcbor-pattern MapPattern.
     pub fn from_dcbor_pattern(map_pattern: dcbor_pattern::MapPattern) -> Self {
-        MapPattern(map_pattern)
+        MapPattern::Dcbor(map_pattern)
+    }
+
+    fn matches_cbor(&self, cbor: &CBOR) -> bool {
+        let (key_pattern, value_pattern) = match self {
+            MapPattern::Dcbor(pattern) => return pattern.matches(cbor),
+            MapPattern::RequiredKey(key) => (key, None),
+            MapPattern::RequiredKeyValue { key, value } => (key, Some(value)),
+        };
+        match cbor.as_case() {
+            CBORCase::Map(map) => map.iter().any(|(key, value)| {
+                key_pattern.matches(key)
+                    && value_pattern.is_none_or(|p| p.matches(value))
+            }),
+            _ => false,
+        }
     }
 }
 
@@ -35,7 +75,7 @@ impl Matcher for MapPattern {
     ) -> (Vec<Path>, HashMap<String, Vec<Path>>) {
         let paths = if let Some(cbor_value) = haystack.subject().as_leaf() {
             // Use dcbor-pattern to match against the CBOR value directly
-            if self.0.matches(&cbor_value) {
+            if self.matches_cbor(&cbor_value) {
                 vec![vec![haystack.clone()]]
             } else {
                 vec![]
@@ -66,16 +106,22 @@ impl Matcher for MapPattern {
 
 impl std::hash::Hash for MapPattern {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        // Hash the string representation since dcbor_pattern::MapPattern
-        // doesn't implement Hash
-        self.0.to_string().hash(state);
+        // Hash the string representation since dcbor-pattern's patterns
+        // don't implement Hash
+        self.to_string().hash(state);
     }
 }
 
 impl std::fmt::Display for MapPattern {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        // Delegate to dcbor-pattern's Display implementation
-        write!(f, "{}", self.0)
+        match self {
+            // Delegate to dcbor-pattern's Display implementation
+            MapPattern::Dcbor(pattern) => write!(f, "{}", pattern),
+            MapPattern::RequiredKey(key) => write!(f, "map(key: {})", key),
+            MapPattern::RequiredKeyValue { key, value } => {
+                write!(f, "map(key: {}, val: {})", key, value)
+            }
+        }
     }
 }
 
@@ -139,5 +185,42 @@ mod tests {
 
         let pattern = MapPattern::interval(1..);
         assert_eq!(pattern.to_string(), "{{1,}}");
+
+        let pattern = MapPattern::required_key(DCBORPattern::text("age"));
+        assert_eq!(pattern.to_string(), r#"map(key: "age")"#);
+
+        let pattern = MapPattern::required_key_value(
+            DCBORPattern::text("age"),
+            DCBORPattern::any_number(),
+        );
+        assert_eq!(pattern.to_string(), r#"map(key: "age", val: number)"#);
+    }
+
+    #[test]
+    fn test_map_pattern_required_key() {
+        let mut cbor_map = Map::new();
+        cbor_map.insert("name", "Alice");
+        cbor_map.insert("age", 30);
+        let envelope = Envelope::new(cbor_map);
+
+        let pattern = MapPattern::required_key(DCBORPattern::text("age"));
+        assert_eq!(pattern.paths(&envelope), vec![vec![envelope.clone()]]);
+        let pattern = MapPattern::required_key(DCBORPattern::text("email"));
+        assert!(pattern.paths(&envelope).is_empty());
+
+        // The value must belong to the same entry as the key.
+        let pattern = MapPattern::required_key_value(
+            DCBORPattern::text("age"),
+            DCBORPattern::any_number(),
+        );
+        assert_eq!(pattern.paths(&envelope).len(), 1);
+        let pattern = MapPattern::required_key_value(
+            DCBORPattern::text("name"),
+            DCBORPattern::any_number(),
+        );
+        assert!(pattern.paths(&envelope).is_empty());
+
+        let pattern = MapPattern::required_key(DCBORPattern::any());
+        assert!(pattern.paths(&Envelope::new("age")).is_empty());
     }
 }
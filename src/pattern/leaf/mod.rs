@@ -5,6 +5,7 @@ mod bool_pattern;
 mod byte_string_pattern;
 mod cbor_pattern;
 mod date_pattern;
+mod integer_pattern;
 mod known_value_pattern;
 mod map_pattern;
 mod null_pattern;
@@ -20,7 +21,10 @@ pub(crate) use bool_pattern::BoolPattern;
 pub(crate) use byte_string_pattern::ByteStringPattern;
 pub(crate) use cbor_pattern::CBORPattern;
 pub(crate) use date_pattern::DatePattern;
-pub(crate) use known_value_pattern::KnownValuePattern;
+pub(crate) use integer_pattern::IntegerPattern;
+pub(crate) use known_value_pattern::{
+    KnownValuePattern, registered_known_value,
+};
 pub(crate) use map_pattern::MapPattern;
 pub(crate) use null_pattern::NullPattern;
 pub(crate) use number_pattern::NumberPattern;
@@ -39,6 +43,8 @@ pub enum LeafPattern {
     Cbor(CBORPattern),
     /// Matches a numeric value.
     Number(NumberPattern),
+    /// Matches an integer value.
+    Integer(IntegerPattern),
     /// Matches a text value.
     Text(TextPattern),
     /// Matches a byte string value.
@@ -69,6 +75,9 @@ impl Matcher for LeafPattern {
             LeafPattern::Number(pattern) => {
                 pattern.paths_with_captures(haystack)
             }
+            LeafPattern::Integer(pattern) => {
+                pattern.paths_with_captures(haystack)
+            }
             LeafPattern::Text(pattern) => pattern.paths_with_captures(haystack),
             LeafPattern::ByteString(pattern) => {
                 pattern.paths_with_captures(haystack)
@@ -100,6 +109,9 @@ impl Matcher for LeafPattern {
             LeafPattern::Number(pattern) => {
                 pattern.compile(code, literals, captures);
             }
+            LeafPattern::Integer(pattern) => {
+                pattern.compile(code, literals, captures);
+            }
             LeafPattern::Text(pattern) => {
                 pattern.compile(code, literals, captures);
             }
@@ -134,6 +146,7 @@ impl Matcher for LeafPattern {
         match self {
             LeafPattern::Cbor(pattern) => pattern.is_complex(),
             LeafPattern::Number(pattern) => pattern.is_complex(),
+            LeafPattern::Integer(pattern) => pattern.is_complex(),
             LeafPattern::Text(pattern) => pattern.is_complex(),
             LeafPattern::ByteString(pattern) => pattern.is_complex(),
             LeafPattern::Tag(pattern) => pattern.is_complex(),
@@ -152,6 +165,7 @@ impl std::fmt::Display for LeafPattern {
         match self {
             LeafPattern::Cbor(pattern) => write!(f, "{}", pattern),
             LeafPattern::Number(pattern) => write!(f, "{}", pattern),
+            LeafPattern::Integer(pattern) => write!(f, "{}", pattern),
             LeafPattern::Text(pattern) => write!(f, "{}", pattern),
             LeafPattern::ByteString(pattern) => write!(f, "{}", pattern),
             LeafPattern::Tag(pattern) => write!(f, "{}", pattern),
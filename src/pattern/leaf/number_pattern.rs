@@ -3,27 +3,43 @@ use std::collections::HashMap;
 use bc_envelope::prelude::*;
 
 use crate::{
-    Pattern,
+    Error, Pattern, Result,
     pattern::{Matcher, Path, compile_as_atomic, leaf::LeafPattern, vm::Instr},
 };
 
-/// Pattern for matching number values. This is a wrapper around
-/// dcbor_pattern::NumberPattern that provides envelope-specific integration.
+/// Pattern for matching number values. Most variants wrap
+/// dcbor_pattern::NumberPattern and provide envelope-specific integration.
 #[derive(Debug, Clone)]
-pub struct NumberPattern(dcbor_pattern::NumberPattern);
+pub enum NumberPattern {
+    /// A number pattern evaluated by dcbor-pattern.
+    Dcbor(dcbor_pattern::NumberPattern),
+    /// Matches a number exactly equal to any value in the list.
+    ///
+    /// Comparison uses `f64` equality, so `0` and `-0` are the same value.
+    /// NaN never equals itself under `f64` equality, so a NaN in the list is
+    /// treated specially: it matches any NaN number.
+    OneOf(Vec<f64>),
+    /// Matches positive or negative infinity.
+    Infinite,
+    /// Matches numbers that are neither NaN nor infinite.
+    Finite,
+    /// Matches a finite number that is a whole multiple of the divisor, to
+    /// within floating-point rounding.
+    DivisibleBy(f64),
+}
 
 // Re-export the dcbor-pattern NumberPattern methods through associated
 // functions
 impl NumberPattern {
     /// Creates a new `NumberPattern` that matches any number.
-    pub fn any() -> Self { Self(dcbor_pattern::NumberPattern::any()) }
+    pub fn any() -> Self { Self::Dcbor(dcbor_pattern::NumberPattern::any()) }
 
     /// Creates a new `NumberPattern` that matches the exact number.
     pub fn exact<T>(value: T) -> Self
     where
         T: Into<f64>,
     {
-        Self(dcbor_pattern::NumberPattern::value(value))
+        Self::Dcbor(dcbor_pattern::NumberPattern::value(value))
     }
 
     /// Creates a new `NumberPattern` that matches numbers within the specified
@@ -32,7 +48,7 @@ impl NumberPattern {
     where
         A: Into<f64> + Copy,
     {
-        Self(dcbor_pattern::NumberPattern::range(range))
+        Self::Dcbor(dcbor_pattern::NumberPattern::range(range))
     }
 
     /// Creates a new `NumberPattern` that matches numbers greater than the
@@ -41,7 +57,7 @@ impl NumberPattern {
     where
         T: Into<f64>,
     {
-        Self(dcbor_pattern::NumberPattern::greater_than(value))
+        Self::Dcbor(dcbor_pattern::NumberPattern::greater_than(value))
     }
 
     /// Creates a new `NumberPattern` that matches numbers greater than or
@@ -50,7 +66,7 @@ impl NumberPattern {
     where
         T: Into<f64>,
     {
-        Self(dcbor_pattern::NumberPattern::greater_than_or_equal(value))
+        Self::Dcbor(dcbor_pattern::NumberPattern::greater_than_or_equal(value))
     }
 
     /// Creates a new `NumberPattern` that matches numbers less than the
@@ -59,7 +75,7 @@ impl NumberPattern {
     where
         T: Into<f64>,
     {
-        Self(dcbor_pattern::NumberPattern::less_than(value))
+        Self::Dcbor(dcbor_pattern::NumberPattern::less_than(value))
     }
 
     /// Creates a new `NumberPattern` that matches numbers less than or equal
@@ -68,28 +84,140 @@ impl NumberPattern {
     where
         T: Into<f64>,
     {
-        Self(dcbor_pattern::NumberPattern::less_than_or_equal(value))
+        Self::Dcbor(dcbor_pattern::NumberPattern::less_than_or_equal(value))
     }
 
     /// Creates a new `NumberPattern` that matches NaN values.
-    pub fn nan() -> Self { Self(dcbor_pattern::NumberPattern::nan()) }
+    pub fn nan() -> Self { Self::Dcbor(dcbor_pattern::NumberPattern::nan()) }
+
+    /// Creates a new `NumberPattern` that matches positive infinity.
+    pub fn infinity() -> Self {
+        Self::Dcbor(dcbor_pattern::NumberPattern::infinity())
+    }
+
+    /// Creates a new `NumberPattern` that matches negative infinity.
+    pub fn neg_infinity() -> Self {
+        Self::Dcbor(dcbor_pattern::NumberPattern::neg_infinity())
+    }
+
+    /// Creates a new `NumberPattern` that matches positive or negative
+    /// infinity.
+    pub fn infinite() -> Self { Self::Infinite }
+
+    /// Creates a new `NumberPattern` that matches numbers that are neither
+    /// NaN nor infinite.
+    pub fn finite() -> Self { Self::Finite }
+
+    /// Creates a new `NumberPattern` that matches numbers that are a whole
+    /// multiple of `divisor`.
+    ///
+    /// Returns an error if `divisor` is zero, infinite, or NaN.
+    pub fn divisible_by(divisor: f64) -> Result<Self> {
+        if divisor == 0.0 || !divisor.is_finite() {
+            return Err(Error::InvalidDivisorValue(divisor));
+        }
+        Ok(Self::DivisibleBy(divisor))
+    }
 
     /// Creates a new `NumberPattern` from a dcbor-pattern NumberPattern.
     pub fn from_dcbor_pattern(
         dcbor_pattern: dcbor_pattern::NumberPattern,
     ) -> Self {
-        Self(dcbor_pattern)
+        Self::Dcbor(dcbor_pattern)
+    }
+
+    /// Creates a new `NumberPattern` that matches numbers equal to any of the
+    /// given values.
+    pub fn one_of<T: Into<f64>>(values: impl IntoIterator<Item = T>) -> Self {
+        Self::OneOf(values.into_iter().map(Into::into).collect())
+    }
+
+    fn matches_cbor(&self, cbor: &CBOR) -> bool {
+        match self {
+            // Delegate to dcbor-pattern for CBOR matching using paths() method
+            NumberPattern::Dcbor(pattern) => {
+                !dcbor_pattern::Matcher::paths(pattern, cbor).is_empty()
+            }
+            NumberPattern::OneOf(values) => {
+                let Ok(value) = f64::try_from(cbor.clone()) else {
+                    return false;
+                };
+                if value.is_nan() {
+                    values.iter().any(|v| v.is_nan())
+                } else {
+                    values.contains(&value)
+                }
+            }
+            NumberPattern::Infinite => f64::try_from(cbor.clone())
+                .is_ok_and(|value| value.is_infinite()),
+            NumberPattern::Finite => f64::try_from(cbor.clone())
+                .is_ok_and(|value| value.is_finite()),
+            NumberPattern::DivisibleBy(divisor) => {
+                let Ok(value) = f64::try_from(cbor.clone()) else {
+                    return false;
+                };
+                if !value.is_finite() {
+                    return false;
+                }
+                // `%` is exact, so an integral divisor needs no tolerance.
+                // Any other divisor may not be representable, and its error
+                // grows with the quotient, so allow a few units in the last
+                // place of the larger of the two, at either end of the
+                // remainder's range.
+                let remainder = (value % divisor).abs();
+                if divisor.fract() == 0.0 {
+                    return remainder == 0.0;
+                }
+                let tolerance =
+                    4.0 * f64::EPSILON * value.abs().max(divisor.abs());
+                remainder < tolerance
+                    || divisor.abs() - remainder < tolerance
+            }
+        }
     }
 }
 
 impl PartialEq for NumberPattern {
-    fn eq(&self, other: &Self) -> bool { self.0 == other.0 }
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (NumberPattern::Dcbor(a), NumberPattern::Dcbor(b)) => a == b,
+            (NumberPattern::OneOf(a), NumberPattern::OneOf(b)) => {
+                a.len() == b.len()
+                    && a.iter().zip(b).all(|(a, b)| a.to_bits() == b.to_bits())
+            }
+            (NumberPattern::Infinite, NumberPattern::Infinite) => true,
+            (NumberPattern::Finite, NumberPattern::Finite) => true,
+            (NumberPattern::DivisibleBy(a), NumberPattern::DivisibleBy(b)) => {
+                a.to_bits() == b.to_bits()
+            }
+            _ => false,
+        }
+    }
 }
 
 impl Eq for NumberPattern {}
 
 impl std::hash::Hash for NumberPattern {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) { self.0.hash(state); }
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        match self {
+            NumberPattern::Dcbor(pattern) => {
+                0u8.hash(state);
+                pattern.hash(state);
+            }
+            NumberPattern::OneOf(values) => {
+                1u8.hash(state);
+                for value in values {
+                    value.to_bits().hash(state);
+                }
+            }
+            NumberPattern::Infinite => 2u8.hash(state),
+            NumberPattern::Finite => 4u8.hash(state),
+            NumberPattern::DivisibleBy(divisor) => {
+                3u8.hash(state);
+                divisor.to_bits().hash(state);
+            }
+        }
+    }
 }
 
 impl Matcher for NumberPattern {
@@ -100,13 +228,9 @@ impl Matcher for NumberPattern {
         // Try to extract CBOR from the envelope using the existing as_leaf()
         // method
         let paths = if let Some(cbor) = haystack.subject().as_leaf() {
-            // Delegate to dcbor-pattern for CBOR matching using paths() method
-            // NumberPattern doesn't support captures, so we only get paths
-            let dcbor_paths = dcbor_pattern::Matcher::paths(&self.0, &cbor);
-
-            // For simple leaf patterns, if dcbor-pattern found matches, return
-            // the envelope
-            if !dcbor_paths.is_empty() {
+            // For simple leaf patterns, if the number matched, return the
+            // envelope. NumberPattern doesn't support captures.
+            if self.matches_cbor(&cbor) {
                 vec![vec![haystack.clone()]]
             } else {
                 vec![]
@@ -135,7 +259,26 @@ impl Matcher for NumberPattern {
 
 impl std::fmt::Display for NumberPattern {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+        match self {
+            NumberPattern::Dcbor(pattern) => write!(f, "{}", pattern),
+            NumberPattern::OneOf(values) => {
+                let values: Vec<_> = values
+                    .iter()
+                    .map(|v| match *v {
+                        v if v.is_nan() => "NaN".to_string(),
+                        f64::INFINITY => "Infinity".to_string(),
+                        f64::NEG_INFINITY => "-Infinity".to_string(),
+                        v => v.to_string(),
+                    })
+                    .collect();
+                write!(f, "number(one_of: [{}])", values.join(", "))
+            }
+            NumberPattern::Infinite => write!(f, "number(infinite)"),
+            NumberPattern::Finite => write!(f, "number(finite)"),
+            NumberPattern::DivisibleBy(divisor) => {
+                write!(f, "number(div: {})", divisor)
+            }
+        }
     }
 }
 
@@ -158,6 +301,23 @@ mod tests {
         assert_eq!(NumberPattern::less_than(5.0).to_string(), "<5");
         assert_eq!(NumberPattern::less_than_or_equal(5.0).to_string(), "<=5");
         assert_eq!(NumberPattern::nan().to_string(), "NaN");
+        assert_eq!(
+            NumberPattern::one_of([1.0, 2.5, f64::NAN, f64::INFINITY])
+                .to_string(),
+            "number(one_of: [1, 2.5, NaN, Infinity])"
+        );
+        assert_eq!(NumberPattern::infinity().to_string(), "Infinity");
+        assert_eq!(NumberPattern::neg_infinity().to_string(), "-Infinity");
+        assert_eq!(NumberPattern::infinite().to_string(), "number(infinite)");
+        assert_eq!(NumberPattern::finite().to_string(), "number(finite)");
+        assert_eq!(
+            NumberPattern::divisible_by(100.0).unwrap().to_string(),
+            "number(div: 100)"
+        );
+        assert_eq!(
+            NumberPattern::divisible_by(0.25).unwrap().to_string(),
+            "number(div: 0.25)"
+        );
     }
 
     #[test]
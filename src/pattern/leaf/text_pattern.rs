@@ -1,49 +1,155 @@
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeSet, HashMap},
+    ops::{Bound, RangeBounds},
+};
 
 use bc_envelope::prelude::*;
 
 use crate::{
-    Pattern,
+    Error, Interval, Pattern, Result,
     pattern::{Matcher, Path, compile_as_atomic, leaf::LeafPattern, vm::Instr},
 };
 
-/// Pattern for matching text values. This is a wrapper around
-/// dcbor_pattern::TextPattern that provides envelope-specific integration.
-#[derive(Debug, Clone)]
-pub struct TextPattern(dcbor_pattern::TextPattern);
+/// Pattern for matching text values. Most variants wrap
+/// dcbor_pattern::TextPattern and provide envelope-specific integration.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TextPattern {
+    /// A text pattern evaluated by dcbor-pattern.
+    Dcbor(dcbor_pattern::TextPattern),
+    /// Matches text equal to any value in the set, with a single lookup
+    /// rather than one comparison per value.
+    OneOf(BTreeSet<String>),
+    /// Matches text against a glob, where `*` matches any run of
+    /// characters and `?` matches any single character. The glob is kept
+    /// alongside the equivalent regex pattern so it can be displayed.
+    Glob { glob: String, pattern: dcbor_pattern::TextPattern },
+    /// Matches text that starts with the prefix.
+    StartsWith(String),
+    /// Matches text that ends with the suffix.
+    EndsWith(String),
+    /// Matches text whose length in characters is within the interval.
+    Length(Interval),
+}
 
 // Re-export the dcbor-pattern TextPattern enum variants through associated
 // functions
 impl TextPattern {
     /// Creates a new `TextPattern` that matches any text.
-    pub fn any() -> Self { Self(dcbor_pattern::TextPattern::any()) }
+    pub fn any() -> Self { Self::Dcbor(dcbor_pattern::TextPattern::any()) }
 
     /// Creates a new `TextPattern` that matches the specific text.
     pub fn value<T: Into<String>>(value: T) -> Self {
-        Self(dcbor_pattern::TextPattern::value(value))
+        Self::Dcbor(dcbor_pattern::TextPattern::value(value))
     }
 
     /// Creates a new `TextPattern` that matches the regex for a text.
     pub fn regex(regex: regex::Regex) -> Self {
-        Self(dcbor_pattern::TextPattern::regex(regex))
+        Self::Dcbor(dcbor_pattern::TextPattern::regex(regex))
+    }
+
+    /// Creates a new `TextPattern` that matches text equal to any of the
+    /// given values.
+    pub fn one_of<T: Into<String>>(
+        values: impl IntoIterator<Item = T>,
+    ) -> Self {
+        Self::OneOf(values.into_iter().map(Into::into).collect())
+    }
+
+    /// Creates a new `TextPattern` that matches text against a glob, where
+    /// `*` matches any run of characters, `?` matches any single character,
+    /// and every other character matches itself.
+    ///
+    /// Returns an error if the equivalent regex cannot be compiled, such as
+    /// when it is too large.
+    pub fn glob(glob: impl Into<String>) -> Result<Self> {
+        let glob = glob.into();
+        let mut source = String::from("^(?s:");
+        for ch in glob.chars() {
+            match ch {
+                '*' => source.push_str(".*"),
+                '?' => source.push('.'),
+                _ => {
+                    source.push_str(&regex::escape(ch.encode_utf8(&mut [0; 4])))
+                }
+            }
+        }
+        source.push_str(")$");
+        let regex = regex::Regex::new(&source)
+            .map_err(|_| Error::InvalidRegex(0..glob.len()))?;
+        let pattern = dcbor_pattern::TextPattern::regex(regex);
+        Ok(Self::Glob { glob, pattern })
+    }
+
+    /// Creates a new `TextPattern` that matches text starting with `prefix`.
+    pub fn starts_with(prefix: impl Into<String>) -> Self {
+        Self::StartsWith(prefix.into())
+    }
+
+    /// Creates a new `TextPattern` that matches text ending with `suffix`.
+    pub fn ends_with(suffix: impl Into<String>) -> Self {
+        Self::EndsWith(suffix.into())
+    }
+
+    /// Creates a new `TextPattern` that matches text of exactly `length`
+    /// characters.
+    pub fn length(length: usize) -> Self {
+        Self::Length(Interval::new(length..=length))
+    }
+
+    /// Creates a new `TextPattern` that matches text whose length in
+    /// characters is within `range`.
+    ///
+    /// Returns `Error::EmptyLengthRange` if `range` is empty, such as `..0`
+    /// or `5..=3`.
+    pub fn length_range(range: impl RangeBounds<usize>) -> Result<Self> {
+        let min = match range.start_bound() {
+            Bound::Included(&start) => Some(start),
+            Bound::Excluded(&start) => start.checked_add(1),
+            Bound::Unbounded => Some(0),
+        };
+        let max = match range.end_bound() {
+            Bound::Included(&end) => Some(Some(end)),
+            Bound::Excluded(&end) => end.checked_sub(1).map(Some),
+            Bound::Unbounded => Some(None),
+        };
+        match (min, max) {
+            (Some(min), Some(Some(max))) if min <= max => {
+                Ok(Self::Length(Interval::new(min..=max)))
+            }
+            (Some(min), Some(None)) => Ok(Self::Length(Interval::new(min..))),
+            _ => Err(Error::EmptyLengthRange),
+        }
     }
 
     /// Creates a new `TextPattern` from a dcbor-pattern TextPattern.
     pub fn from_dcbor_pattern(
         dcbor_pattern: dcbor_pattern::TextPattern,
     ) -> Self {
-        Self(dcbor_pattern)
+        Self::Dcbor(dcbor_pattern)
     }
-}
-
-impl PartialEq for TextPattern {
-    fn eq(&self, other: &Self) -> bool { self.0 == other.0 }
-}
-
-impl Eq for TextPattern {}
 
-impl std::hash::Hash for TextPattern {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) { self.0.hash(state); }
+    fn matches_cbor(&self, cbor: &CBOR) -> bool {
+        match self {
+            // Delegate to dcbor-pattern for CBOR matching using paths() method
+            TextPattern::Dcbor(pattern)
+            | TextPattern::Glob { pattern, .. } => {
+                !dcbor_pattern::Matcher::paths(pattern, cbor).is_empty()
+            }
+            TextPattern::OneOf(values) => {
+                cbor.as_text().is_some_and(|text| values.contains(text))
+            }
+            TextPattern::StartsWith(prefix) => {
+                cbor.as_text().is_some_and(|text| text.starts_with(prefix))
+            }
+            TextPattern::EndsWith(suffix) => {
+                cbor.as_text().is_some_and(|text| text.ends_with(suffix))
+            }
+            // Length is counted in Unicode scalar values, not bytes.
+            TextPattern::Length(interval) => cbor
+                .as_text()
+                .is_some_and(|text| interval.contains(text.chars().count())),
+        }
+    }
 }
 
 impl Matcher for TextPattern {
@@ -54,13 +160,9 @@ impl Matcher for TextPattern {
         // Try to extract CBOR from the envelope using the existing as_leaf()
         // method
         if let Some(cbor) = haystack.subject().as_leaf() {
-            // Delegate to dcbor-pattern for CBOR matching using paths() method
-            // TextPattern doesn't support captures, so we only get paths
-            let dcbor_paths = dcbor_pattern::Matcher::paths(&self.0, &cbor);
-
-            // For simple leaf patterns, if dcbor-pattern found matches, return
-            // the envelope
-            if !dcbor_paths.is_empty() {
+            // For simple leaf patterns, if the text matched, return the
+            // envelope. TextPattern doesn't support captures.
+            if self.matches_cbor(&cbor) {
                 let envelope_paths = vec![vec![haystack.clone()]];
                 let envelope_captures = HashMap::new(); // No captures for simple text patterns
                 (envelope_paths, envelope_captures)
@@ -90,7 +192,31 @@ impl Matcher for TextPattern {
 
 impl std::fmt::Display for TextPattern {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+        match self {
+            TextPattern::Dcbor(pattern) => write!(f, "{}", pattern),
+            TextPattern::OneOf(values) => {
+                let values: Vec<_> = values
+                    .iter()
+                    .map(|v| TextPattern::value(v.as_str()).to_string())
+                    .collect();
+                write!(f, "text(one_of: [{}])", values.join(", "))
+            }
+            TextPattern::Glob { glob, .. } => {
+                write!(f, "text_glob({})", TextPattern::value(glob.as_str()))
+            }
+            TextPattern::StartsWith(prefix) => {
+                write!(f, "text(^{})", TextPattern::value(prefix.as_str()))
+            }
+            TextPattern::EndsWith(suffix) => {
+                write!(f, "text({}$)", TextPattern::value(suffix.as_str()))
+            }
+            TextPattern::Length(interval) if interval.is_single() => {
+                write!(f, "text(len: {})", interval.min())
+            }
+            TextPattern::Length(interval) => {
+                write!(f, "text(len: {})", interval)
+            }
+        }
     }
 }
 
@@ -107,6 +233,43 @@ mod tests {
                 .to_string(),
             r#"/^\d+$/"#
         );
+        assert_eq!(
+            TextPattern::one_of(["b", "a"]).to_string(),
+            r#"text(one_of: ["a", "b"])"#
+        );
+        assert_eq!(
+            TextPattern::starts_with("Al").to_string(),
+            r#"text(^"Al")"#
+        );
+        assert_eq!(
+            TextPattern::ends_with("ce").to_string(),
+            r#"text("ce"$)"#
+        );
+        assert_eq!(TextPattern::length(5).to_string(), "text(len: 5)");
+        assert_eq!(
+            TextPattern::length_range(3..=10).unwrap().to_string(),
+            "text(len: {3,10})"
+        );
+        assert_eq!(
+            TextPattern::length_range(4..).unwrap().to_string(),
+            "text(len: {4,})"
+        );
+        assert_eq!(
+            TextPattern::length_range(3..4).unwrap(),
+            TextPattern::length(3)
+        );
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_text_pattern_empty_length_range() {
+        for result in [
+            TextPattern::length_range(..0),
+            TextPattern::length_range(5..=3),
+            TextPattern::length_range(3..3),
+        ] {
+            assert_eq!(result, Err(Error::EmptyLengthRange));
+        }
     }
 
     #[test]
@@ -8,6 +8,16 @@ use crate::pattern::{Pattern, vm::Instr};
 /// envelope.
 pub type Path = Vec<Envelope>;
 
+/// Compares two paths lexicographically by the digests of their elements.
+///
+/// This gives paths a stable, deterministic order that does not depend on the
+/// order in which they were matched.
+pub fn compare_paths(a: &Path, b: &Path) -> std::cmp::Ordering {
+    a.iter()
+        .map(|e| *e.digest().data())
+        .cmp(b.iter().map(|e| *e.digest().data()))
+}
+
 #[doc(hidden)]
 pub trait Matcher: std::fmt::Debug + std::fmt::Display + Clone {
     /// Return all matching paths along with any named captures.
@@ -25,6 +35,18 @@ pub trait Matcher: std::fmt::Debug + std::fmt::Display + Clone {
         !self.paths(haystack).is_empty()
     }
 
+    /// Return only the named captures, discarding the matching paths.
+    fn all_captures(&self, haystack: &Envelope) -> HashMap<String, Vec<Path>> {
+        self.paths_with_captures(haystack).1
+    }
+
+    /// Return the first path captured under `name`, if any.
+    fn first_capture(&self, haystack: &Envelope, name: &str) -> Option<Path> {
+        self.all_captures(haystack)
+            .remove(name)
+            .and_then(|paths| paths.into_iter().next())
+    }
+
     fn compile(
         &self,
         _code: &mut Vec<Instr>,
@@ -43,6 +65,60 @@ pub trait Matcher: std::fmt::Debug + std::fmt::Display + Clone {
 
 /// Helper you can reuse in many impls: push self into `literals` and
 /// emit a single MatchPredicate.
+///
+/// This is exported as `compile_pattern_as_atomic` so that downstream crates
+/// can implement `Matcher` for their own pattern types. `pat` must be a leaf
+/// or structure pattern; meta patterns need their own bytecode.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use bc_envelope::prelude::*;
+/// use bc_envelope_pattern::{
+///     Instr, Matcher, Path, Pattern, compile_pattern_as_atomic,
+/// };
+///
+/// /// Matches the text "Hello" wherever it appears as a subject.
+/// #[derive(Debug, Clone)]
+/// struct Greeting(Pattern);
+///
+/// impl Greeting {
+///     fn new() -> Self { Greeting(Pattern::text("Hello")) }
+/// }
+///
+/// impl std::fmt::Display for Greeting {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         write!(f, "greeting")
+///     }
+/// }
+///
+/// impl Matcher for Greeting {
+///     fn paths_with_captures(
+///         &self,
+///         haystack: &Envelope,
+///     ) -> (Vec<Path>, HashMap<String, Vec<Path>>) {
+///         self.0.paths_with_captures(haystack)
+///     }
+///
+///     fn compile(
+///         &self,
+///         code: &mut Vec<Instr>,
+///         literals: &mut Vec<Pattern>,
+///         captures: &mut Vec<String>,
+///     ) {
+///         compile_pattern_as_atomic(&self.0, code, literals, captures);
+///     }
+/// }
+///
+/// let greeting = Greeting::new();
+/// assert!(greeting.matches(&Envelope::new("Hello")));
+/// assert!(!greeting.matches(&Envelope::new("Goodbye")));
+///
+/// let (mut code, mut literals) = (Vec::new(), Vec::new());
+/// greeting.compile(&mut code, &mut literals, &mut Vec::new());
+/// assert!(matches!(code[..], [Instr::MatchPredicate(0)]));
+/// assert_eq!(literals, vec![Pattern::text("Hello")]);
+/// ```
 pub fn compile_as_atomic(
     pat: &Pattern,
     code: &mut Vec<Instr>,
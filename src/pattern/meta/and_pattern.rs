@@ -20,16 +20,20 @@ impl Matcher for AndPattern {
         &self,
         haystack: &Envelope,
     ) -> (Vec<Path>, HashMap<String, Vec<Path>>) {
-        let paths = if self
-            .patterns()
-            .iter()
-            .all(|pattern| pattern.matches(haystack))
-        {
-            vec![vec![haystack.clone()]]
-        } else {
-            vec![]
-        };
-        (paths, HashMap::new())
+        // As in the compiled form, the last pattern determines the paths.
+        let mut paths = vec![vec![haystack.clone()]];
+        let mut captures: HashMap<String, Vec<Path>> = HashMap::new();
+        for pattern in self.patterns() {
+            let (pattern_paths, caps) = pattern.paths_with_captures(haystack);
+            if pattern_paths.is_empty() {
+                return (vec![], HashMap::new());
+            }
+            paths = pattern_paths;
+            for (name, pths) in caps {
+                captures.entry(name).or_default().extend(pths);
+            }
+        }
+        (paths, captures)
     }
 
     /// Compile into byte-code (AND = all must match).
@@ -39,10 +43,34 @@ impl Matcher for AndPattern {
         lits: &mut Vec<Pattern>,
         captures: &mut Vec<String>,
     ) {
-        // Each pattern must match at this position
-        for pattern in self.patterns() {
-            pattern.compile(code, lits, captures);
+        // Each pattern must match at this position. All but the last are
+        // checked without moving, so a pattern like `assert` does not shift
+        // the position the remaining patterns see. The last pattern
+        // determines the resulting paths.
+        let Some((last, rest)) = self.patterns().split_last() else {
+            return;
+        };
+        for pattern in rest {
+            let idx = lits.len();
+            lits.push(pattern.clone());
+
+            let mut inner_names = Vec::new();
+            pattern.collect_capture_names(&mut inner_names);
+            let mut map = Vec::new();
+            for name in inner_names {
+                let pos = match captures.iter().position(|n| n == &name) {
+                    Some(i) => i,
+                    None => {
+                        captures.push(name.clone());
+                        captures.len() - 1
+                    }
+                };
+                map.push((name, pos));
+            }
+
+            code.push(Instr::Lookahead { pat_idx: idx, capture_map: map });
         }
+        last.compile(code, lits, captures);
     }
 
     fn is_complex(&self) -> bool {
@@ -60,7 +88,7 @@ impl std::fmt::Display for AndPattern {
             "{}",
             self.patterns()
                 .iter()
-                .map(|p| p.to_string())
+                .map(|p| p.operand())
                 .collect::<Vec<_>>()
                 .join(" & ")
         )
@@ -78,4 +106,36 @@ mod tests {
         let and_pattern = AndPattern::new(vec![pattern1, pattern2]);
         assert_eq!(and_pattern.to_string(), ">5 & <10");
     }
+
+    #[test]
+    fn test_and_pattern_merges_captures() {
+        let env = Envelope::new(42);
+        let and_pattern = AndPattern::new(vec![
+            Pattern::capture("a", Pattern::number(42)),
+            Pattern::capture("b", Pattern::number_greater_than(40)),
+        ]);
+        let (paths, caps) = and_pattern.paths_with_captures(&env);
+        assert_eq!(paths, vec![vec![env.clone()]]);
+        assert_eq!(caps["a"], vec![vec![env.clone()]]);
+        assert_eq!(caps["b"], vec![vec![env.clone()]]);
+
+        // The paths are those of the last pattern.
+        let env = Envelope::new("Alice").add_assertion("knows", "Bob");
+        let and_pattern = AndPattern::new(vec![
+            Pattern::capture("node", Pattern::any_node()),
+            Pattern::any_assertion(),
+        ]);
+        let (paths, caps) = and_pattern.paths_with_captures(&env);
+        assert_eq!(paths, Pattern::any_assertion().paths(&env));
+        assert_eq!(caps["node"], vec![vec![env.clone()]]);
+
+        let env = Envelope::new(42);
+        let failing = AndPattern::new(vec![
+            Pattern::capture("a", Pattern::number(42)),
+            Pattern::capture("b", Pattern::number_less_than(40)),
+        ]);
+        let (paths, caps) = failing.paths_with_captures(&env);
+        assert!(paths.is_empty());
+        assert!(caps.is_empty());
+    }
 }
@@ -0,0 +1,63 @@
+use std::collections::{HashMap, HashSet};
+
+use bc_components::DigestProvider;
+use bc_envelope::prelude::*;
+
+use crate::pattern::{Matcher, Path, Pattern, vm::Instr};
+
+/// A pattern that matches like its inner pattern but returns each distinct
+/// path only once.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct DedupPattern(Box<Pattern>);
+
+impl DedupPattern {
+    /// Creates a new `DedupPattern` wrapping the given pattern.
+    pub fn new(pattern: Pattern) -> Self { DedupPattern(Box::new(pattern)) }
+
+    /// Returns the inner pattern.
+    pub fn pattern(&self) -> &Pattern { &self.0 }
+}
+
+impl Matcher for DedupPattern {
+    fn paths_with_captures(
+        &self,
+        haystack: &Envelope,
+    ) -> (Vec<Path>, HashMap<String, Vec<Path>>) {
+        let (paths, captures) = self.0.paths_with_captures(haystack);
+        let mut seen = HashSet::new();
+        let paths = paths
+            .into_iter()
+            .filter(|path| {
+                seen.insert(path.iter().map(|e| e.digest()).collect::<Vec<_>>())
+            })
+            .collect();
+        (paths, captures)
+    }
+
+    fn compile(
+        &self,
+        code: &mut Vec<Instr>,
+        lits: &mut Vec<Pattern>,
+        captures: &mut Vec<String>,
+    ) {
+        self.0.compile(code, lits, captures);
+        code.push(Instr::Dedup);
+    }
+}
+
+impl std::fmt::Display for DedupPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "dedup({})", self.pattern())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dedup_pattern_display() {
+        let pattern = DedupPattern::new(Pattern::text("test"));
+        assert_eq!(pattern.to_string(), r#"dedup("test")"#);
+    }
+}
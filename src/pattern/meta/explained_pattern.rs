@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use bc_envelope::prelude::*;
+
+use crate::pattern::{Matcher, Path, Pattern, vm::Instr};
+
+/// A pattern that matches exactly like its inner pattern but carries a
+/// human-readable explanation of what it is looking for.
+///
+/// The explanation is for debugging only: it never affects matching and is
+/// not part of the pattern's textual form.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct ExplainedPattern {
+    inner: Box<Pattern>,
+    explanation: String,
+}
+
+impl ExplainedPattern {
+    /// Creates a new `ExplainedPattern` wrapping the given pattern.
+    pub fn new(pattern: Pattern, explanation: impl Into<String>) -> Self {
+        ExplainedPattern {
+            inner: Box::new(pattern),
+            explanation: explanation.into(),
+        }
+    }
+
+    /// Returns the inner pattern.
+    pub fn pattern(&self) -> &Pattern { &self.inner }
+
+    /// Returns the explanation attached to the inner pattern.
+    pub fn explanation(&self) -> &str { &self.explanation }
+}
+
+impl Matcher for ExplainedPattern {
+    fn paths_with_captures(
+        &self,
+        haystack: &Envelope,
+    ) -> (Vec<Path>, HashMap<String, Vec<Path>>) {
+        self.inner.paths_with_captures(haystack)
+    }
+
+    fn compile(
+        &self,
+        code: &mut Vec<Instr>,
+        lits: &mut Vec<Pattern>,
+        captures: &mut Vec<String>,
+    ) {
+        self.inner.compile(code, lits, captures);
+    }
+
+    fn is_complex(&self) -> bool { self.inner.is_complex() }
+}
+
+impl std::fmt::Display for ExplainedPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.pattern())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explained_pattern_display() {
+        let pattern =
+            ExplainedPattern::new(Pattern::text("test"), "looking for test");
+        assert_eq!(pattern.to_string(), r#""test""#);
+    }
+}
@@ -3,11 +3,19 @@
 mod and_pattern;
 mod any_pattern;
 mod capture_pattern;
+mod dedup_pattern;
+mod explained_pattern;
 mod not_pattern;
 mod or_pattern;
+mod path_filter_pattern;
+mod priority_or_pattern;
 mod repeat_pattern;
+mod search_count_pattern;
 mod search_pattern;
+mod traverse_back_pattern;
 mod traverse_pattern;
+mod unique_pattern;
+mod while_pattern;
 
 use std::collections::HashMap;
 
@@ -15,11 +23,20 @@ pub(crate) use and_pattern::AndPattern;
 pub(crate) use any_pattern::AnyPattern;
 use bc_envelope::prelude::*;
 pub(crate) use capture_pattern::CapturePattern;
+pub(crate) use dedup_pattern::DedupPattern;
+pub(crate) use explained_pattern::ExplainedPattern;
 pub(crate) use not_pattern::NotPattern;
 pub(crate) use or_pattern::OrPattern;
+pub(crate) use path_filter_pattern::PathFilterPattern;
+pub(crate) use priority_or_pattern::PriorityOrPattern;
 pub(crate) use repeat_pattern::GroupPattern;
+pub(crate) use search_count_pattern::SearchCountPattern;
 pub(crate) use search_pattern::SearchPattern;
+pub(crate) use traverse_back_pattern::TraverseBackPattern;
+pub use traverse_pattern::TraversalOrder;
 pub(crate) use traverse_pattern::TraversePattern;
+pub(crate) use unique_pattern::UniquePattern;
+pub(crate) use while_pattern::WhilePattern;
 
 use crate::{
     Pattern,
@@ -35,16 +52,35 @@ pub enum MetaPattern {
     And(AndPattern),
     /// Matches if any contained pattern matches.
     Or(OrPattern),
+    /// Matches if any contained pattern matches, trying them in priority
+    /// order.
+    PriorityOr(PriorityOrPattern),
     /// Matches if the inner pattern does not match.
     Not(NotPattern),
     /// Searches the entire envelope tree for matches.
     Search(SearchPattern),
+    /// Matches if the number of the inner pattern's matches is within an
+    /// interval.
+    SearchCount(SearchCountPattern),
     /// Matches a traversal order of patterns.
     Traverse(TraversePattern),
+    /// Matches a pattern, then steps back to its parent.
+    TraverseBack(TraverseBackPattern),
     /// Matches with repetition.
     Group(GroupPattern),
     /// Captures a pattern match.
     Capture(CapturePattern),
+    /// Removes duplicate paths from the inner pattern's matches.
+    Dedup(DedupPattern),
+    /// Keeps only the first of the inner pattern's paths ending at each
+    /// envelope.
+    Unique(UniquePattern),
+    /// Keeps only the inner pattern's paths accepted by a predicate.
+    PathFilter(PathFilterPattern),
+    /// Repeats a step while a condition holds.
+    While(WhilePattern),
+    /// Matches like the inner pattern, carrying a debugging explanation.
+    Explained(ExplainedPattern),
 }
 
 impl Matcher for MetaPattern {
@@ -56,19 +92,43 @@ impl Matcher for MetaPattern {
             MetaPattern::Any(pattern) => pattern.paths_with_captures(haystack),
             MetaPattern::And(pattern) => pattern.paths_with_captures(haystack),
             MetaPattern::Or(pattern) => pattern.paths_with_captures(haystack),
+            MetaPattern::PriorityOr(pattern) => {
+                pattern.paths_with_captures(haystack)
+            }
             MetaPattern::Not(pattern) => pattern.paths_with_captures(haystack),
             MetaPattern::Search(pattern) => {
                 pattern.paths_with_captures(haystack)
             }
+            MetaPattern::SearchCount(pattern) => {
+                pattern.paths_with_captures(haystack)
+            }
             MetaPattern::Traverse(pattern) => {
                 pattern.paths_with_captures(haystack)
             }
+            MetaPattern::TraverseBack(pattern) => {
+                pattern.paths_with_captures(haystack)
+            }
             MetaPattern::Group(pattern) => {
                 pattern.paths_with_captures(haystack)
             }
             MetaPattern::Capture(pattern) => {
                 pattern.paths_with_captures(haystack)
             }
+            MetaPattern::Dedup(pattern) => {
+                pattern.paths_with_captures(haystack)
+            }
+            MetaPattern::Unique(pattern) => {
+                pattern.paths_with_captures(haystack)
+            }
+            MetaPattern::PathFilter(pattern) => {
+                pattern.paths_with_captures(haystack)
+            }
+            MetaPattern::While(pattern) => {
+                pattern.paths_with_captures(haystack)
+            }
+            MetaPattern::Explained(pattern) => {
+                pattern.paths_with_captures(haystack)
+            }
         }
     }
 
@@ -82,19 +142,43 @@ impl Matcher for MetaPattern {
             MetaPattern::Any(pattern) => pattern.compile(code, lits, captures),
             MetaPattern::And(pattern) => pattern.compile(code, lits, captures),
             MetaPattern::Or(pattern) => pattern.compile(code, lits, captures),
+            MetaPattern::PriorityOr(pattern) => {
+                pattern.compile(code, lits, captures)
+            }
             MetaPattern::Not(pattern) => pattern.compile(code, lits, captures),
             MetaPattern::Search(pattern) => {
                 pattern.compile(code, lits, captures)
             }
+            MetaPattern::SearchCount(pattern) => {
+                pattern.compile(code, lits, captures)
+            }
             MetaPattern::Traverse(pattern) => {
                 pattern.compile(code, lits, captures)
             }
+            MetaPattern::TraverseBack(pattern) => {
+                pattern.compile(code, lits, captures)
+            }
             MetaPattern::Group(pattern) => {
                 pattern.compile(code, lits, captures)
             }
             MetaPattern::Capture(pattern) => {
                 pattern.compile(code, lits, captures)
             }
+            MetaPattern::Dedup(pattern) => {
+                pattern.compile(code, lits, captures)
+            }
+            MetaPattern::Unique(pattern) => {
+                pattern.compile(code, lits, captures)
+            }
+            MetaPattern::PathFilter(pattern) => {
+                pattern.compile(code, lits, captures)
+            }
+            MetaPattern::While(pattern) => {
+                pattern.compile(code, lits, captures)
+            }
+            MetaPattern::Explained(pattern) => {
+                pattern.compile(code, lits, captures)
+            }
         }
     }
 
@@ -103,11 +187,19 @@ impl Matcher for MetaPattern {
             MetaPattern::Any(pattern) => pattern.is_complex(),
             MetaPattern::And(pattern) => pattern.is_complex(),
             MetaPattern::Or(pattern) => pattern.is_complex(),
+            MetaPattern::PriorityOr(pattern) => pattern.is_complex(),
             MetaPattern::Not(pattern) => pattern.is_complex(),
             MetaPattern::Search(pattern) => pattern.is_complex(),
+            MetaPattern::SearchCount(pattern) => pattern.is_complex(),
             MetaPattern::Traverse(pattern) => pattern.is_complex(),
+            MetaPattern::TraverseBack(pattern) => pattern.is_complex(),
             MetaPattern::Group(pattern) => pattern.is_complex(),
             MetaPattern::Capture(pattern) => pattern.is_complex(),
+            MetaPattern::Dedup(pattern) => pattern.is_complex(),
+            MetaPattern::Unique(pattern) => pattern.is_complex(),
+            MetaPattern::PathFilter(pattern) => pattern.is_complex(),
+            MetaPattern::While(pattern) => pattern.is_complex(),
+            MetaPattern::Explained(pattern) => pattern.is_complex(),
         }
     }
 }
@@ -118,16 +210,142 @@ impl std::fmt::Display for MetaPattern {
             MetaPattern::Any(pattern) => write!(f, "{}", pattern),
             MetaPattern::And(pattern) => write!(f, "{}", pattern),
             MetaPattern::Or(pattern) => write!(f, "{}", pattern),
+            MetaPattern::PriorityOr(pattern) => write!(f, "{}", pattern),
             MetaPattern::Not(pattern) => write!(f, "{}", pattern),
             MetaPattern::Search(pattern) => write!(f, "{}", pattern),
+            MetaPattern::SearchCount(pattern) => write!(f, "{}", pattern),
             MetaPattern::Traverse(pattern) => write!(f, "{}", pattern),
+            MetaPattern::TraverseBack(pattern) => write!(f, "{}", pattern),
             MetaPattern::Group(pattern) => write!(f, "{}", pattern),
             MetaPattern::Capture(pattern) => write!(f, "{}", pattern),
+            MetaPattern::Dedup(pattern) => write!(f, "{}", pattern),
+            MetaPattern::Unique(pattern) => write!(f, "{}", pattern),
+            MetaPattern::PathFilter(pattern) => write!(f, "{}", pattern),
+            MetaPattern::While(pattern) => write!(f, "{}", pattern),
+            MetaPattern::Explained(pattern) => write!(f, "{}", pattern),
         }
     }
 }
 
 impl MetaPattern {
+    /// Returns the patterns nested directly inside this meta pattern.
+    pub(crate) fn child_patterns(&self) -> Vec<&Pattern> {
+        match self {
+            MetaPattern::Any(_) => Vec::new(),
+            MetaPattern::And(p) => p.patterns().iter().collect(),
+            MetaPattern::Or(p) => p.patterns().iter().collect(),
+            MetaPattern::PriorityOr(p) => {
+                p.branches().iter().map(|(_, pattern)| pattern).collect()
+            }
+            MetaPattern::Not(p) => vec![p.pattern()],
+            MetaPattern::Search(p) => vec![p.pattern()],
+            MetaPattern::SearchCount(p) => vec![p.pattern()],
+            MetaPattern::Traverse(p) => {
+                let mut result = vec![p.first()];
+                let mut rest = p.rest();
+                while let Some(traverse) = rest {
+                    result.push(traverse.first());
+                    rest = traverse.rest();
+                }
+                result
+            }
+            MetaPattern::TraverseBack(p) => vec![p.child(), p.parent()],
+            MetaPattern::Group(p) => vec![p.pattern()],
+            MetaPattern::Capture(p) => vec![p.pattern()],
+            MetaPattern::Dedup(p) => vec![p.pattern()],
+            MetaPattern::Unique(p) => vec![p.pattern()],
+            MetaPattern::PathFilter(p) => vec![p.pattern()],
+            MetaPattern::While(p) => vec![p.condition(), p.body()],
+            MetaPattern::Explained(p) => vec![p.pattern()],
+        }
+    }
+
+    /// Rebuilds this meta pattern with each directly nested pattern replaced
+    /// by the result of `f`.
+    pub(crate) fn map_child_patterns(
+        self,
+        f: &mut dyn FnMut(Pattern) -> Pattern,
+    ) -> Self {
+        match self {
+            MetaPattern::Any(p) => MetaPattern::Any(p),
+            MetaPattern::And(p) => MetaPattern::And(AndPattern::new(
+                p.patterns().iter().cloned().map(f).collect(),
+            )),
+            MetaPattern::Or(p) => MetaPattern::Or(OrPattern::new(
+                p.patterns().iter().cloned().map(f).collect(),
+            )),
+            MetaPattern::PriorityOr(p) => {
+                MetaPattern::PriorityOr(PriorityOrPattern::new(
+                    p.branches()
+                        .iter()
+                        .map(|(priority, pattern)| {
+                            (*priority, f(pattern.clone()))
+                        })
+                        .collect(),
+                ))
+            }
+            MetaPattern::Not(p) => {
+                MetaPattern::Not(NotPattern::new(f(p.pattern().clone())))
+            }
+            MetaPattern::Search(p) => {
+                let search = SearchPattern::new(f(p.pattern().clone()));
+                MetaPattern::Search(match p.limit() {
+                    Some(limit) => search.with_limit(limit),
+                    None => search,
+                })
+            }
+            MetaPattern::SearchCount(p) => {
+                MetaPattern::SearchCount(SearchCountPattern::new(
+                    f(p.pattern().clone()),
+                    *p.interval(),
+                ))
+            }
+            MetaPattern::Traverse(p) => MetaPattern::Traverse(
+                TraversePattern::new(p.patterns().into_iter().map(f).collect())
+                    .with_order(p.order()),
+            ),
+            MetaPattern::TraverseBack(p) => {
+                let child = f(p.child().clone());
+                MetaPattern::TraverseBack(TraverseBackPattern::new(
+                    child,
+                    f(p.parent().clone()),
+                ))
+            }
+            MetaPattern::Group(p) => MetaPattern::Group(GroupPattern::repeat(
+                f(p.pattern().clone()),
+                *p.quantifier(),
+            )),
+            MetaPattern::Capture(p) => MetaPattern::Capture(
+                CapturePattern::new(p.name(), f(p.pattern().clone())),
+            ),
+            MetaPattern::Dedup(p) => {
+                MetaPattern::Dedup(DedupPattern::new(f(p.pattern().clone())))
+            }
+            MetaPattern::Unique(p) => {
+                MetaPattern::Unique(UniquePattern::new(f(p.pattern().clone())))
+            }
+            MetaPattern::PathFilter(p) => {
+                MetaPattern::PathFilter(PathFilterPattern::new(
+                    f(p.pattern().clone()),
+                    p.predicate().clone(),
+                ))
+            }
+            MetaPattern::While(p) => {
+                let condition = f(p.condition().clone());
+                MetaPattern::While(WhilePattern::new(
+                    condition,
+                    f(p.body().clone()),
+                ))
+            }
+            MetaPattern::Explained(p) => {
+                MetaPattern::Explained(ExplainedPattern::new(
+                    f(p.pattern().clone()),
+                    p.explanation(),
+                ))
+            }
+        }
+    }
+
     pub(crate) fn collect_capture_names(&self, out: &mut Vec<String>) {
         match self {
             MetaPattern::Any(_) => {}
@@ -141,14 +359,38 @@ impl MetaPattern {
                     pat.collect_capture_names(out);
                 }
             }
+            MetaPattern::PriorityOr(p) => {
+                for (_, pat) in p.branches() {
+                    pat.collect_capture_names(out);
+                }
+            }
             MetaPattern::Not(p) => p.pattern().collect_capture_names(out),
             MetaPattern::Search(p) => p.pattern().collect_capture_names(out),
+            MetaPattern::SearchCount(p) => {
+                p.pattern().collect_capture_names(out)
+            }
             MetaPattern::Traverse(p) => {
                 for pat in p.patterns() {
                     pat.collect_capture_names(out);
                 }
             }
+            MetaPattern::TraverseBack(p) => {
+                p.child().collect_capture_names(out);
+                p.parent().collect_capture_names(out);
+            }
             MetaPattern::Group(p) => p.pattern().collect_capture_names(out),
+            MetaPattern::Dedup(p) => p.pattern().collect_capture_names(out),
+            MetaPattern::Unique(p) => p.pattern().collect_capture_names(out),
+            MetaPattern::Explained(p) => {
+                p.pattern().collect_capture_names(out)
+            }
+            MetaPattern::PathFilter(p) => {
+                p.pattern().collect_capture_names(out)
+            }
+            MetaPattern::While(p) => {
+                p.condition().collect_capture_names(out);
+                p.body().collect_capture_names(out);
+            }
             MetaPattern::Capture(p) => {
                 if !out.contains(&p.name().to_string()) {
                     out.push(p.name().to_string());
@@ -47,7 +47,7 @@ impl Matcher for NotPattern {
 
 impl std::fmt::Display for NotPattern {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "!{}", self.pattern())
+        write!(f, "!{}", self.pattern().operand())
     }
 }
 
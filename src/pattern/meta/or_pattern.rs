@@ -2,7 +2,11 @@ use std::collections::HashMap;
 
 use bc_envelope::prelude::*;
 
-use crate::pattern::{Matcher, Path, Pattern, vm::Instr};
+use crate::pattern::{
+    Matcher, Path, Pattern,
+    structure::{PredicatePattern, StructurePattern, SubjectPattern},
+    vm::Instr,
+};
 
 /// A pattern that matches if any contained pattern matches.
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -13,6 +17,22 @@ impl OrPattern {
     pub fn new(patterns: Vec<Pattern>) -> Self { OrPattern(patterns) }
 
     pub fn patterns(&self) -> &[Pattern] { &self.0 }
+
+    /// Returns `p` if this is `pred(p) | subj(p)`, as built by
+    /// [`Pattern::predicate_or_subject`].
+    pub(crate) fn predicate_or_subject(&self) -> Option<&Pattern> {
+        match self.patterns() {
+            [
+                Pattern::Structure(StructurePattern::Predicate(
+                    PredicatePattern::Pattern(predicate),
+                )),
+                Pattern::Structure(StructurePattern::Subject(
+                    SubjectPattern::Pattern(subject),
+                )),
+            ] if predicate == subject => Some(predicate),
+            _ => None,
+        }
+    }
 }
 
 impl Matcher for OrPattern {
@@ -43,11 +63,13 @@ impl Matcher for OrPattern {
             return;
         }
 
-        // For N patterns: Split(p1, Split(p2, ... Split(pN-1, pN)))
+        // For N patterns: Split(p1, Split(p2, ... Split(pN-1, pN))). The
+        // splits are laid out first, each falling through to the next.
         let mut splits = Vec::new();
+        let split_count = self.patterns().len() - 1;
 
         // Generate splits for all but the last pattern
-        for _ in 0..self.patterns().len() - 1 {
+        for _ in 0..split_count {
             splits.push(code.len());
             code.push(Instr::Split { a: 0, b: 0 }); // Placeholder
         }
@@ -64,10 +86,13 @@ impl Matcher for OrPattern {
             code.push(Instr::Jump(0)); // Placeholder
 
             // If there's a next pattern, update the split to point here
-            if i < self.patterns().len() - 1 {
-                let next_pattern = code.len();
-                code[splits[i]] =
-                    Instr::Split { a: pattern_start, b: next_pattern };
+            if i < split_count {
+                let next = if i + 1 < split_count {
+                    splits[i + 1]
+                } else {
+                    code.len()
+                };
+                code[splits[i]] = Instr::Split { a: pattern_start, b: next };
             }
 
             // Will patch this jump once we know where "past all" is
@@ -76,7 +101,7 @@ impl Matcher for OrPattern {
 
         // Now patch all the jumps to point past all the patterns
         let past_all = code.len();
-        for &jump in &splits[self.patterns().len() - 1..] {
+        for &jump in &splits[split_count..] {
             code[jump] = Instr::Jump(past_all);
         }
     }
@@ -91,6 +116,9 @@ impl Matcher for OrPattern {
 
 impl std::fmt::Display for OrPattern {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(pattern) = self.predicate_or_subject() {
+            return write!(f, "pred_or_subj({})", pattern);
+        }
         write!(
             f,
             "{}",
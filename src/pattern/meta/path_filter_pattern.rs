@@ -0,0 +1,106 @@
+use std::{collections::HashMap, sync::Arc};
+
+use bc_envelope::prelude::*;
+
+use crate::pattern::{Matcher, Path, Pattern, meta::MetaPattern, vm::Instr};
+
+/// A predicate applied to each path matched by a `PathFilterPattern`.
+pub type PathPredicate = Arc<dyn Fn(&Path) -> bool + Send + Sync>;
+
+/// A pattern that matches like its inner pattern but keeps only the paths
+/// accepted by a caller-supplied predicate.
+///
+/// The predicate cannot be written in pattern syntax, so filter patterns
+/// display as `filter(<inner>)` but cannot be parsed. Two filter patterns are
+/// equal only if they share the same predicate instance.
+///
+/// Compiled programs are cached by pattern equality, so identical closures
+/// wrapped in separate `Arc`s each get their own cache entry. Clone one
+/// `PathPredicate` rather than recreating it for every match.
+#[derive(Clone)]
+pub struct PathFilterPattern {
+    inner: Box<Pattern>,
+    predicate: PathPredicate,
+}
+
+impl PathFilterPattern {
+    /// Creates a new `PathFilterPattern` keeping the paths of `inner` for
+    /// which `predicate` returns true.
+    pub fn new(inner: Pattern, predicate: PathPredicate) -> Self {
+        PathFilterPattern { inner: Box::new(inner), predicate }
+    }
+
+    /// Returns the inner pattern.
+    pub fn pattern(&self) -> &Pattern { &self.inner }
+
+    /// Returns the path predicate.
+    pub fn predicate(&self) -> &PathPredicate { &self.predicate }
+
+    /// Returns true if `path` is accepted by the predicate.
+    pub fn accepts(&self, path: &Path) -> bool { (self.predicate)(path) }
+}
+
+impl std::fmt::Debug for PathFilterPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PathFilterPattern")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for PathFilterPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+            && Arc::ptr_eq(&self.predicate, &other.predicate)
+    }
+}
+
+impl Eq for PathFilterPattern {}
+
+impl std::hash::Hash for PathFilterPattern {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.inner.hash(state);
+        (Arc::as_ptr(&self.predicate) as *const () as usize).hash(state);
+    }
+}
+
+impl Matcher for PathFilterPattern {
+    fn paths_with_captures(
+        &self,
+        haystack: &Envelope,
+    ) -> (Vec<Path>, HashMap<String, Vec<Path>>) {
+        let (paths, captures) = self.inner.paths_with_captures(haystack);
+        let paths = paths.into_iter().filter(|p| self.accepts(p)).collect();
+        (paths, captures)
+    }
+
+    fn compile(
+        &self,
+        code: &mut Vec<Instr>,
+        lits: &mut Vec<Pattern>,
+        captures: &mut Vec<String>,
+    ) {
+        self.inner.compile(code, lits, captures);
+        let idx = lits.len();
+        lits.push(Pattern::Meta(MetaPattern::PathFilter(self.clone())));
+        code.push(Instr::FilterPath(idx));
+    }
+}
+
+impl std::fmt::Display for PathFilterPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "filter({})", self.pattern())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_filter_pattern_display() {
+        let pattern =
+            PathFilterPattern::new(Pattern::text("test"), Arc::new(|_| true));
+        assert_eq!(pattern.to_string(), r#"filter("test")"#);
+    }
+}
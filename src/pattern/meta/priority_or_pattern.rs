@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use bc_envelope::prelude::*;
+
+use super::OrPattern;
+use crate::pattern::{Matcher, Path, Pattern, vm::Instr};
+
+/// A pattern that matches if any contained pattern matches, trying the
+/// alternatives in descending order of priority.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct PriorityOrPattern(Vec<(u32, Pattern)>);
+
+impl PriorityOrPattern {
+    /// Creates a new `PriorityOrPattern` with the given prioritized
+    /// patterns. Branches are ordered by descending priority; branches with
+    /// equal priority keep their relative order.
+    pub fn new(mut branches: Vec<(u32, Pattern)>) -> Self {
+        branches.sort_by(|(a, _), (b, _)| b.cmp(a));
+        PriorityOrPattern(branches)
+    }
+
+    /// The branches, in the order they are tried.
+    pub fn branches(&self) -> &[(u32, Pattern)] { &self.0 }
+
+    /// An `or` that the VM runs in priority order. The VM runs the last
+    /// alternative of an `or` first, so the branches are reversed.
+    fn as_or(&self) -> OrPattern {
+        OrPattern::new(self.0.iter().rev().map(|(_, p)| p.clone()).collect())
+    }
+}
+
+impl Matcher for PriorityOrPattern {
+    fn paths_with_captures(
+        &self,
+        haystack: &Envelope,
+    ) -> (Vec<Path>, HashMap<String, Vec<Path>>) {
+        self.as_or().paths_with_captures(haystack)
+    }
+
+    /// Compiles as an `or` that explores the highest-priority branch first.
+    fn compile(
+        &self,
+        code: &mut Vec<Instr>,
+        lits: &mut Vec<Pattern>,
+        captures: &mut Vec<String>,
+    ) {
+        self.as_or().compile(code, lits, captures)
+    }
+
+    fn is_complex(&self) -> bool { self.as_or().is_complex() }
+}
+
+impl std::fmt::Display for PriorityOrPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.branches()
+                .iter()
+                .map(|(priority, p)| format!("prio({}, {})", priority, p))
+                .collect::<Vec<_>>()
+                .join(" | ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority_or_pattern_display() {
+        let pattern = PriorityOrPattern::new(vec![
+            (1, Pattern::any_number()),
+            (10, Pattern::any_text()),
+        ]);
+        assert_eq!(pattern.to_string(), "prio(10, text) | prio(1, number)");
+    }
+}
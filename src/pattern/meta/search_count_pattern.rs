@@ -0,0 +1,92 @@
+use std::{collections::HashMap, ops::RangeBounds};
+
+use bc_envelope::prelude::*;
+
+use crate::{
+    Interval, Pattern,
+    pattern::{Matcher, Path, meta::MetaPattern, vm::Instr},
+};
+
+/// A pattern that matches the current envelope when the number of paths its
+/// inner pattern produces from that envelope is within an interval.
+///
+/// The inner pattern is usually a `search`, so that the count covers the
+/// whole envelope tree. Captures made by the inner pattern are discarded.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct SearchCountPattern {
+    inner: Box<Pattern>,
+    interval: Interval,
+}
+
+impl SearchCountPattern {
+    /// Creates a new `SearchCountPattern` that matches when the number of
+    /// paths produced by `pattern` is within `interval`.
+    pub fn new(pattern: Pattern, interval: impl RangeBounds<usize>) -> Self {
+        SearchCountPattern {
+            inner: Box::new(pattern),
+            interval: Interval::new(interval),
+        }
+    }
+
+    /// Returns the inner pattern.
+    pub fn pattern(&self) -> &Pattern { &self.inner }
+
+    /// Returns the interval the number of matches must fall within.
+    pub fn interval(&self) -> &Interval { &self.interval }
+
+    /// Returns true if the number of paths the inner pattern produces from
+    /// `haystack` is within the interval.
+    pub fn accepts(&self, haystack: &Envelope) -> bool {
+        self.interval.contains(self.inner.paths(haystack).len())
+    }
+}
+
+impl Matcher for SearchCountPattern {
+    fn paths_with_captures(
+        &self,
+        haystack: &Envelope,
+    ) -> (Vec<Path>, HashMap<String, Vec<Path>>) {
+        let paths = if self.accepts(haystack) {
+            vec![vec![haystack.clone()]]
+        } else {
+            vec![]
+        };
+        (paths, HashMap::new())
+    }
+
+    fn compile(
+        &self,
+        code: &mut Vec<Instr>,
+        lits: &mut Vec<Pattern>,
+        _captures: &mut Vec<String>,
+    ) {
+        let idx = lits.len();
+        lits.push(Pattern::Meta(MetaPattern::SearchCount(self.clone())));
+        code.push(Instr::CountMatches(idx));
+    }
+}
+
+impl std::fmt::Display for SearchCountPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.interval.is_single() {
+            write!(f, "count({}, {})", self.interval.min(), self.pattern())
+        } else {
+            write!(f, "count({}, {})", self.interval, self.pattern())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_count_pattern_display() {
+        let pattern =
+            SearchCountPattern::new(Pattern::search(Pattern::text("a")), 2..=2);
+        assert_eq!(pattern.to_string(), r#"count(2, search("a"))"#);
+
+        let pattern = SearchCountPattern::new(Pattern::any(), 1..);
+        assert_eq!(pattern.to_string(), "count({1,}, *)");
+    }
+}
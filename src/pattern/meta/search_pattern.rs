@@ -6,12 +6,26 @@ use bc_envelope::prelude::*;
 use crate::pattern::{Matcher, Path, Pattern, vm::Instr};
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
-pub struct SearchPattern(Box<Pattern>);
+pub struct SearchPattern {
+    pattern: Box<Pattern>,
+    limit: Option<usize>,
+}
 
 impl SearchPattern {
-    pub fn new(pattern: Pattern) -> Self { SearchPattern(Box::new(pattern)) }
+    pub fn new(pattern: Pattern) -> Self {
+        SearchPattern { pattern: Box::new(pattern), limit: None }
+    }
+
+    /// Returns this search, stopping once `limit` paths have been found.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
 
-    pub fn pattern(&self) -> &Pattern { &self.0 }
+    pub fn pattern(&self) -> &Pattern { &self.pattern }
+
+    /// Returns the maximum number of paths this search returns, if any.
+    pub fn limit(&self) -> Option<usize> { self.limit }
 }
 
 impl Matcher for SearchPattern {
@@ -33,7 +47,7 @@ impl Matcher for SearchPattern {
                 new_path.push(current_envelope.clone());
 
                 // Test the pattern against this node
-                let pattern_paths = self.0.paths(current_envelope);
+                let pattern_paths = self.pattern.paths(current_envelope);
 
                 // If the pattern matches, emit the full paths
                 for pattern_path in pattern_paths {
@@ -62,7 +76,11 @@ impl Matcher for SearchPattern {
 
             let mut seen = std::collections::HashSet::new();
             let mut unique = Vec::new();
+            let limit = self.limit.unwrap_or(usize::MAX);
             for path in result_paths.into_inner() {
+                if unique.len() >= limit {
+                    break;
+                }
                 let digest_path: Vec<_> =
                     path.iter().map(|e| e.digest()).collect();
                 if seen.insert(digest_path) {
@@ -82,10 +100,10 @@ impl Matcher for SearchPattern {
         captures: &mut Vec<String>,
     ) {
         let idx = lits.len();
-        lits.push((*self.0).clone());
+        lits.push((*self.pattern).clone());
 
         let mut inner_names = Vec::new();
-        self.0.collect_capture_names(&mut inner_names);
+        self.pattern.collect_capture_names(&mut inner_names);
         let mut map = Vec::new();
         for name in inner_names {
             let pos = if let Some(i) = captures.iter().position(|n| n == &name)
@@ -99,13 +117,22 @@ impl Matcher for SearchPattern {
             map.push((name, pos));
         }
 
-        code.push(Instr::Search { pat_idx: idx, capture_map: map });
+        code.push(Instr::Search {
+            pat_idx: idx,
+            capture_map: map,
+            limit: self.limit,
+        });
     }
 }
 
 impl std::fmt::Display for SearchPattern {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "search({})", self.pattern())
+        match self.limit {
+            Some(limit) => {
+                write!(f, "search({}, limit: {})", self.pattern(), limit)
+            }
+            None => write!(f, "search({})", self.pattern()),
+        }
     }
 }
 
@@ -117,5 +144,7 @@ mod tests {
     fn test_search_pattern_display() {
         let pattern = SearchPattern::new(Pattern::text("test"));
         assert_eq!(pattern.to_string(), r#"search("test")"#);
+        let pattern = pattern.with_limit(5);
+        assert_eq!(pattern.to_string(), r#"search("test", limit: 5)"#);
     }
 }
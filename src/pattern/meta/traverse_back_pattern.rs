@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use bc_envelope::prelude::*;
+
+use crate::pattern::{Matcher, Path, Pattern, vm::Instr};
+
+/// A pattern that matches `child`, then steps back from the end of each
+/// matched path to the envelope containing it and matches `parent` there.
+///
+/// Envelopes do not link to their parents, so the parent is taken from the
+/// matched path itself. A match whose path has no element before its tip has
+/// no parent and is discarded.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct TraverseBackPattern {
+    child: Box<Pattern>,
+    parent: Box<Pattern>,
+}
+
+impl TraverseBackPattern {
+    /// Creates a new `TraverseBackPattern` from `child` back to `parent`.
+    pub fn new(child: Pattern, parent: Pattern) -> Self {
+        TraverseBackPattern { child: Box::new(child), parent: Box::new(parent) }
+    }
+
+    /// Returns the pattern matched before stepping back.
+    pub fn child(&self) -> &Pattern { &self.child }
+
+    /// Returns the pattern matched against the parent.
+    pub fn parent(&self) -> &Pattern { &self.parent }
+}
+
+impl Matcher for TraverseBackPattern {
+    fn paths_with_captures(
+        &self,
+        haystack: &Envelope,
+    ) -> (Vec<Path>, HashMap<String, Vec<Path>>) {
+        let (child_paths, child_captures) =
+            self.child.paths_with_captures(haystack);
+        let mut result = Vec::new();
+        let mut captures: HashMap<String, Vec<Path>> = HashMap::new();
+        for mut path in child_paths {
+            if path.len() < 2 {
+                continue;
+            }
+            path.pop();
+            let parent_env = path.last().cloned().unwrap();
+            let (tail_paths, tail_captures) =
+                self.parent.paths_with_captures(&parent_env);
+            if tail_paths.is_empty() {
+                continue;
+            }
+            for (name, pths) in tail_captures {
+                captures.entry(name).or_default().extend(pths);
+            }
+            for tail_path in tail_paths {
+                let mut combined = path.clone();
+                combined.extend(tail_path.into_iter().skip(1));
+                result.push(combined);
+            }
+        }
+        if result.is_empty() {
+            return (result, HashMap::new());
+        }
+        for (name, pths) in child_captures {
+            captures.entry(name).or_default().extend(pths);
+        }
+        (result, captures)
+    }
+
+    fn compile(
+        &self,
+        code: &mut Vec<Instr>,
+        lits: &mut Vec<Pattern>,
+        captures: &mut Vec<String>,
+    ) {
+        self.child.compile(code, lits, captures);
+        code.push(Instr::NavigateParent);
+        code.push(Instr::ExtendTraversal);
+        self.parent.compile(code, lits, captures);
+        code.push(Instr::CombineTraversal);
+    }
+
+    fn is_complex(&self) -> bool { true }
+}
+
+impl std::fmt::Display for TraverseBackPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} <- {}", self.child.operand(), self.parent.operand())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traverse_back_pattern_display() {
+        let pattern =
+            TraverseBackPattern::new(Pattern::text("Bob"), Pattern::any_node());
+        assert_eq!(pattern.to_string(), r#""Bob" <- node"#);
+    }
+
+    #[test]
+    fn test_traverse_back_pattern_captures() {
+        let envelope = Envelope::new("Alice").add_assertion("age", 30);
+        let pattern = TraverseBackPattern::new(
+            Pattern::search(Pattern::capture("x", Pattern::any_number())),
+            Pattern::capture("y", Pattern::any()),
+        );
+        let (paths, captures) = pattern.paths_with_captures(&envelope);
+        assert_eq!(paths.len(), 1);
+        assert_eq!(captures["x"], vec![vec![Envelope::new(30)]]);
+        assert_eq!(captures["y"].len(), 1);
+    }
+}
@@ -4,10 +4,22 @@ use bc_envelope::prelude::*;
 
 use crate::pattern::{Matcher, Path, Pattern, vm::Instr};
 
+/// The order in which a traversal visits the children it descends into.
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub enum TraversalOrder {
+    /// Follows each child to the end before moving on to its next sibling.
+    #[default]
+    DepthFirst,
+    /// Visits every child at one depth before any child below it, so
+    /// shallower results come before deeper ones.
+    BreadthFirst,
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct TraversePattern {
     first: Box<Pattern>,
     rest: Option<Box<TraversePattern>>,
+    order: TraversalOrder,
 }
 
 impl TraversePattern {
@@ -24,7 +36,28 @@ impl TraversePattern {
         } else {
             Some(Box::new(TraversePattern::new(rest_patterns)))
         };
-        TraversePattern { first: Box::new(first_pat), rest }
+        TraversePattern {
+            first: Box::new(first_pat),
+            rest,
+            order: TraversalOrder::DepthFirst,
+        }
+    }
+
+    /// Returns this traversal with the given child visiting order.
+    pub fn with_order(mut self, order: TraversalOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Returns the order in which this traversal visits children.
+    pub fn order(&self) -> TraversalOrder { self.order }
+
+    /// Returns the first pattern of the traversal.
+    pub(crate) fn first(&self) -> &Pattern { &self.first }
+
+    /// Returns the remainder of the traversal, if any.
+    pub(crate) fn rest(&self) -> Option<&TraversePattern> {
+        self.rest.as_deref()
     }
 
     pub fn patterns(&self) -> Vec<Pattern> {
@@ -41,7 +74,7 @@ impl Matcher for TraversePattern {
         &self,
         haystack: &Envelope,
     ) -> (Vec<Path>, HashMap<String, Vec<Path>>) {
-        let paths = {
+        let mut paths = {
             // Match head first
             let head_paths = self.first.paths(haystack);
             // If there's no further traversal, return head paths
@@ -62,6 +95,9 @@ impl Matcher for TraversePattern {
                 head_paths
             }
         };
+        if self.order == TraversalOrder::BreadthFirst {
+            paths.sort_by_key(|path| path.len());
+        }
         (paths, HashMap::new())
     }
 
@@ -72,6 +108,8 @@ impl Matcher for TraversePattern {
         lits: &mut Vec<Pattern>,
         captures: &mut Vec<String>,
     ) {
+        let start = code.len();
+
         // Compile the first pattern
         self.first.compile(code, lits, captures);
 
@@ -83,6 +121,17 @@ impl Matcher for TraversePattern {
             // Combine the paths correctly
             code.push(Instr::CombineTraversal);
         }
+
+        // Queue the children of every step instead of stacking them. Steps
+        // that run as separate sub-matches, such as repeats and searches,
+        // keep their own order.
+        if self.order == TraversalOrder::BreadthFirst {
+            for instr in &mut code[start..] {
+                if let Instr::PushAxis(axis) = *instr {
+                    *instr = Instr::PushAxisBFS(axis);
+                }
+            }
+        }
     }
 
     fn is_complex(&self) -> bool {
@@ -94,15 +143,20 @@ impl Matcher for TraversePattern {
 
 impl std::fmt::Display for TraversePattern {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            self.patterns()
+        let steps = match self.rest {
+            // A lone step stands on its own.
+            None => self.first.to_string(),
+            Some(_) => self
+                .patterns()
                 .iter()
-                .map(|p| p.to_string())
+                .map(|p| p.operand())
                 .collect::<Vec<_>>()
-                .join(" -> ")
-        )
+                .join(" -> "),
+        };
+        match self.order {
+            TraversalOrder::DepthFirst => write!(f, "{}", steps),
+            TraversalOrder::BreadthFirst => write!(f, "bfs({})", steps),
+        }
     }
 }
 
@@ -116,5 +170,8 @@ mod tests {
         let pattern2 = Pattern::wrapped();
         let traversal_pattern = TraversePattern::new(vec![pattern1, pattern2]);
         assert_eq!(traversal_pattern.to_string(), "wrapped -> wrapped");
+        let traversal_pattern =
+            traversal_pattern.with_order(TraversalOrder::BreadthFirst);
+        assert_eq!(traversal_pattern.to_string(), "bfs(wrapped -> wrapped)");
     }
 }
@@ -0,0 +1,66 @@
+use std::collections::{HashMap, HashSet};
+
+use bc_components::DigestProvider;
+use bc_envelope::prelude::*;
+
+use crate::pattern::{Matcher, Path, Pattern, vm::Instr};
+
+/// A pattern that matches like its inner pattern but returns only the first
+/// path ending at each distinct envelope.
+///
+/// Unlike [`DedupPattern`](super::DedupPattern), paths that reach the same
+/// envelope by different routes are duplicates.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct UniquePattern(Box<Pattern>);
+
+impl UniquePattern {
+    /// Creates a new `UniquePattern` wrapping the given pattern.
+    pub fn new(pattern: Pattern) -> Self { UniquePattern(Box::new(pattern)) }
+
+    /// Returns the inner pattern.
+    pub fn pattern(&self) -> &Pattern { &self.0 }
+}
+
+impl Matcher for UniquePattern {
+    fn paths_with_captures(
+        &self,
+        haystack: &Envelope,
+    ) -> (Vec<Path>, HashMap<String, Vec<Path>>) {
+        let (paths, captures) = self.0.paths_with_captures(haystack);
+        let mut seen = HashSet::new();
+        let paths = paths
+            .into_iter()
+            .filter(|path| {
+                path.last().is_some_and(|tip| seen.insert(tip.digest()))
+            })
+            .collect();
+        (paths, captures)
+    }
+
+    fn compile(
+        &self,
+        code: &mut Vec<Instr>,
+        lits: &mut Vec<Pattern>,
+        captures: &mut Vec<String>,
+    ) {
+        self.0.compile(code, lits, captures);
+        code.push(Instr::DedupTip);
+    }
+}
+
+impl std::fmt::Display for UniquePattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unique({})", self.pattern())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unique_pattern_display() {
+        let pattern = UniquePattern::new(Pattern::text("test"));
+        assert_eq!(pattern.to_string(), r#"unique("test")"#);
+    }
+}
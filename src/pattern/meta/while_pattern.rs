@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use bc_envelope::prelude::*;
+
+use crate::{
+    Reluctance,
+    pattern::{Matcher, Path, Pattern, vm::Instr},
+};
+
+/// A pattern that repeatedly takes a `body` step for as long as the envelope
+/// the step starts from matches `condition`.
+///
+/// Steps are taken greedily, like `(condition & body)*`: the match is the
+/// longest chain of steps, which may be empty.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct WhilePattern {
+    condition: Box<Pattern>,
+    body: Box<Pattern>,
+}
+
+impl WhilePattern {
+    /// Creates a new `WhilePattern` taking `body` steps while `condition`
+    /// holds.
+    pub fn new(condition: Pattern, body: Pattern) -> Self {
+        WhilePattern { condition: Box::new(condition), body: Box::new(body) }
+    }
+
+    /// Returns the pattern each step's starting envelope must match.
+    pub fn condition(&self) -> &Pattern { &self.condition }
+
+    /// Returns the pattern matched for each step.
+    pub fn body(&self) -> &Pattern { &self.body }
+
+    /// The equivalent repetition. `and` checks every pattern but the last
+    /// without moving, so `condition` tests where the step starts and `body`
+    /// determines where it ends.
+    fn as_repeat(&self) -> Pattern {
+        Pattern::repeat(
+            Pattern::and(vec![
+                self.condition().clone(),
+                self.body().clone(),
+            ]),
+            ..,
+            Reluctance::Greedy,
+        )
+    }
+}
+
+impl Matcher for WhilePattern {
+    fn paths_with_captures(
+        &self,
+        haystack: &Envelope,
+    ) -> (Vec<Path>, HashMap<String, Vec<Path>>) {
+        self.as_repeat().paths_with_captures(haystack)
+    }
+
+    fn compile(
+        &self,
+        code: &mut Vec<Instr>,
+        lits: &mut Vec<Pattern>,
+        captures: &mut Vec<String>,
+    ) {
+        self.as_repeat().compile(code, lits, captures)
+    }
+
+    fn is_complex(&self) -> bool { true }
+}
+
+impl std::fmt::Display for WhilePattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "while({}, {})", self.condition, self.body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_while_pattern_display() {
+        let pattern = WhilePattern::new(Pattern::wrapped(), Pattern::unwrap());
+        assert_eq!(pattern.to_string(), "while(wrapped, unwrap)");
+    }
+}
@@ -1,5 +1,15 @@
 // Pattern module - provides pattern matching functionality for envelopes
+mod analysis;
+#[cfg(feature = "async")]
+mod async_paths;
+mod cbor;
+#[cfg(feature = "domain")]
+mod domain;
 mod matcher;
+#[cfg(feature = "precompile")]
+mod precompile;
+mod summary;
+mod traversal_stats;
 mod vm;
 
 // Subdirectory modules
@@ -19,27 +29,33 @@ use std::{
 
 use bc_envelope::prelude::*;
 use known_values::KnownValue;
-pub use matcher::{Matcher, Path, compile_as_atomic};
+pub use matcher::{Matcher, Path, compare_paths, compile_as_atomic};
+pub use meta::TraversalOrder;
+pub use traversal_stats::TraversalStats;
+pub use vm::{Axis, Instr, Program};
 
 use self::{
     leaf::{
         ArrayPattern, BoolPattern, ByteStringPattern, DatePattern,
-        KnownValuePattern, LeafPattern, MapPattern, NullPattern, NumberPattern,
-        TextPattern,
+        IntegerPattern, KnownValuePattern, LeafPattern, MapPattern,
+        NullPattern, NumberPattern, TextPattern, registered_known_value,
     },
     meta::{
-        AndPattern, AnyPattern, CapturePattern, GroupPattern, MetaPattern,
-        NotPattern, OrPattern, SearchPattern, TraversePattern,
+        AndPattern, AnyPattern, CapturePattern, DedupPattern,
+        ExplainedPattern, GroupPattern, MetaPattern, NotPattern, OrPattern,
+        PathFilterPattern, PriorityOrPattern, SearchCountPattern,
+        SearchPattern, TraverseBackPattern, TraversePattern, UniquePattern,
+        WhilePattern,
     },
     structure::{
         AssertionsPattern, DigestPattern, LeafStructurePattern, NodePattern,
         ObjectPattern, ObscuredPattern, PredicatePattern, StructurePattern,
-        SubjectPattern, WrappedPattern,
+        SubjectPattern, TaggedContentPattern, WrappedPattern,
     },
 };
 use crate::{
-    DCBORPattern, Quantifier, Reluctance,
-    pattern::{leaf::CBORPattern, vm::Instr},
+    DCBORPattern, Error, Quantifier, Reluctance, Result,
+    pattern::leaf::CBORPattern,
 };
 
 /// The main pattern type used for matching envelopes.
@@ -55,12 +71,60 @@ pub enum Pattern {
     Meta(MetaPattern),
 }
 
+/// The top-level category of a [`Pattern`], as returned by
+/// [`Pattern::kind`].
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum PatternKind {
+    /// A [`Pattern::Leaf`] pattern.
+    Leaf,
+    /// A [`Pattern::Structure`] pattern.
+    Structure,
+    /// A [`Pattern::Meta`] pattern.
+    Meta,
+}
+
+impl Pattern {
+    /// Returns the top-level category of this pattern. Only the outermost
+    /// pattern is considered, so `search(text)` is a meta pattern.
+    pub fn kind(&self) -> PatternKind {
+        match self {
+            Pattern::Leaf(_) => PatternKind::Leaf,
+            Pattern::Structure(_) => PatternKind::Structure,
+            Pattern::Meta(_) => PatternKind::Meta,
+        }
+    }
+
+    /// Returns `true` if this pattern is a leaf pattern at the top level,
+    /// not wrapped in any meta pattern.
+    pub fn is_pure_leaf(&self) -> bool { self.kind() == PatternKind::Leaf }
+
+    /// Returns `true` if this pattern is a structure pattern at the top
+    /// level, not wrapped in any meta pattern.
+    pub fn is_pure_structure(&self) -> bool {
+        self.kind() == PatternKind::Structure
+    }
+
+    /// Returns `true` if this pattern is a meta pattern at the top level.
+    pub fn is_meta(&self) -> bool { self.kind() == PatternKind::Meta }
+}
+
+thread_local! {
+    static LAST_FAILURE_EXPLANATION: RefCell<Option<String>> =
+        const { RefCell::new(None) };
+}
+
 impl Matcher for Pattern {
     fn paths_with_captures(
         &self,
         haystack: &Envelope,
     ) -> (Vec<Path>, HashMap<String, Vec<Path>>) {
         let results = self.vm_run(haystack);
+        if let Pattern::Meta(MetaPattern::Explained(explained)) = self {
+            let failure = results
+                .is_empty()
+                .then(|| explained.explanation().to_string());
+            LAST_FAILURE_EXPLANATION.with(|cell| *cell.borrow_mut() = failure);
+        }
         let mut paths = Vec::new();
         let mut captures: HashMap<String, Vec<Path>> = HashMap::new();
         for (p, caps) in results {
@@ -96,11 +160,33 @@ impl Pattern {
         Pattern::Leaf(LeafPattern::Cbor(CBORPattern::value(cbor)))
     }
 
+    /// Creates a new `Pattern` that matches the CBOR value written in dCBOR
+    /// diagnostic notation, such as `42`, `"hello"` or `1(1703462400)`.
+    ///
+    /// Returns an error if `diag` is not valid diagnostic notation.
+    pub fn from_dcbor_diagnostic(diag: &str) -> Result<Self> {
+        let parsed = dcbor_parse::parse_dcbor_item(diag)
+            .map_err(|e| Error::InvalidDCBORDiagnostic(e.to_string()))?;
+        let cbor = CBOR::try_from_data(parsed.to_cbor_data())
+            .map_err(|e| Error::InvalidDCBORDiagnostic(e.to_string()))?;
+        Ok(Pattern::cbor(cbor))
+    }
+
     /// Creates a new `Pattern` that matches CBOR values using dcbor-pattern
     /// expressions.
     pub fn cbor_pattern(pattern: DCBORPattern) -> Self {
         Pattern::Leaf(LeafPattern::Cbor(CBORPattern::pattern(pattern)))
     }
+
+    /// Creates a new `Pattern` that matches CBOR values whose deterministic
+    /// CBOR encoding matches the given binary regex, such as
+    /// `^\x18` for unsigned integers encoded with a one-byte argument.
+    ///
+    /// Like the other CBOR patterns, this only matches leaf and known value
+    /// subjects.
+    pub fn cbor_bytes_pattern(byte_regex: regex::bytes::Regex) -> Self {
+        Pattern::Leaf(LeafPattern::Cbor(CBORPattern::bytes_regex(byte_regex)))
+    }
 }
 
 impl Pattern {
@@ -131,6 +217,54 @@ impl Pattern {
     pub fn text_regex(regex: regex::Regex) -> Self {
         Pattern::Leaf(LeafPattern::Text(TextPattern::regex(regex)))
     }
+
+    /// Creates a new `Pattern` that matches text equal to any of the given
+    /// values. Unlike an `or` of `text` patterns, this is a single set
+    /// lookup however many values there are.
+    pub fn text_matches_any<T: Into<String>>(
+        values: impl IntoIterator<Item = T>,
+    ) -> Self {
+        Pattern::Leaf(LeafPattern::Text(TextPattern::one_of(values)))
+    }
+
+    /// Creates a new `Pattern` that matches text values against a glob,
+    /// where `*` matches any run of characters and `?` matches any single
+    /// character. The whole text must match, so `"Alice*"` matches text
+    /// starting with `Alice`.
+    ///
+    /// Returns an error if the equivalent regex cannot be compiled.
+    pub fn text_matches_glob(glob: &str) -> Result<Self> {
+        Ok(Pattern::Leaf(LeafPattern::Text(TextPattern::glob(glob)?)))
+    }
+
+    /// Creates a new `Pattern` that matches text values starting with
+    /// `prefix`. An empty prefix matches any text.
+    pub fn text_starts_with(prefix: impl Into<String>) -> Self {
+        Pattern::Leaf(LeafPattern::Text(TextPattern::starts_with(prefix)))
+    }
+
+    /// Creates a new `Pattern` that matches text values ending with
+    /// `suffix`. An empty suffix matches any text.
+    pub fn text_ends_with(suffix: impl Into<String>) -> Self {
+        Pattern::Leaf(LeafPattern::Text(TextPattern::ends_with(suffix)))
+    }
+
+    /// Creates a new `Pattern` that matches text values of exactly `length`
+    /// characters. Length is counted in Unicode scalar values, so `"café"`
+    /// has length 4.
+    pub fn text_length(length: usize) -> Self {
+        Pattern::Leaf(LeafPattern::Text(TextPattern::length(length)))
+    }
+
+    /// Creates a new `Pattern` that matches text values whose length in
+    /// characters is within `range`, such as `4..` for at least 4.
+    ///
+    /// Returns `Error::EmptyLengthRange` if `range` is empty, such as `..0`.
+    pub fn text_length_range(range: impl RangeBounds<usize>) -> Result<Self> {
+        Ok(Pattern::Leaf(LeafPattern::Text(TextPattern::length_range(
+            range,
+        )?)))
+    }
 }
 
 impl Pattern {
@@ -173,6 +307,34 @@ impl Pattern {
     pub fn date_regex(regex: regex::Regex) -> Self {
         Pattern::Leaf(LeafPattern::Date(DatePattern::regex(regex)))
     }
+
+    /// Creates a new `Pattern` that matches Date (CBOR tag 1) values at any
+    /// time in `year`. Shorthand for a `date_regex` on the year of the
+    /// ISO-8601 representation.
+    pub fn date_in_year(year: i32) -> Self {
+        let year = match year {
+            0..=9999 => format!("{:04}", year),
+            ..0 => format!("-{:04}", year.unsigned_abs()),
+            _ => format!("+{}", year),
+        };
+        Pattern::date_regex(
+            regex::Regex::new(&format!("^{}-", regex::escape(&year))).unwrap(),
+        )
+    }
+
+    /// Creates a new `Pattern` that matches Date (CBOR tag 1) values in
+    /// `month` (1 to 12) of any year. Shorthand for a `date_regex` on the
+    /// month of the ISO-8601 representation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `month` is not between 1 and 12.
+    pub fn date_in_month(month: u32) -> Self {
+        assert!((1..=12).contains(&month), "month must be between 1 and 12");
+        Pattern::date_regex(
+            regex::Regex::new(&format!(r"^-?\d{{4,}}-{:02}-", month)).unwrap(),
+        )
+    }
 }
 
 impl Pattern {
@@ -225,6 +387,80 @@ impl Pattern {
     pub fn number_nan() -> Self {
         Pattern::Leaf(LeafPattern::Number(NumberPattern::nan()))
     }
+
+    /// Creates a new `Pattern` that matches positive infinity.
+    pub fn number_positive_infinity() -> Self {
+        Pattern::Leaf(LeafPattern::Number(NumberPattern::infinity()))
+    }
+
+    /// Creates a new `Pattern` that matches negative infinity.
+    pub fn number_negative_infinity() -> Self {
+        Pattern::Leaf(LeafPattern::Number(NumberPattern::neg_infinity()))
+    }
+
+    /// Creates a new `Pattern` that matches positive or negative infinity.
+    pub fn number_is_infinite() -> Self {
+        Pattern::Leaf(LeafPattern::Number(NumberPattern::infinite()))
+    }
+
+    /// Creates a new `Pattern` that matches numbers that are a whole multiple
+    /// of `divisor`, allowing for floating-point rounding. NaN and infinite
+    /// numbers never match.
+    ///
+    /// Returns `Error::InvalidDivisorValue` if `divisor` is zero, infinite,
+    /// or NaN.
+    pub fn number_divisible_by(divisor: impl Into<f64>) -> Result<Self> {
+        Ok(Pattern::Leaf(LeafPattern::Number(NumberPattern::divisible_by(
+            divisor.into(),
+        )?)))
+    }
+
+    /// Creates a new `Pattern` that matches numbers that are neither NaN nor
+    /// infinite.
+    pub fn number_is_finite() -> Self {
+        Pattern::Leaf(LeafPattern::Number(NumberPattern::finite()))
+    }
+
+    /// Creates a new `Pattern` that matches numbers equal to any of the given
+    /// values. Unlike an `or` of `number` patterns, this is a single scan of
+    /// the values. A NaN in the values matches any NaN number.
+    pub fn number_matches_any<T: Into<f64>>(
+        values: impl IntoIterator<Item = T>,
+    ) -> Self {
+        Pattern::Leaf(LeafPattern::Number(NumberPattern::one_of(values)))
+    }
+}
+
+impl Pattern {
+    /// Creates a new `Pattern` that matches any number encoded as a CBOR
+    /// integer.
+    pub fn any_integer() -> Self {
+        Pattern::Leaf(LeafPattern::Integer(IntegerPattern::any()))
+    }
+
+    /// Creates a new `Pattern` that matches a specific integer. Unlike
+    /// `number`, the value must be encoded as a CBOR integer.
+    pub fn integer(value: i64) -> Self {
+        Pattern::Leaf(LeafPattern::Integer(IntegerPattern::value(value)))
+    }
+
+    /// Creates a new `Pattern` that matches integers within a specified range
+    /// (inclusive).
+    pub fn integer_range(range: RangeInclusive<i64>) -> Self {
+        Pattern::Leaf(LeafPattern::Integer(IntegerPattern::range(range)))
+    }
+
+    /// Creates a new `Pattern` that matches integers greater than the
+    /// specified value.
+    pub fn integer_greater_than(value: i64) -> Self {
+        Pattern::Leaf(LeafPattern::Integer(IntegerPattern::greater_than(value)))
+    }
+
+    /// Creates a new `Pattern` that matches integers less than the specified
+    /// value.
+    pub fn integer_less_than(value: i64) -> Self {
+        Pattern::Leaf(LeafPattern::Integer(IntegerPattern::less_than(value)))
+    }
 }
 
 impl Pattern {
@@ -258,11 +494,33 @@ impl Pattern {
         Pattern::Leaf(LeafPattern::KnownValue(KnownValuePattern::named(name)))
     }
 
+    /// Creates a new `Pattern` that matches either the known value named
+    /// `name` or the text `name`, for fields that may be stored either way.
+    /// Shorthand for `or([known_value_named(name), text(name)])`, so it
+    /// displays as `'name' | "name"`, parenthesized when nested in another
+    /// operator.
+    pub fn known_value_or_text(name: &str) -> Self {
+        Pattern::or(vec![
+            Pattern::known_value_named(name),
+            Pattern::text(name),
+        ])
+    }
+
     pub fn known_value_regex(regex: regex::Regex) -> Self {
         Pattern::Leaf(LeafPattern::KnownValue(KnownValuePattern::regex(regex)))
     }
 
     pub fn unit() -> Self { Self::known_value(known_values::UNIT) }
+
+    /// Creates a new `Pattern` that matches the known value with the given
+    /// assigned name, resolved against the global known values registry.
+    ///
+    /// Returns an error if the name is not registered.
+    pub fn parse_known_value_from_name(name: &str) -> Result<Self> {
+        registered_known_value(name)
+            .map(Self::known_value)
+            .ok_or_else(|| Error::UnknownKnownValueName(name.to_string()))
+    }
 }
 
 impl Pattern {
@@ -278,6 +536,16 @@ impl Pattern {
         Pattern::Leaf(LeafPattern::Array(ArrayPattern::count(count)))
     }
 
+    /// Creates a new `Pattern` that matches arrays whose element at the
+    /// 0-based `index` matches `element_pattern`. Arrays shorter than
+    /// `index + 1` do not match.
+    pub fn cbor_array_at(index: usize, element_pattern: DCBORPattern) -> Self {
+        Pattern::Leaf(LeafPattern::Array(ArrayPattern::at_index(
+            index,
+            element_pattern,
+        )))
+    }
+
     /// Creates an array pattern from a dcbor-pattern.
     /// This is used internally by the parser to delegate to dcbor-pattern.
     pub fn array_from_dcbor_pattern(pattern: DCBORPattern) -> Self {
@@ -299,6 +567,25 @@ impl Pattern {
     pub fn map_with_count(count: usize) -> Self {
         Pattern::Leaf(LeafPattern::Map(MapPattern::interval(count..=count)))
     }
+
+    /// Creates a new `Pattern` that matches maps with at least one key
+    /// matching `key_pattern`.
+    pub fn map_with_required_key(key_pattern: DCBORPattern) -> Self {
+        Pattern::Leaf(LeafPattern::Map(MapPattern::required_key(key_pattern)))
+    }
+
+    /// Creates a new `Pattern` that matches maps with at least one entry
+    /// whose key matches `key_pattern` and whose value matches
+    /// `value_pattern`.
+    pub fn map_with_required_key_value(
+        key_pattern: DCBORPattern,
+        value_pattern: DCBORPattern,
+    ) -> Self {
+        Pattern::Leaf(LeafPattern::Map(MapPattern::required_key_value(
+            key_pattern,
+            value_pattern,
+        )))
+    }
 }
 
 impl Pattern {
@@ -343,6 +630,21 @@ impl Pattern {
         ))
     }
 
+    /// Creates a new `Pattern` that matches a value tagged with `tag_value`
+    /// and descends into its content, matching it with `content_pattern`, an
+    /// envelope pattern rather than a dcbor-pattern.
+    ///
+    /// Content that encodes an envelope is matched as that envelope; any
+    /// other content is matched as a leaf.
+    pub fn tag_with_envelope_content(
+        tag_value: u64,
+        content_pattern: Pattern,
+    ) -> Self {
+        Pattern::Structure(StructurePattern::TaggedContent(
+            TaggedContentPattern::new(tag_value, content_pattern),
+        ))
+    }
+
     /// Creates a new `Pattern` that matches a tagged value from a
     /// dcbor_pattern::TaggedPattern. This is an internal helper for the
     /// parser.
@@ -370,6 +672,13 @@ impl Pattern {
         Pattern::Structure(StructurePattern::Leaf(LeafStructurePattern::new()))
     }
 
+    /// Creates a new `Pattern` that matches a leaf envelope whose CBOR value
+    /// is exactly `value`. Unlike `cbor(value)`, this does not match a node
+    /// whose subject is `value`.
+    pub fn leaf_with_value(value: impl CBOREncodable) -> Self {
+        Pattern::and(vec![Pattern::leaf(), Pattern::cbor(value)])
+    }
+
     pub fn any_assertion() -> Self {
         Pattern::Structure(StructurePattern::Assertions(
             AssertionsPattern::any(),
@@ -387,6 +696,94 @@ impl Pattern {
             AssertionsPattern::with_object(pattern),
         ))
     }
+
+    /// Creates a pattern that matches an assertion whose predicate and
+    /// object both match the given patterns.
+    pub fn assertion_with_predicate_and_object(
+        predicate: Pattern,
+        object: Pattern,
+    ) -> Self {
+        Pattern::Structure(StructurePattern::Assertions(
+            AssertionsPattern::with_both(predicate, object),
+        ))
+    }
+
+    /// Creates a pattern that matches the assertion at index `n` of a node,
+    /// counting from zero in canonical order (sorted by digest), if that
+    /// assertion matches `pattern`.
+    pub fn nth_assertion(n: usize, pattern: Pattern) -> Self {
+        Pattern::Structure(StructurePattern::Assertions(
+            AssertionsPattern::at_index(n, pattern),
+        ))
+    }
+
+    /// Creates a pattern that matches an assertion for which `f`, called
+    /// with the assertion's predicate and object, returns true.
+    ///
+    /// This is an escape hatch for rules that patterns cannot express. The
+    /// function has no pattern syntax, so the pattern displays as
+    /// `assert(custom)` and cannot be parsed or decoded from CBOR.
+    #[cfg(feature = "custom-predicates")]
+    pub fn assertion_where(
+        f: impl Fn(&Envelope, &Envelope) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Pattern::Structure(StructurePattern::Assertions(
+            AssertionsPattern::Where(
+                structure::AssertionPredicatePattern::new(std::sync::Arc::new(
+                    f,
+                )),
+            ),
+        ))
+    }
+
+    /// Creates a pattern that matches an assertion whose predicate is the
+    /// given known value. Shorthand for
+    /// `assertion_with_predicate(known_value(value))`.
+    pub fn assertion_pred_known_value(value: KnownValue) -> Self {
+        Pattern::assertion_with_predicate(Pattern::known_value(value))
+    }
+
+    /// Creates a pattern that matches an assertion whose predicate is the
+    /// given known value, such as `'isA'`. The same as
+    /// [`Pattern::assertion_pred_known_value`].
+    pub fn has_known_value_assertion(value: KnownValue) -> Self {
+        Pattern::assertion_pred_known_value(value)
+    }
+
+    /// Creates a pattern that matches an assertion whose predicate is the
+    /// given known value and whose object matches `object_pattern`.
+    pub fn has_known_value_assertion_with_object(
+        value: KnownValue,
+        object_pattern: Pattern,
+    ) -> Self {
+        Pattern::assertion_with_predicate_and_object(
+            Pattern::known_value(value),
+            object_pattern,
+        )
+    }
+
+    /// Creates a pattern that matches an `'isA': "type_name"` assertion, the
+    /// usual way of saying that an envelope is of a given type. Types that
+    /// are themselves known values need
+    /// [`Pattern::has_known_value_assertion_with_object`] instead.
+    pub fn has_type(type_name: &str) -> Self {
+        Pattern::has_known_value_assertion_with_object(
+            known_values::IS_A,
+            Pattern::text(type_name),
+        )
+    }
+
+    /// Creates a pattern that matches an assertion whose predicate and
+    /// object are exactly the given CBOR values.
+    pub fn assertion_exact(
+        predicate: impl CBOREncodable,
+        object: impl CBOREncodable,
+    ) -> Self {
+        Pattern::assertion_with_predicate_and_object(
+            Pattern::cbor(predicate),
+            Pattern::cbor(object),
+        )
+    }
 }
 
 impl Pattern {
@@ -399,6 +796,30 @@ impl Pattern {
             pattern,
         )))
     }
+
+    /// Creates a new `Pattern` that matches an envelope whose subject matches
+    /// `subject_pattern` and that has at least one assertion matching
+    /// `assertion_pattern`. Shorthand for
+    /// `and([subject(subject_pattern), assertion_pattern])`, so the paths
+    /// are those of `assertion_pattern`.
+    pub fn subject_and_assertion(
+        subject_pattern: Pattern,
+        assertion_pattern: Pattern,
+    ) -> Self {
+        Pattern::and(vec![Pattern::subject(subject_pattern), assertion_pattern])
+    }
+
+    /// Creates a new `Pattern` that matches the subject of any node matching
+    /// `node_pattern`. Shorthand for `node_pattern -> subj`.
+    pub fn subject_of_node_matching(node_pattern: Pattern) -> Self {
+        Pattern::traverse(vec![node_pattern, Pattern::any_subject()])
+    }
+
+    /// Creates a new `Pattern` that matches the subject of any node.
+    /// Shorthand for `node -> subj`.
+    pub fn traverse_to_subject() -> Self {
+        Pattern::subject_of_node_matching(Pattern::any_node())
+    }
 }
 
 impl Pattern {
@@ -421,6 +842,45 @@ impl Pattern {
             pattern,
         )))
     }
+
+    /// Creates a new `Pattern` that matches either a predicate or a subject
+    /// matching `pattern`, such as an identifier that may appear in either
+    /// position. Shorthand for `or([predicate(pattern), subject(pattern)])`,
+    /// which displays as `pred_or_subj(pattern)`.
+    pub fn predicate_or_subject(pattern: Pattern) -> Self {
+        Pattern::or(vec![
+            Pattern::predicate(pattern.clone()),
+            Pattern::subject(pattern),
+        ])
+    }
+
+    /// Creates a new `Pattern` that matches a predicate that is the given
+    /// known value. Shorthand for `predicate(known_value(value))`.
+    pub fn predicate_known_value(value: KnownValue) -> Self {
+        Pattern::predicate(Pattern::known_value(value))
+    }
+
+    /// Creates a new `Pattern` that matches an object that is the given
+    /// known value. Shorthand for `object(known_value(value))`.
+    pub fn object_known_value(value: KnownValue) -> Self {
+        Pattern::object(Pattern::known_value(value))
+    }
+
+    /// Creates a new `Pattern` that matches the object of any assertion whose
+    /// predicate matches `pred_pattern`. Shorthand for
+    /// `assertpred(pred_pattern) -> obj`.
+    pub fn object_of_assertion_with_predicate(pred_pattern: Pattern) -> Self {
+        Pattern::traverse(vec![
+            Pattern::assertion_with_predicate(pred_pattern),
+            Pattern::any_object(),
+        ])
+    }
+
+    /// Alias for [`object_of_assertion_with_predicate`](Self::object_of_assertion_with_predicate):
+    /// `assertpred(pred_pattern) -> obj`.
+    pub fn traverse_to_object_of(pred_pattern: Pattern) -> Self {
+        Pattern::object_of_assertion_with_predicate(pred_pattern)
+    }
 }
 
 impl Pattern {
@@ -430,6 +890,63 @@ impl Pattern {
         )))
     }
 
+    /// Creates a new `Pattern` that matches envelopes with the same digest
+    /// as `reference`. Shorthand for `digest(reference.digest())`.
+    ///
+    /// Like any digest match, this also accepts an elided, encrypted, or
+    /// compressed form of `reference`; use [`Pattern::deep_equal`] to
+    /// compare structure as well.
+    pub fn has_same_digest_as(reference: &Envelope) -> Self {
+        Pattern::digest(reference.digest())
+    }
+
+    /// Creates a new `Pattern` that matches envelopes with exactly the
+    /// structure of `target`, element by element.
+    ///
+    /// A digest match alone accepts any envelope with the same content,
+    /// including one where parts are elided, encrypted, or compressed. This
+    /// pattern also requires every element to be of the same case as in
+    /// `target`, so it fits tests that compare against an envelope built in
+    /// place. It grows with the size of `target` and is correspondingly
+    /// expensive to match.
+    pub fn deep_equal(target: &Envelope) -> Self {
+        let mut patterns = match target.case() {
+            EnvelopeCase::Node { subject, assertions, .. } => {
+                let mut patterns = vec![
+                    Pattern::node_with_assertions_range(
+                        assertions.len()..=assertions.len(),
+                    ),
+                    Pattern::subject(Pattern::deep_equal(subject)),
+                ];
+                patterns.extend(assertions.iter().map(|assertion| {
+                    Pattern::traverse(vec![
+                        Pattern::any_assertion(),
+                        Pattern::deep_equal(assertion),
+                    ])
+                }));
+                patterns
+            }
+            EnvelopeCase::Assertion(assertion) => vec![
+                Pattern::predicate(Pattern::deep_equal(&assertion.predicate())),
+                Pattern::object(Pattern::deep_equal(&assertion.object())),
+            ],
+            EnvelopeCase::Wrapped { envelope, .. } => {
+                vec![Pattern::wrapped_content_matching(Pattern::deep_equal(
+                    envelope,
+                ))]
+            }
+            EnvelopeCase::Elided(_) => vec![Pattern::elided()],
+            EnvelopeCase::Encrypted(_) => vec![Pattern::encrypted()],
+            EnvelopeCase::Compressed(_) => vec![Pattern::compressed()],
+            EnvelopeCase::Leaf { .. } | EnvelopeCase::KnownValue { .. } => {
+                vec![Pattern::leaf()]
+            }
+        };
+        // The digest comes last so that the path ends at the envelope itself.
+        patterns.push(Pattern::digest(target.digest()));
+        Pattern::and(patterns)
+    }
+
     pub fn digest_prefix(prefix: impl AsRef<[u8]>) -> Self {
         Pattern::Structure(StructurePattern::Digest(DigestPattern::prefix(
             prefix,
@@ -456,6 +973,52 @@ impl Pattern {
         )))
     }
 
+    /// Creates a new `Pattern` that matches a node only if every one of its
+    /// assertions matches the given pattern. The pattern is matched against
+    /// each assertion envelope, so `pred(...)` and `obj(...)` are the natural
+    /// building blocks.
+    pub fn node_matching_all_assertions(assertion_pattern: Pattern) -> Self {
+        Pattern::Structure(StructurePattern::Node(NodePattern::all_assertions(
+            assertion_pattern,
+        )))
+    }
+
+    /// Creates a new `Pattern` that matches a node only if the predicate of
+    /// every one of its assertions matches at least one of the given
+    /// patterns. A node with any other predicate does not match, which makes
+    /// this a closed-world check against unexpected assertions.
+    pub fn all_assertions_have_predicate_in(predicates: Vec<Pattern>) -> Self {
+        Pattern::Structure(StructurePattern::Node(NodePattern::all_preds_in(
+            predicates,
+        )))
+    }
+
+    /// Creates a new `Pattern` that matches a node with at least `min`, and
+    /// at most `max` if given, assertions whose predicate matches
+    /// `pred_pattern`.
+    pub fn node_assertion_predicate_count(
+        pred_pattern: Pattern,
+        min: usize,
+        max: Option<usize>,
+    ) -> Self {
+        let node = match max {
+            Some(max) => NodePattern::predicate_count(pred_pattern, min..=max),
+            None => NodePattern::predicate_count(pred_pattern, min..),
+        };
+        Pattern::Structure(StructurePattern::Node(node))
+    }
+
+    /// Creates a new `Pattern` that matches a node with exactly as many
+    /// assertions as there are patterns, where the assertions can be paired
+    /// one-to-one with the patterns so that each assertion matches its
+    /// pattern. The order of the patterns does not matter. As with
+    /// `node_matching_all_assertions`, each pattern is matched against an
+    /// assertion envelope, so `predicate` and `object` patterns are the most
+    /// useful.
+    pub fn node_with_exact_assertions(patterns: Vec<Pattern>) -> Self {
+        Pattern::Structure(StructurePattern::Node(NodePattern::exact(patterns)))
+    }
+
     pub fn obscured() -> Self {
         Pattern::Structure(StructurePattern::Obscured(ObscuredPattern::any()))
     }
@@ -477,6 +1040,46 @@ impl Pattern {
             ObscuredPattern::compressed(),
         ))
     }
+
+    /// Creates a new `Pattern` that matches any node, leaf, or wrapped
+    /// envelope, along with the assertions of a node (as `assert` does).
+    /// Obscured envelopes do not match.
+    pub fn any_structure() -> Self {
+        Pattern::or(vec![
+            Pattern::any_node(),
+            Pattern::leaf(),
+            Pattern::wrapped(),
+            Pattern::any_assertion(),
+        ])
+    }
+
+    /// Creates a new `Pattern` that matches any elided, encrypted, or
+    /// compressed envelope. An alias for [`Pattern::obscured`], named to
+    /// pair with [`Pattern::any_structure`].
+    pub fn any_obscured() -> Self { Pattern::obscured() }
+
+    /// Creates a structure pattern that matches envelopes of the named case:
+    /// `"node"`, `"leaf"`, `"wrapped"`, `"assertion"`, `"elided"`,
+    /// `"encrypted"` or `"compressed"`. The pattern matches on the form of
+    /// the envelope alone, not its content.
+    ///
+    /// Each name maps to the corresponding keyword pattern, so
+    /// `"assertion"` behaves like `assert` and matches the assertions of a
+    /// node.
+    ///
+    /// Returns an error if the name is not one of these.
+    pub fn matches_case(variant_name: &str) -> Result<Self> {
+        match variant_name {
+            "node" => Ok(Pattern::any_node()),
+            "leaf" => Ok(Pattern::leaf()),
+            "wrapped" => Ok(Pattern::wrapped()),
+            "assertion" => Ok(Pattern::any_assertion()),
+            "elided" => Ok(Pattern::elided()),
+            "encrypted" => Ok(Pattern::encrypted()),
+            "compressed" => Ok(Pattern::compressed()),
+            _ => Err(Error::UnknownEnvelopeCase(variant_name.to_string())),
+        }
+    }
 }
 
 //
@@ -490,6 +1093,9 @@ impl Pattern {
 impl Pattern {
     /// Creates a new `Pattern` that matches any element.
     pub fn any() -> Self { Pattern::Meta(MetaPattern::Any(AnyPattern::new())) }
+
+    /// Creates a new `Pattern` that matches no element. Equivalent to `!*`.
+    pub fn none() -> Self { Pattern::not_matching(Pattern::any()) }
 }
 
 impl Pattern {
@@ -504,6 +1110,37 @@ impl Pattern {
     pub fn or(patterns: Vec<Pattern>) -> Self {
         Pattern::Meta(MetaPattern::Or(OrPattern::new(patterns)))
     }
+
+    /// Creates a new `Pattern` that matches if at least one of the specified
+    /// patterns matches, trying them in descending order of priority.
+    ///
+    /// Matching the same paths as `or`, this only changes the order in
+    /// which the alternatives are explored, and so the order of the
+    /// returned paths. Branches with equal priority keep their relative
+    /// order.
+    pub fn or_with_priority(patterns: Vec<(u32, Pattern)>) -> Self {
+        Pattern::Meta(MetaPattern::PriorityOr(PriorityOrPattern::new(patterns)))
+    }
+
+    /// Creates a new `Pattern` that matches `self` if it matches, and
+    /// otherwise matches `fallback`. Unlike `or`, the fallback's paths are
+    /// never returned when `self` matches.
+    pub fn or_default(self, fallback: Pattern) -> Self {
+        Pattern::or(vec![
+            self.clone(),
+            Pattern::and(vec![Pattern::not_matching(self), fallback]),
+        ])
+    }
+
+    /// Creates a new `Pattern` that matches wherever `pattern` matches, and
+    /// also matches a bare leaf that has no assertions at all. This is
+    /// useful for optional schema fields.
+    ///
+    /// A node with zero assertions cannot exist, so the "empty" case is
+    /// expressed with `leaf` and the result displays as `pattern | leaf`.
+    pub fn or_empty(pattern: Pattern) -> Self {
+        Pattern::or(vec![pattern, Pattern::leaf()])
+    }
 }
 
 impl Pattern {
@@ -511,22 +1148,290 @@ impl Pattern {
     pub fn traverse(patterns: Vec<Pattern>) -> Self {
         Pattern::Meta(MetaPattern::Traverse(TraversePattern::new(patterns)))
     }
+
+    /// Creates a new `Pattern` that matches a traversal of `steps`, visiting
+    /// the children each step descends into in the given `order`.
+    ///
+    /// With [`TraversalOrder::BreadthFirst`], shallower paths are returned
+    /// before deeper ones. Only the traversal's own descents are reordered;
+    /// steps that run as separate sub-matches, such as repeats and searches,
+    /// keep their own order.
+    pub fn traverse_ordered(
+        steps: Vec<Pattern>,
+        order: TraversalOrder,
+    ) -> Self {
+        Pattern::Meta(MetaPattern::Traverse(
+            TraversePattern::new(steps).with_order(order),
+        ))
+    }
+
+    /// Returns this pattern as a traversal in the given order. A depth-first
+    /// traversal keeps its steps; anything else becomes a single step.
+    pub(crate) fn into_ordered_traversal(self, order: TraversalOrder) -> Self {
+        let steps = match self {
+            Pattern::Meta(MetaPattern::Traverse(traverse))
+                if traverse.order() == TraversalOrder::DepthFirst =>
+            {
+                traverse.patterns()
+            }
+            other => vec![other],
+        };
+        Pattern::traverse_ordered(steps, order)
+    }
+
+    /// Creates a new depth-first traversal. This is the same as `traverse`.
+    pub fn traverse_depth_first(steps: Vec<Pattern>) -> Self {
+        Pattern::traverse_ordered(steps, TraversalOrder::DepthFirst)
+    }
+
+    /// Creates a new breadth-first traversal, written `bfs(a -> b)`.
+    pub fn traverse_breadth_first(steps: Vec<Pattern>) -> Self {
+        Pattern::traverse_ordered(steps, TraversalOrder::BreadthFirst)
+    }
+
+    /// Creates a new `Pattern` that matches `child`, then steps back from the
+    /// end of each matched path to the envelope containing it and matches
+    /// `parent` there. The returned paths end at the parent's match.
+    ///
+    /// Envelopes do not link to their parents, so the parent is taken from
+    /// the path matched so far; a match with no preceding element in its
+    /// path is discarded.
+    pub fn traverse_back(child: Pattern, parent: Pattern) -> Self {
+        Pattern::Meta(MetaPattern::TraverseBack(TraverseBackPattern::new(
+            child, parent,
+        )))
+    }
+
+    /// Creates a new `Pattern` that matches a chain of steps, each step
+    /// matching from where the previous one ended. This is an alias for
+    /// `traverse`.
+    pub fn chain(steps: Vec<Pattern>) -> Self { Pattern::traverse(steps) }
+
+    /// Creates a new `Pattern` that traverses the given steps, where each
+    /// step is paired with whether it is required. An optional step is
+    /// matched greedily zero or one times, as `(step)?`, so
+    /// `[(unwrap, false), (node, true)]` means "optionally unwrap, then
+    /// match a node".
+    pub fn traverse_optional_steps(steps: Vec<(Pattern, bool)>) -> Self {
+        Pattern::traverse(
+            steps
+                .into_iter()
+                .map(|(step, required)| {
+                    if required {
+                        step
+                    } else {
+                        Pattern::repeat(step, 0..=1, Reluctance::Greedy)
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// Creates a new `Pattern` that takes `body` steps for as long as the
+    /// envelope each step starts from matches `condition`. Steps are taken
+    /// greedily, as `(condition & body)*`, so the match is the longest such
+    /// chain, which may be empty.
+    pub fn traverse_while(condition: Pattern, body: Pattern) -> Self {
+        Pattern::Meta(MetaPattern::While(WhilePattern::new(condition, body)))
+    }
 }
 
 impl Pattern {
     /// Creates a new `Pattern` that searches for a specific pattern within the
     /// envelope. Useful for finding patterns that may not be at the root
     /// of the envelope.
+    ///
+    /// Each returned path starts at the envelope the search was run on and
+    /// includes every intermediate envelope visited on the way to the match,
+    /// so a match at depth `n` yields a path of `n + 1` elements.
     pub fn search(pattern: Pattern) -> Self {
         Pattern::Meta(MetaPattern::Search(SearchPattern::new(pattern)))
     }
+
+    /// Creates a new `Pattern` that searches like `search`, but stops walking
+    /// the envelope once `max_results` paths have been found.
+    pub fn limited_search(pattern: Pattern, max_results: usize) -> Self {
+        Pattern::Meta(MetaPattern::Search(
+            SearchPattern::new(pattern).with_limit(max_results),
+        ))
+    }
+
+    /// Creates a new `Pattern` that matches the current envelope when the
+    /// number of paths `pattern` produces from it is at least `min` and, if
+    /// given, at most `max`. The inner pattern's captures are discarded.
+    pub fn search_count(
+        pattern: Pattern,
+        min: usize,
+        max: Option<usize>,
+    ) -> Self {
+        let count = match max {
+            Some(max) => SearchCountPattern::new(pattern, min..=max),
+            None => SearchCountPattern::new(pattern, min..),
+        };
+        Pattern::Meta(MetaPattern::SearchCount(count))
+    }
+
+    /// Creates a new `Pattern` that matches the current envelope when
+    /// `pattern` occurs exactly `expected` times anywhere within it.
+    /// Shorthand for `count(expected, search(pattern))`.
+    pub fn search_count_equals(pattern: Pattern, expected: usize) -> Self {
+        Pattern::search_count(
+            Pattern::search(pattern),
+            expected,
+            Some(expected),
+        )
+    }
+
+    /// Creates a new `Pattern` that searches for a specific text value.
+    /// Shorthand for `search(text(value))`.
+    pub fn search_text<T: Into<String>>(value: T) -> Self {
+        Pattern::search(Pattern::text(value))
+    }
+
+    /// Creates a new `Pattern` that searches for text values matching the
+    /// given regular expression. Shorthand for `search(text_regex(regex))`.
+    pub fn search_text_regex(regex: regex::Regex) -> Self {
+        Pattern::search(Pattern::text_regex(regex))
+    }
+
+    /// Creates a new `Pattern` that searches for a specific number value.
+    /// Shorthand for `search(number(value))`.
+    pub fn search_number<T: Into<f64>>(value: T) -> Self {
+        Pattern::search(Pattern::number(value))
+    }
+
+    /// Creates a new `Pattern` that searches for numbers within a range
+    /// (inclusive). Shorthand for `search(number_range(range))`.
+    pub fn search_number_range<A: Into<f64> + Copy>(
+        range: RangeInclusive<A>,
+    ) -> Self {
+        Pattern::search(Pattern::number_range(range))
+    }
+
+    /// Creates a new `Pattern` that searches for a specific date value.
+    /// Shorthand for `search(date(date))`.
+    pub fn search_date(date: Date) -> Self {
+        Pattern::search(Pattern::date(date))
+    }
+
+    /// Creates a new `Pattern` that searches for dates whose ISO-8601 string
+    /// representation matches the given regular expression. Shorthand for
+    /// `search(date_regex(regex))`.
+    pub fn search_date_regex(regex: regex::Regex) -> Self {
+        Pattern::search(Pattern::date_regex(regex))
+    }
+
+    /// Creates a new `Pattern` that searches for assertions whose predicate
+    /// matches the given pattern. Shorthand for
+    /// `search(assertion_with_predicate(pattern))`.
+    pub fn search_predicate(pred_pattern: Pattern) -> Self {
+        Pattern::search(Pattern::assertion_with_predicate(pred_pattern))
+    }
+
+    /// Creates a new `Pattern` that searches for assertions whose object
+    /// matches the given pattern. Shorthand for
+    /// `search(assertion_with_object(pattern))`.
+    pub fn search_object(obj_pattern: Pattern) -> Self {
+        Pattern::search(Pattern::assertion_with_object(obj_pattern))
+    }
+
+    /// Creates a new `Pattern` that searches for envelopes whose subject
+    /// matches the given pattern. Shorthand for `search(subject(pattern))`.
+    pub fn search_subject(subj_pattern: Pattern) -> Self {
+        Pattern::search(Pattern::subject(subj_pattern))
+    }
+
+    /// Creates a new `Pattern` that matches an envelope with no elided
+    /// element anywhere in its tree, such as a credential presented in full.
+    /// Shorthand for `!search(elided)`.
+    pub fn has_no_elided() -> Self {
+        Pattern::not_matching(Pattern::search(Pattern::elided()))
+    }
+
+    /// Creates a new `Pattern` that matches an envelope with no encrypted
+    /// element anywhere in its tree. Shorthand for `!search(encrypted)`.
+    pub fn has_no_encrypted() -> Self {
+        Pattern::not_matching(Pattern::search(Pattern::encrypted()))
+    }
+
+    /// Creates a new `Pattern` that matches an envelope with no elided,
+    /// encrypted, or compressed element anywhere in its tree. Shorthand for
+    /// `!search(obscured)`.
+    pub fn has_no_obscured() -> Self {
+        Pattern::not_matching(Pattern::search(Pattern::obscured()))
+    }
+}
+
+impl Pattern {
+    /// Creates a new `Pattern` that matches like `pattern` but returns each
+    /// distinct path only once, even when several branches reach it.
+    pub fn dedup(pattern: Pattern) -> Self {
+        Pattern::Meta(MetaPattern::Dedup(DedupPattern::new(pattern)))
+    }
+
+    /// Returns this pattern with a human-readable explanation attached.
+    ///
+    /// The explanation is for debugging only: matching is unchanged and the
+    /// explanation is not part of the pattern's `Display` form, so it does
+    /// not survive a round trip through text or CBOR. When an explained
+    /// pattern fails to match, the explanation can be retrieved with
+    /// [`Pattern::last_failure_explanation`].
+    pub fn with_explanation(self, text: impl Into<String>) -> Self {
+        Pattern::Meta(MetaPattern::Explained(ExplainedPattern::new(self, text)))
+    }
+
+    /// Returns the explanation of the most recent explained pattern that
+    /// failed to match on this thread, if any.
+    ///
+    /// Only patterns created with [`Pattern::with_explanation`] that are
+    /// matched directly update this state; a successful match clears it.
+    /// Explanations on patterns nested inside other patterns are not
+    /// recorded. This is a debugging aid and should not drive program logic.
+    pub fn last_failure_explanation() -> Option<String> {
+        LAST_FAILURE_EXPLANATION.with(|cell| cell.borrow().clone())
+    }
+
+    /// Creates a new `Pattern` that matches like `inner` but returns only the
+    /// first path ending at each distinct envelope. Unlike `dedup`, paths
+    /// that reach the same envelope by different routes are collapsed.
+    pub fn unique_by_digest(inner: Pattern) -> Self {
+        Pattern::Meta(MetaPattern::Unique(UniquePattern::new(inner)))
+    }
+
+    /// Creates a new `Pattern` that matches like `inner` but keeps only the
+    /// paths for which `predicate` returns true. This is an escape hatch for
+    /// conditions the pattern syntax cannot express; the resulting pattern
+    /// displays as `filter(<inner>)` and cannot be parsed back.
+    pub fn path_filter(
+        inner: Pattern,
+        predicate: impl Fn(&Path) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Pattern::Meta(MetaPattern::PathFilter(PathFilterPattern::new(
+            inner,
+            std::sync::Arc::new(predicate),
+        )))
+    }
 }
 
 impl Pattern {
     /// Creates a new `Pattern` that negates another pattern; matches if the
     /// specified pattern does not match.
+    ///
+    /// Negating a negation cancels out when the inner pattern only ever
+    /// returns the envelope being matched, as a leaf pattern does, so
+    /// `not_matching(not_matching(p))` is then `p` itself. Otherwise the
+    /// double negation is kept, since `p` could return other paths.
     pub fn not_matching(pattern: Pattern) -> Self {
-        Pattern::Meta(MetaPattern::Not(NotPattern::new(pattern)))
+        match pattern {
+            Pattern::Meta(MetaPattern::Not(not))
+                if not.pattern().yields_haystack() =>
+            {
+                not.pattern().clone()
+            }
+            pattern => {
+                Pattern::Meta(MetaPattern::Not(NotPattern::new(pattern)))
+            }
+        }
     }
 }
 
@@ -575,6 +1480,30 @@ impl Pattern {
         )))
     }
 
+    /// Creates a new `Pattern` that greedily matches `inner` zero or one
+    /// times, like `(inner)?`.
+    pub fn optional(inner: Pattern) -> Self {
+        Pattern::repeat(inner, 0..=1, Reluctance::Greedy)
+    }
+
+    /// Creates a new `Pattern` that lazily matches `inner` zero or one times,
+    /// like `(inner)??`.
+    pub fn optional_lazy(inner: Pattern) -> Self {
+        Pattern::repeat(inner, 0..=1, Reluctance::Lazy)
+    }
+
+    /// Creates a new `Pattern` that greedily matches `inner` one or more
+    /// times, like `(inner)+`.
+    pub fn one_or_more(inner: Pattern) -> Self {
+        Pattern::repeat(inner, 1.., Reluctance::Greedy)
+    }
+
+    /// Creates a new `Pattern` that greedily matches `inner` zero or more
+    /// times, like `(inner)*`.
+    pub fn zero_or_more(inner: Pattern) -> Self {
+        Pattern::repeat(inner, .., Reluctance::Greedy)
+    }
+
     pub fn group(pattern: Pattern) -> Self {
         Pattern::Meta(MetaPattern::Group(GroupPattern::new(pattern)))
     }
@@ -585,12 +1514,158 @@ impl Pattern {
     pub fn capture(name: impl AsRef<str>, pattern: Pattern) -> Self {
         Pattern::Meta(MetaPattern::Capture(CapturePattern::new(name, pattern)))
     }
+
+    /// Returns a copy of this pattern with every capture whose name is a key
+    /// in `map` renamed to the corresponding value. Captures not named in
+    /// `map` keep their names.
+    pub fn clone_with_new_captures(
+        self,
+        map: &HashMap<String, String>,
+    ) -> Self {
+        let pattern = self.map_child_patterns(&mut |child| {
+            child.clone_with_new_captures(map)
+        });
+        match pattern {
+            Pattern::Meta(MetaPattern::Capture(capture)) => {
+                match map.get(capture.name()) {
+                    Some(name) => {
+                        Pattern::capture(name, capture.pattern().clone())
+                    }
+                    None => Pattern::Meta(MetaPattern::Capture(capture)),
+                }
+            }
+            other => other,
+        }
+    }
+
+    /// Returns `true` if this pattern contains a capture anywhere in its
+    /// tree, and so may produce captures when matched.
+    pub fn has_captures(&self) -> bool { self.capture_depth() > 0 }
+
+    /// Returns the maximum nesting depth of captures in this pattern: `0` if
+    /// it has none, `1` if no capture contains another, and so on.
+    pub fn capture_depth(&self) -> usize {
+        let child_depth = self
+            .child_patterns()
+            .into_iter()
+            .map(Pattern::capture_depth)
+            .max()
+            .unwrap_or(0);
+        match self {
+            Pattern::Meta(MetaPattern::Capture(_)) => child_depth + 1,
+            Pattern::Leaf(LeafPattern::Cbor(cbor)) => {
+                cbor.dcbor_capture_depth()
+            }
+            _ => child_depth,
+        }
+    }
+
+    /// Returns true if this pattern and `other` are structurally identical
+    /// apart from the names of their captures, and so produce the same
+    /// match paths.
+    pub fn equivalent_to(&self, other: &Pattern) -> bool {
+        self.clone().without_capture_names()
+            == other.clone().without_capture_names()
+    }
+
+    /// Returns a hash of this pattern's structure, ignoring the names of its
+    /// captures. Patterns that are [`equivalent_to`](Self::equivalent_to)
+    /// each other have the same structural hash.
+    ///
+    /// The hash uses `DefaultHasher::new()`, which is deterministic within a
+    /// build but not guaranteed to be stable across Rust releases, so it
+    /// should not be persisted.
+    pub fn structural_hash(&self) -> u64 {
+        use std::hash::{DefaultHasher, Hash, Hasher};
+
+        let mut hasher = DefaultHasher::new();
+        self.clone().without_capture_names().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Returns a copy of this pattern with every capture given the same
+    /// empty name.
+    fn without_capture_names(self) -> Self {
+        let pattern =
+            self.map_child_patterns(&mut |child| child.without_capture_names());
+        match pattern {
+            Pattern::Meta(MetaPattern::Capture(capture)) => {
+                Pattern::capture("", capture.pattern().clone())
+            }
+            other => other,
+        }
+    }
 }
 
 //
 //
 // endregion
 
+impl Pattern {
+    /// Removes consecutive envelopes with the same digest from a path,
+    /// keeping the first of each run.
+    pub fn compress_path(path: Path) -> Path {
+        let mut result: Path = Vec::with_capacity(path.len());
+        for envelope in path {
+            if result.last().map(|last| last.digest())
+                != Some(envelope.digest())
+            {
+                result.push(envelope);
+            }
+        }
+        result
+    }
+
+    /// Applies [`Pattern::compress_path`] to each path. Paths are not
+    /// deduplicated against each other.
+    pub fn paths_compressed(paths: Vec<Path>) -> Vec<Path> {
+        paths.into_iter().map(Pattern::compress_path).collect()
+    }
+
+    /// Returns the number of assertions matching `assertion_pattern` on the
+    /// envelopes at the tips of this pattern's paths through `haystack`.
+    ///
+    /// `assertion_pattern` is matched against each assertion envelope, as in
+    /// [`Pattern::node_matching_all_assertions`]. Assertions are only tested,
+    /// so no path is built for each one. An envelope without assertions, or
+    /// a pattern that does not match, contributes zero.
+    pub fn count_matching_assertions(
+        &self,
+        haystack: &Envelope,
+        assertion_pattern: &Pattern,
+    ) -> usize {
+        self.paths(haystack)
+            .iter()
+            .filter_map(|path| path.last())
+            .map(|tip| {
+                tip.assertions()
+                    .iter()
+                    .filter(|assertion| assertion_pattern.matches(assertion))
+                    .count()
+            })
+            .sum()
+    }
+}
+
+impl Pattern {
+    /// Displays this pattern as an operand of `&`, `!`, `->` or `<-`,
+    /// parenthesizing it if it is an alternation, which binds more loosely
+    /// than any of them.
+    pub(crate) fn operand(&self) -> String {
+        match self {
+            Pattern::Meta(MetaPattern::Or(or))
+                if or.predicate_or_subject().is_some() =>
+            {
+                self.to_string()
+            }
+            Pattern::Meta(MetaPattern::Or(_) | MetaPattern::PriorityOr(_)) => {
+                format!("({})", self)
+            }
+            _ => self.to_string(),
+        }
+    }
+}
+
 impl std::fmt::Display for Pattern {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -601,6 +1676,75 @@ impl std::fmt::Display for Pattern {
     }
 }
 
+/// `a >> b` builds a traversal from `a` to `b`. Chaining is
+/// left-associative and flattens, so `a >> b >> c` is the same pattern as
+/// `Pattern::traverse(vec![a, b, c])`.
+impl std::ops::Shr<Pattern> for Pattern {
+    type Output = Pattern;
+
+    fn shr(self, rhs: Pattern) -> Self::Output {
+        let mut steps = match self {
+            Pattern::Meta(MetaPattern::Traverse(traverse))
+                if traverse.order() == TraversalOrder::DepthFirst =>
+            {
+                traverse.patterns()
+            }
+            other => vec![other],
+        };
+        steps.push(rhs);
+        Pattern::traverse(steps)
+    }
+}
+
+/// `a & b` builds a conjunction of `a` and `b`. Chaining flattens, so
+/// `a & b & c` is the same pattern as `Pattern::and(vec![a, b, c])`.
+impl std::ops::BitAnd for Pattern {
+    type Output = Pattern;
+
+    fn bitand(self, rhs: Pattern) -> Self::Output {
+        let mut patterns = match self {
+            Pattern::Meta(MetaPattern::And(and)) => and.patterns().to_vec(),
+            other => vec![other],
+        };
+        patterns.push(rhs);
+        Pattern::and(patterns)
+    }
+}
+
+/// `a | b` builds an alternation of `a` and `b`. Chaining flattens, so
+/// `a | b | c` is the same pattern as `Pattern::or(vec![a, b, c])`.
+impl std::ops::BitOr for Pattern {
+    type Output = Pattern;
+
+    fn bitor(self, rhs: Pattern) -> Self::Output {
+        let mut patterns = match self {
+            Pattern::Meta(MetaPattern::Or(or)) => or.patterns().to_vec(),
+            other => vec![other],
+        };
+        patterns.push(rhs);
+        Pattern::or(patterns)
+    }
+}
+
+/// `!a` builds the negation of `a`.
+impl std::ops::Not for Pattern {
+    type Output = Pattern;
+
+    fn not(self) -> Self::Output { Pattern::not_matching(self) }
+}
+
+/// The most compiled programs kept per thread. The cache is cleared when it
+/// fills up, so a long-running process that builds many distinct patterns
+/// recompiles them instead of growing without bound.
+const PROGRAM_CACHE_CAPACITY: usize = 1024;
+
+// Keyed by the pattern itself rather than a hash of it, so that two different
+// patterns can never share a compiled program.
+thread_local! {
+    static PROG: RefCell<HashMap<Pattern, vm::Program>> =
+        RefCell::new(HashMap::new());
+}
+
 impl Pattern {
     /// Internal helper that runs the pattern through the VM and returns the
     /// matching paths.
@@ -608,35 +1752,21 @@ impl Pattern {
         &self,
         env: &Envelope,
     ) -> Vec<(Path, HashMap<String, Vec<Path>>)> {
-        thread_local! {
-            static PROG: RefCell<HashMap<u64, vm::Program>> = RefCell::new(HashMap::new());
+        #[cfg(feature = "precompile")]
+        if let Some(prog) = precompile::precompiled_program(self) {
+            return vm::run(&prog, env);
         }
 
-        // cheap structural hash
-        use std::{
-            collections::hash_map::DefaultHasher,
-            hash::{Hash, Hasher},
-        };
-        let mut h = DefaultHasher::new();
-        self.hash(&mut h);
-        let key = h.finish();
-
         let prog = PROG
-            .with(|cell| cell.borrow().get(&key).cloned())
+            .with(|cell| cell.borrow().get(self).cloned())
             .unwrap_or_else(|| {
-                let mut p = vm::Program {
-                    code: Vec::new(),
-                    literals: Vec::new(),
-                    capture_names: Vec::new(),
-                };
-                self.compile(
-                    &mut p.code,
-                    &mut p.literals,
-                    &mut p.capture_names,
-                );
-                p.code.push(Instr::Accept);
+                let p = self.compile_program();
                 PROG.with(|cell| {
-                    cell.borrow_mut().insert(key, p.clone());
+                    let mut cache = cell.borrow_mut();
+                    if cache.len() >= PROGRAM_CACHE_CAPACITY {
+                        cache.clear();
+                    }
+                    cache.insert(self.clone(), p.clone());
                 });
                 p
             });
@@ -644,11 +1774,56 @@ impl Pattern {
         vm::run(&prog, env)
     }
 
+    /// Compiles this pattern into a complete VM program.
+    fn compile_program(&self) -> vm::Program {
+        let mut p = vm::Program {
+            code: Vec::new(),
+            literals: Vec::new(),
+            capture_names: Vec::new(),
+        };
+        self.compile(&mut p.code, &mut p.literals, &mut p.capture_names);
+        p.code.push(Instr::Accept);
+        p
+    }
+
     #[allow(dead_code)]
     fn vm_paths(&self, env: &Envelope) -> Vec<Path> {
         self.vm_run(env).into_iter().map(|(p, _)| p).collect()
     }
 
+    /// Rebuilds this pattern with each directly nested pattern replaced by
+    /// the result of `f`. Leaf patterns are returned unchanged.
+    pub(crate) fn map_child_patterns(
+        self,
+        f: &mut dyn FnMut(Pattern) -> Pattern,
+    ) -> Self {
+        match self {
+            Pattern::Leaf(leaf) => Pattern::Leaf(leaf),
+            Pattern::Structure(structure) => {
+                Pattern::Structure(structure.map_child_patterns(f))
+            }
+            Pattern::Meta(meta) => Pattern::Meta(meta.map_child_patterns(f)),
+        }
+    }
+
+    /// Returns the patterns nested directly inside this pattern.
+    pub(crate) fn child_patterns(&self) -> Vec<&Pattern> {
+        match self {
+            Pattern::Leaf(_) => Vec::new(),
+            Pattern::Structure(structure) => structure.child_patterns(),
+            Pattern::Meta(meta) => meta.child_patterns(),
+        }
+    }
+
+    /// Returns the branches of this pattern if it is a prioritized
+    /// alternation built by [`Pattern::or_with_priority`].
+    pub(crate) fn priority_branches(&self) -> Option<&[(u32, Pattern)]> {
+        match self {
+            Pattern::Meta(MetaPattern::PriorityOr(or)) => Some(or.branches()),
+            _ => None,
+        }
+    }
+
     pub(crate) fn collect_capture_names(&self, out: &mut Vec<String>) {
         if let Pattern::Meta(meta) = self {
             meta.collect_capture_names(out)
@@ -677,4 +1852,27 @@ impl Pattern {
     pub fn unwrap() -> Self {
         Pattern::Structure(StructurePattern::Wrapped(WrappedPattern::unwrap()))
     }
+
+    /// Creates a new `Pattern` that matches a wrapped envelope whose content
+    /// matches the given pattern. Unlike `unwrap_matching`, the matched path
+    /// ends at the wrapped envelope rather than its content.
+    pub fn wrapped_content_matching(pattern: Pattern) -> Self {
+        Pattern::Structure(StructurePattern::Wrapped(
+            WrappedPattern::content_matching(pattern),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_program_cache_is_bounded() {
+        for i in 0..(PROGRAM_CACHE_CAPACITY as i32 * 3) {
+            assert!(Pattern::number(i).matches(&Envelope::new(i)));
+            let len = PROG.with(|cell| cell.borrow().len());
+            assert!(len <= PROGRAM_CACHE_CAPACITY);
+        }
+    }
 }
@@ -0,0 +1,48 @@
+//! A process-wide registry of compiled programs.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock, RwLock},
+};
+
+use super::{Pattern, vm::Program};
+
+/// Programs compiled by [`Pattern::with_precompiled`], keyed by pattern.
+fn registry() -> &'static RwLock<HashMap<Pattern, Arc<Program>>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<Pattern, Arc<Program>>>> =
+        OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+impl Pattern {
+    /// Compiles this pattern now and shares the program with every thread,
+    /// returning the pattern unchanged.
+    ///
+    /// Compiled programs are otherwise cached per thread, so a pattern
+    /// cloned to a new thread is compiled again on its first use there.
+    /// After this call, any equal pattern, including every clone, uses the
+    /// shared program on all threads.
+    ///
+    /// Precompiled programs are kept for the life of the process, so this is
+    /// meant for a bounded set of long-lived patterns.
+    pub fn with_precompiled(self) -> Pattern {
+        if precompiled_program(&self).is_none() {
+            let program = Arc::new(self.compile_program());
+            registry()
+                .write()
+                .unwrap_or_else(|e| e.into_inner())
+                .entry(self.clone())
+                .or_insert(program);
+        }
+        self
+    }
+}
+
+/// Returns the shared program for `pattern`, if it has been precompiled.
+pub(super) fn precompiled_program(pattern: &Pattern) -> Option<Arc<Program>> {
+    registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(pattern)
+        .cloned()
+}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use bc_envelope::prelude::*;
+
+/// A predicate applied to the predicate and object of each assertion tested
+/// by an `AssertionPredicatePattern`.
+pub type AssertionPredicate =
+    Arc<dyn Fn(&Envelope, &Envelope) -> bool + Send + Sync>;
+
+/// Matches assertions whose predicate and object are accepted by a
+/// caller-supplied function.
+///
+/// The function cannot be written in pattern syntax, so these patterns
+/// display as `assert(custom)` but cannot be parsed. Two of them are equal
+/// only if they share the same function instance.
+///
+/// Compiled programs are cached by pattern equality, so identical functions
+/// wrapped in separate `Arc`s each get their own cache entry. Clone one
+/// `AssertionPredicate` rather than recreating it for every match.
+#[derive(Clone)]
+pub struct AssertionPredicatePattern {
+    predicate: AssertionPredicate,
+}
+
+impl AssertionPredicatePattern {
+    /// Creates a new `AssertionPredicatePattern` accepting the assertions
+    /// for which `predicate` returns true.
+    pub fn new(predicate: AssertionPredicate) -> Self {
+        AssertionPredicatePattern { predicate }
+    }
+
+    /// Returns true if `assertion` is an assertion whose predicate and
+    /// object are accepted by the function.
+    pub fn accepts(&self, assertion: &Envelope) -> bool {
+        match (assertion.as_predicate(), assertion.as_object()) {
+            (Some(predicate), Some(object)) => {
+                (self.predicate)(&predicate, &object)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Debug for AssertionPredicatePattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AssertionPredicatePattern").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for AssertionPredicatePattern {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.predicate, &other.predicate)
+    }
+}
+
+impl Eq for AssertionPredicatePattern {}
+
+impl std::hash::Hash for AssertionPredicatePattern {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        (Arc::as_ptr(&self.predicate) as *const () as usize).hash(state);
+    }
+}
+
+impl std::fmt::Display for AssertionPredicatePattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "assert(custom)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assertion_predicate_pattern() {
+        let pattern = AssertionPredicatePattern::new(Arc::new(|_, object| {
+            object.extract_subject::<i32>().is_ok_and(|n| n > 18)
+        }));
+        assert_eq!(pattern.to_string(), "assert(custom)");
+        assert!(pattern.accepts(&Envelope::new_assertion("age", 30)));
+        assert!(!pattern.accepts(&Envelope::new_assertion("age", 12)));
+        assert!(!pattern.accepts(&Envelope::new("age")));
+        assert_eq!(pattern, pattern.clone());
+    }
+}
@@ -2,6 +2,8 @@ use std::collections::HashMap;
 
 use bc_envelope::prelude::*;
 
+#[cfg(feature = "custom-predicates")]
+use crate::pattern::structure::AssertionPredicatePattern;
 use crate::pattern::{
     Matcher, Path, Pattern, structure::StructurePattern, vm::Instr,
 };
@@ -14,6 +16,15 @@ pub enum AssertionsPattern {
     WithPredicate(Box<Pattern>),
     /// Matches assertions with objects that match a specific pattern.
     WithObject(Box<Pattern>),
+    /// Matches assertions whose predicate and object both match the
+    /// respective patterns.
+    WithBoth(Box<Pattern>, Box<Pattern>),
+    /// Matches the assertion at index `n`, in canonical (digest) order, if
+    /// it matches `inner`.
+    AtIndex { n: usize, inner: Box<Pattern> },
+    /// Matches assertions accepted by a caller-supplied function.
+    #[cfg(feature = "custom-predicates")]
+    Where(AssertionPredicatePattern),
 }
 
 impl AssertionsPattern {
@@ -31,6 +42,18 @@ impl AssertionsPattern {
     pub fn with_object(pattern: Pattern) -> Self {
         AssertionsPattern::WithObject(Box::new(pattern))
     }
+
+    /// Creates a new `AssertionsPattern` that matches assertions whose
+    /// predicate matches `predicate` and whose object matches `object`.
+    pub fn with_both(predicate: Pattern, object: Pattern) -> Self {
+        AssertionsPattern::WithBoth(Box::new(predicate), Box::new(object))
+    }
+
+    /// Creates a new `AssertionsPattern` that matches the assertion at index
+    /// `n`, in canonical order, if it matches `pattern`.
+    pub fn at_index(n: usize, pattern: Pattern) -> Self {
+        AssertionsPattern::AtIndex { n, inner: Box::new(pattern) }
+    }
 }
 
 impl Matcher for AssertionsPattern {
@@ -38,8 +61,13 @@ impl Matcher for AssertionsPattern {
         &self,
         haystack: &Envelope,
     ) -> (Vec<Path>, HashMap<String, Vec<Path>>) {
+        let mut assertions = haystack.assertions();
+        if let AssertionsPattern::AtIndex { n, .. } = self {
+            assertions.sort_by_key(|assertion| assertion.digest());
+            assertions = assertions.into_iter().skip(*n).take(1).collect();
+        }
         let mut paths = Vec::new();
-        for assertion in haystack.assertions() {
+        for assertion in assertions {
             match self {
                 AssertionsPattern::Any => {
                     paths.push(vec![assertion.clone()]);
@@ -58,6 +86,26 @@ impl Matcher for AssertionsPattern {
                         paths.push(vec![assertion.clone()]);
                     }
                 }
+                AssertionsPattern::WithBoth(pred_pattern, obj_pattern) => {
+                    if let (Some(predicate), Some(object)) =
+                        (assertion.as_predicate(), assertion.as_object())
+                        && pred_pattern.matches(&predicate)
+                        && obj_pattern.matches(&object)
+                    {
+                        paths.push(vec![assertion.clone()]);
+                    }
+                }
+                AssertionsPattern::AtIndex { inner, .. } => {
+                    if inner.matches(&assertion) {
+                        paths.push(vec![assertion.clone()]);
+                    }
+                }
+                #[cfg(feature = "custom-predicates")]
+                AssertionsPattern::Where(predicate) => {
+                    if predicate.accepts(&assertion) {
+                        paths.push(vec![assertion.clone()]);
+                    }
+                }
             }
         }
         (paths, HashMap::new())
@@ -87,6 +135,18 @@ impl std::fmt::Display for AssertionsPattern {
             AssertionsPattern::WithObject(pattern) => {
                 write!(f, "assertobj({})", pattern)
             }
+            AssertionsPattern::WithBoth(pred_pattern, obj_pattern) => {
+                write!(
+                    f,
+                    "assert(pred: {}, obj: {})",
+                    pred_pattern, obj_pattern
+                )
+            }
+            AssertionsPattern::AtIndex { n, inner } => {
+                write!(f, "assert[{}]({})", n, inner)
+            }
+            #[cfg(feature = "custom-predicates")]
+            AssertionsPattern::Where(predicate) => write!(f, "{}", predicate),
         }
     }
 }
@@ -1,5 +1,7 @@
 // Structure patterns - patterns dealing with envelope structure
 
+#[cfg(feature = "custom-predicates")]
+mod assertion_predicate_pattern;
 mod assertions_pattern;
 mod digest_pattern;
 mod leaf_structure_pattern;
@@ -8,10 +10,13 @@ mod object_pattern;
 mod obscured_pattern;
 mod predicate_pattern;
 mod subject_pattern;
+mod tagged_content_pattern;
 mod wrapped_pattern;
 
 use std::collections::HashMap;
 
+#[cfg(feature = "custom-predicates")]
+pub(crate) use assertion_predicate_pattern::AssertionPredicatePattern;
 pub(crate) use assertions_pattern::AssertionsPattern;
 use bc_envelope::prelude::*;
 pub(crate) use digest_pattern::DigestPattern;
@@ -21,6 +26,7 @@ pub(crate) use object_pattern::ObjectPattern;
 pub(crate) use obscured_pattern::ObscuredPattern;
 pub(crate) use predicate_pattern::PredicatePattern;
 pub(crate) use subject_pattern::SubjectPattern;
+pub(crate) use tagged_content_pattern::{TaggedContentPattern, tagged_content};
 pub(crate) use wrapped_pattern::WrappedPattern;
 
 use crate::pattern::{Matcher, Path, Pattern, vm::Instr};
@@ -44,6 +50,8 @@ pub enum StructurePattern {
     Predicate(PredicatePattern),
     /// Matches subjects.
     Subject(SubjectPattern),
+    /// Matches the content of tagged values.
+    TaggedContent(TaggedContentPattern),
     /// Matches wrapped envelopes.
     Wrapped(WrappedPattern),
 }
@@ -78,6 +86,9 @@ impl Matcher for StructurePattern {
             StructurePattern::Subject(pattern) => {
                 pattern.paths_with_captures(haystack)
             }
+            StructurePattern::TaggedContent(pattern) => {
+                pattern.paths_with_captures(haystack)
+            }
             StructurePattern::Wrapped(pattern) => {
                 pattern.paths_with_captures(haystack)
             }
@@ -100,6 +111,9 @@ impl Matcher for StructurePattern {
             StructurePattern::Node(s) => s.compile(code, lits, captures),
             StructurePattern::Obscured(s) => s.compile(code, lits, captures),
             StructurePattern::Predicate(s) => s.compile(code, lits, captures),
+            StructurePattern::TaggedContent(s) => {
+                s.compile(code, lits, captures)
+            }
         }
     }
 
@@ -113,6 +127,7 @@ impl Matcher for StructurePattern {
             StructurePattern::Obscured(pattern) => pattern.is_complex(),
             StructurePattern::Predicate(pattern) => pattern.is_complex(),
             StructurePattern::Subject(pattern) => pattern.is_complex(),
+            StructurePattern::TaggedContent(pattern) => pattern.is_complex(),
             StructurePattern::Wrapped(pattern) => pattern.is_complex(),
         }
     }
@@ -129,7 +144,127 @@ impl std::fmt::Display for StructurePattern {
             StructurePattern::Obscured(pattern) => write!(f, "{}", pattern),
             StructurePattern::Predicate(pattern) => write!(f, "{}", pattern),
             StructurePattern::Subject(pattern) => write!(f, "{}", pattern),
+            StructurePattern::TaggedContent(pattern) => {
+                write!(f, "{}", pattern)
+            }
             StructurePattern::Wrapped(pattern) => write!(f, "{}", pattern),
         }
     }
 }
+
+impl StructurePattern {
+    /// Returns the patterns nested directly inside this structure pattern.
+    pub(crate) fn child_patterns(&self) -> Vec<&Pattern> {
+        match self {
+            StructurePattern::Assertions(
+                AssertionsPattern::WithPredicate(p)
+                | AssertionsPattern::WithObject(p)
+                | AssertionsPattern::AtIndex { inner: p, .. },
+            )
+            | StructurePattern::Node(
+                NodePattern::AllAssertions(p)
+                | NodePattern::PredicateCount { pred: p, .. },
+            )
+            | StructurePattern::Object(ObjectPattern::Pattern(p))
+            | StructurePattern::Predicate(PredicatePattern::Pattern(p))
+            | StructurePattern::Subject(SubjectPattern::Pattern(p))
+            | StructurePattern::Wrapped(
+                WrappedPattern::Unwrap(p) | WrappedPattern::ContentMatching(p),
+            ) => vec![p.as_ref()],
+            StructurePattern::TaggedContent(pattern) => vec![pattern.pattern()],
+            StructurePattern::Assertions(AssertionsPattern::WithBoth(
+                pred,
+                obj,
+            )) => vec![pred.as_ref(), obj.as_ref()],
+            StructurePattern::Node(
+                NodePattern::AllPredsIn(patterns)
+                | NodePattern::Exact(patterns),
+            ) => patterns.iter().collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl StructurePattern {
+    /// Rebuilds this structure pattern with each directly nested pattern
+    /// replaced by the result of `f`.
+    pub(crate) fn map_child_patterns(
+        self,
+        f: &mut dyn FnMut(Pattern) -> Pattern,
+    ) -> Self {
+        let mut map = |p: Box<Pattern>| Box::new(f(*p));
+        match self {
+            StructurePattern::Assertions(AssertionsPattern::WithPredicate(
+                p,
+            )) => StructurePattern::Assertions(
+                AssertionsPattern::WithPredicate(map(p)),
+            ),
+            StructurePattern::Assertions(AssertionsPattern::WithObject(p)) => {
+                StructurePattern::Assertions(AssertionsPattern::WithObject(
+                    map(p),
+                ))
+            }
+            StructurePattern::Assertions(AssertionsPattern::WithBoth(
+                pred,
+                obj,
+            )) => {
+                let pred = map(pred);
+                StructurePattern::Assertions(AssertionsPattern::WithBoth(
+                    pred,
+                    map(obj),
+                ))
+            }
+            StructurePattern::Assertions(AssertionsPattern::AtIndex {
+                n,
+                inner,
+            }) => StructurePattern::Assertions(AssertionsPattern::AtIndex {
+                n,
+                inner: map(inner),
+            }),
+            StructurePattern::Node(NodePattern::AllAssertions(p)) => {
+                StructurePattern::Node(NodePattern::AllAssertions(map(p)))
+            }
+            StructurePattern::Node(NodePattern::AllPredsIn(predicates)) => {
+                StructurePattern::Node(NodePattern::AllPredsIn(
+                    predicates.into_iter().map(&mut *f).collect(),
+                ))
+            }
+            StructurePattern::Node(NodePattern::PredicateCount {
+                pred,
+                interval,
+            }) => StructurePattern::Node(NodePattern::PredicateCount {
+                pred: map(pred),
+                interval,
+            }),
+            StructurePattern::Node(NodePattern::Exact(patterns)) => {
+                StructurePattern::Node(NodePattern::Exact(
+                    patterns.into_iter().map(&mut *f).collect(),
+                ))
+            }
+            StructurePattern::Object(ObjectPattern::Pattern(p)) => {
+                StructurePattern::Object(ObjectPattern::Pattern(map(p)))
+            }
+            StructurePattern::Predicate(PredicatePattern::Pattern(p)) => {
+                StructurePattern::Predicate(PredicatePattern::Pattern(map(p)))
+            }
+            StructurePattern::Subject(SubjectPattern::Pattern(p)) => {
+                StructurePattern::Subject(SubjectPattern::Pattern(map(p)))
+            }
+            StructurePattern::Wrapped(WrappedPattern::Unwrap(p)) => {
+                StructurePattern::Wrapped(WrappedPattern::Unwrap(map(p)))
+            }
+            StructurePattern::Wrapped(WrappedPattern::ContentMatching(p)) => {
+                StructurePattern::Wrapped(WrappedPattern::ContentMatching(map(
+                    p,
+                )))
+            }
+            StructurePattern::TaggedContent(pattern) => {
+                StructurePattern::TaggedContent(TaggedContentPattern::new(
+                    pattern.tag(),
+                    f(pattern.pattern().clone()),
+                ))
+            }
+            other => other,
+        }
+    }
+}
@@ -17,6 +17,17 @@ pub enum NodePattern {
     Any,
     /// Matches a node with the specified count of assertions.
     AssertionsInterval(Interval),
+    /// Matches a node whose every assertion matches the pattern.
+    AllAssertions(Box<Pattern>),
+    /// Matches a node whose every assertion has a predicate matching at
+    /// least one of the patterns.
+    AllPredsIn(Vec<Pattern>),
+    /// Matches a node with a number of assertions whose predicate matches
+    /// the pattern that falls within the interval.
+    PredicateCount { pred: Box<Pattern>, interval: Interval },
+    /// Matches a node whose assertions can be paired one-to-one with the
+    /// patterns, each assertion matching its pattern.
+    Exact(Vec<Pattern>),
 }
 
 impl NodePattern {
@@ -28,6 +39,72 @@ impl NodePattern {
     pub fn interval(interval: impl RangeBounds<usize>) -> Self {
         NodePattern::AssertionsInterval(Interval::new(interval))
     }
+
+    /// Creates a new `NodePattern` that matches a node only if every one of
+    /// its assertions matches the given pattern.
+    pub fn all_assertions(pattern: Pattern) -> Self {
+        NodePattern::AllAssertions(Box::new(pattern))
+    }
+
+    /// Creates a new `NodePattern` that matches a node only if the predicate
+    /// of every one of its assertions matches one of the given patterns.
+    pub fn all_preds_in(predicates: Vec<Pattern>) -> Self {
+        NodePattern::AllPredsIn(predicates)
+    }
+
+    /// Creates a new `NodePattern` that matches a node whose number of
+    /// assertions with a predicate matching `pred` is within `interval`.
+    pub fn predicate_count(
+        pred: Pattern,
+        interval: impl RangeBounds<usize>,
+    ) -> Self {
+        NodePattern::PredicateCount {
+            pred: Box::new(pred),
+            interval: Interval::new(interval),
+        }
+    }
+
+    /// Creates a new `NodePattern` that matches a node with exactly one
+    /// assertion for each of the given patterns, and no others.
+    pub fn exact(patterns: Vec<Pattern>) -> Self {
+        NodePattern::Exact(patterns)
+    }
+}
+
+/// Returns `true` if every pattern can be paired with a different assertion
+/// that it matches, using augmenting paths over the bipartite graph of
+/// pattern-assertion matches.
+fn has_perfect_matching(patterns: &[Pattern], assertions: &[Envelope]) -> bool {
+    let edges: Vec<Vec<bool>> = patterns
+        .iter()
+        .map(|p| assertions.iter().map(|a| p.matches(a)).collect())
+        .collect();
+
+    fn augment(
+        edges: &[Vec<bool>],
+        pattern: usize,
+        visited: &mut [bool],
+        owner: &mut [Option<usize>],
+    ) -> bool {
+        for assertion in 0..owner.len() {
+            if edges[pattern][assertion] && !visited[assertion] {
+                visited[assertion] = true;
+                if owner[assertion].is_none_or(|other| {
+                    augment(edges, other, visited, owner)
+                }) {
+                    owner[assertion] = Some(pattern);
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    let mut owner = vec![None; assertions.len()];
+    (0..patterns.len()).all(|pattern| {
+        let mut visited = vec![false; assertions.len()];
+        augment(&edges, pattern, &mut visited, &mut owner)
+    })
 }
 
 impl Matcher for NodePattern {
@@ -45,6 +122,33 @@ impl Matcher for NodePattern {
                 NodePattern::AssertionsInterval(range) => {
                     range.contains(haystack.assertions().len())
                 }
+                // Vacuously true for a node without assertions, although
+                // Envelope never produces such a node.
+                NodePattern::AllAssertions(pattern) => haystack
+                    .assertions()
+                    .iter()
+                    .all(|assertion| pattern.matches(assertion)),
+                NodePattern::AllPredsIn(predicates) => {
+                    haystack.assertions().iter().all(|assertion| {
+                        assertion.as_predicate().is_some_and(|predicate| {
+                            predicates.iter().any(|p| p.matches(&predicate))
+                        })
+                    })
+                }
+                NodePattern::PredicateCount { pred, interval } => {
+                    let count = haystack
+                        .assertions()
+                        .iter()
+                        .filter_map(|assertion| assertion.as_predicate())
+                        .filter(|predicate| pred.matches(predicate))
+                        .count();
+                    interval.contains(count)
+                }
+                NodePattern::Exact(patterns) => {
+                    let assertions = haystack.assertions();
+                    assertions.len() == patterns.len()
+                        && has_perfect_matching(patterns, &assertions)
+                }
             };
 
             if is_hit {
@@ -78,6 +182,22 @@ impl std::fmt::Display for NodePattern {
             NodePattern::AssertionsInterval(range) => {
                 write!(f, "node({})", range)
             }
+            NodePattern::AllAssertions(pattern) => {
+                write!(f, "node(all: {})", pattern)
+            }
+            NodePattern::AllPredsIn(predicates) => {
+                let predicates: Vec<_> =
+                    predicates.iter().map(|p| p.to_string()).collect();
+                write!(f, "node(preds_in: [{}])", predicates.join(", "))
+            }
+            NodePattern::PredicateCount { pred, interval } => {
+                write!(f, "node(count({}): {})", pred, interval)
+            }
+            NodePattern::Exact(patterns) => {
+                let patterns: Vec<_> =
+                    patterns.iter().map(|p| p.to_string()).collect();
+                write!(f, "node(exactly: [{}])", patterns.join(", "))
+            }
         }
     }
 }
@@ -93,5 +213,30 @@ mod tests {
 
         let count_pattern = NodePattern::interval(1..=3);
         assert_eq!(count_pattern.to_string(), "node({1,3})");
+
+        let all_pattern = NodePattern::all_assertions(Pattern::any_assertion());
+        assert_eq!(all_pattern.to_string(), "node(all: assert)");
+
+        let preds_pattern = NodePattern::all_preds_in(vec![
+            Pattern::text("age"),
+            Pattern::text("name"),
+        ]);
+        assert_eq!(
+            preds_pattern.to_string(),
+            r#"node(preds_in: ["age", "name"])"#
+        );
+
+        let count_pattern =
+            NodePattern::predicate_count(Pattern::any_text(), 2..=5);
+        assert_eq!(count_pattern.to_string(), "node(count(text): {2,5})");
+
+        let exact_pattern = NodePattern::exact(vec![
+            Pattern::predicate(Pattern::text("age")),
+            Pattern::object(Pattern::any_number()),
+        ]);
+        assert_eq!(
+            exact_pattern.to_string(),
+            r#"node(exactly: [pred("age"), obj(number)])"#
+        );
     }
 }
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use bc_envelope::prelude::*;
+
+use crate::{
+    Pattern,
+    pattern::{
+        Matcher, Path,
+        vm::{Axis, Instr},
+    },
+};
+
+/// Matches a leaf holding a value with a specific tag and descends into the
+/// tagged content, matching it with an envelope pattern.
+///
+/// Content that encodes an envelope is matched as that envelope; any other
+/// content is matched as a leaf.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct TaggedContentPattern {
+    tag: u64,
+    pattern: Box<Pattern>,
+}
+
+impl TaggedContentPattern {
+    /// Creates a new `TaggedContentPattern` that matches values tagged with
+    /// `tag` whose content matches `pattern`.
+    pub fn new(tag: u64, pattern: Pattern) -> Self {
+        TaggedContentPattern { tag, pattern: Box::new(pattern) }
+    }
+
+    /// Returns the tag value.
+    pub fn tag(&self) -> u64 { self.tag }
+
+    /// Returns the pattern matched against the tagged content.
+    pub fn pattern(&self) -> &Pattern { &self.pattern }
+}
+
+/// Returns the content of `envelope` as an envelope if its subject is a leaf
+/// holding a value tagged with `tag`.
+pub(crate) fn tagged_content(
+    envelope: &Envelope,
+    tag: u64,
+) -> Option<Envelope> {
+    let (cbor_tag, content) =
+        envelope.subject().as_leaf()?.try_into_tagged_value().ok()?;
+    if cbor_tag.value() != tag {
+        return None;
+    }
+    Some(
+        Envelope::try_from(content.clone())
+            .unwrap_or_else(|_| Envelope::new(content)),
+    )
+}
+
+impl Matcher for TaggedContentPattern {
+    fn paths_with_captures(
+        &self,
+        haystack: &Envelope,
+    ) -> (Vec<Path>, HashMap<String, Vec<Path>>) {
+        let Some(content) = tagged_content(haystack, self.tag) else {
+            return (vec![], HashMap::new());
+        };
+        let (paths, captures) = self.pattern.paths_with_captures(&content);
+        let paths = paths
+            .into_iter()
+            .map(|mut path| {
+                path.insert(0, haystack.clone());
+                path
+            })
+            .collect();
+        (paths, captures)
+    }
+
+    fn compile(
+        &self,
+        code: &mut Vec<Instr>,
+        lits: &mut Vec<Pattern>,
+        captures: &mut Vec<String>,
+    ) {
+        code.push(Instr::PushAxis(Axis::TaggedContent(self.tag)));
+        self.pattern.compile(code, lits, captures);
+    }
+}
+
+impl std::fmt::Display for TaggedContentPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "untag({}, {})", self.tag, self.pattern)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tagged_content_pattern_display() {
+        let pattern = TaggedContentPattern::new(100, Pattern::text("Alice"));
+        assert_eq!(pattern.to_string(), r#"untag(100, "Alice")"#);
+    }
+}
@@ -18,6 +18,9 @@ pub enum WrappedPattern {
     Any,
     /// Matches a wrapped envelope and also matches on its unwrapped content.
     Unwrap(Box<Pattern>),
+    /// Matches a wrapped envelope whose unwrapped content matches the
+    /// pattern, without descending into it.
+    ContentMatching(Box<Pattern>),
 }
 
 impl WrappedPattern {
@@ -34,6 +37,13 @@ impl WrappedPattern {
     /// Creates a new `WrappedPattern` that matches any wrapped envelope and
     /// descends into it.
     pub fn unwrap() -> Self { Self::unwrap_matching(Pattern::any()) }
+
+    /// Creates a new `WrappedPattern` that matches a wrapped envelope whose
+    /// unwrapped content matches the pattern, returning the wrapped envelope
+    /// itself as the match.
+    pub fn content_matching(pattern: Pattern) -> Self {
+        WrappedPattern::ContentMatching(Box::new(pattern))
+    }
 }
 
 impl Default for WrappedPattern {
@@ -69,6 +79,16 @@ impl Matcher for WrappedPattern {
                             vec![]
                         }
                     }
+                    WrappedPattern::ContentMatching(pattern) => {
+                        // Constrain the content but keep the wrapped envelope
+                        // as the match
+                        match subject.try_unwrap() {
+                            Ok(unwrapped) if pattern.matches(&unwrapped) => {
+                                vec![vec![haystack.clone()]]
+                            }
+                            _ => vec![],
+                        }
+                    }
                 }
             } else {
                 vec![]
@@ -84,7 +104,7 @@ impl Matcher for WrappedPattern {
         captures: &mut Vec<String>,
     ) {
         match self {
-            WrappedPattern::Any => {
+            WrappedPattern::Any | WrappedPattern::ContentMatching(_) => {
                 // Just match the wrapped envelope itself, don't descend
                 let idx = lits.len();
                 lits.push(Pattern::Structure(StructurePattern::Wrapped(
@@ -121,6 +141,9 @@ impl std::fmt::Display for WrappedPattern {
                     write!(f, "unwrap({})", pattern)
                 }
             }
+            WrappedPattern::ContentMatching(pattern) => {
+                write!(f, "wrapped(match: {})", pattern)
+            }
         }
     }
 }
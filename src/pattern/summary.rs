@@ -0,0 +1,386 @@
+//! One-line English summaries of patterns.
+
+use dcbor_pattern::{
+    NumberPattern as DCBORNumberPattern, TextPattern as DCBORTextPattern,
+};
+
+use super::{
+    Pattern,
+    leaf::{
+        CBORPattern, IntegerPattern, LeafPattern, NumberPattern, TextPattern,
+    },
+    meta::{MetaPattern, TraversalOrder},
+    structure::{
+        AssertionsPattern, DigestPattern, NodePattern, ObjectPattern,
+        ObscuredPattern, PredicatePattern, StructurePattern, SubjectPattern,
+        WrappedPattern,
+    },
+};
+
+impl Pattern {
+    /// Returns a concise, single-line English description of what this
+    /// pattern matches, such as `a number between 1 and 100` or
+    /// `any assertion`.
+    ///
+    /// Unlike [`Display`](std::fmt::Display), which renders the pattern in
+    /// the pattern expression syntax, the summary is meant for log and
+    /// diagnostic messages. Its wording is not stable and should not be
+    /// parsed.
+    pub fn node_summary(&self) -> String {
+        match self {
+            Pattern::Leaf(leaf) => leaf_summary(leaf),
+            Pattern::Structure(structure) => structure_summary(structure),
+            Pattern::Meta(meta) => meta_summary(meta),
+        }
+    }
+}
+
+fn leaf_summary(leaf: &LeafPattern) -> String {
+    match leaf {
+        LeafPattern::Cbor(CBORPattern::Any) => "any CBOR value".to_string(),
+        LeafPattern::Cbor(CBORPattern::Value(cbor)) => {
+            format!("the CBOR value {}", cbor.diagnostic_flat())
+        }
+        LeafPattern::Cbor(CBORPattern::Pattern(pattern)) => {
+            format!("CBOR matching {}", pattern)
+        }
+        LeafPattern::Cbor(CBORPattern::BytesRegex(regex)) => {
+            format!("CBOR whose encoding matches /{}/", regex)
+        }
+        LeafPattern::Number(number) => number_summary(number),
+        LeafPattern::Integer(integer) => integer_summary(integer),
+        LeafPattern::Text(text) => text_summary(text),
+        LeafPattern::ByteString(_) => kind_summary(leaf, "bstr", "byte string"),
+        LeafPattern::Tag(_) => kind_summary(leaf, "tagged", "tagged value"),
+        LeafPattern::Array(_) => kind_summary(leaf, "array", "array"),
+        LeafPattern::Map(_) => kind_summary(leaf, "map", "map"),
+        LeafPattern::Bool(_) => match leaf.to_string().as_str() {
+            "bool" => "any boolean".to_string(),
+            value => format!("the boolean {}", value),
+        },
+        LeafPattern::Null(_) => "null".to_string(),
+        LeafPattern::Date(_) => kind_summary(leaf, "date", "date"),
+        LeafPattern::KnownValue(_) => {
+            let text = leaf.to_string();
+            if text == "known" {
+                "any known value".to_string()
+            } else if text.starts_with("'/") {
+                format!("a known value matching {}", text)
+            } else {
+                format!("the known value {}", text)
+            }
+        }
+    }
+}
+
+/// Summarizes a leaf pattern whose `Display` is `keyword` when it matches
+/// any value of its kind.
+fn kind_summary(leaf: &LeafPattern, keyword: &str, kind: &str) -> String {
+    let text = leaf.to_string();
+    if text == keyword {
+        format!("any {}", kind)
+    } else {
+        format!("{} {} matching {}", article(kind), kind, text)
+    }
+}
+
+fn article(noun: &str) -> &'static str {
+    if noun.starts_with(['a', 'e', 'i', 'o', 'u']) {
+        "an"
+    } else {
+        "a"
+    }
+}
+
+fn number_summary(number: &NumberPattern) -> String {
+    match number {
+        NumberPattern::Dcbor(number) => match number {
+            DCBORNumberPattern::Any => "any number".to_string(),
+            DCBORNumberPattern::Value(value) => format!("the number {}", value),
+            DCBORNumberPattern::Range(range) => format!(
+                "a number between {} and {}",
+                range.start(),
+                range.end()
+            ),
+            DCBORNumberPattern::GreaterThan(value) => {
+                format!("a number greater than {}", value)
+            }
+            DCBORNumberPattern::GreaterThanOrEqual(value) => {
+                format!("a number of at least {}", value)
+            }
+            DCBORNumberPattern::LessThan(value) => {
+                format!("a number less than {}", value)
+            }
+            DCBORNumberPattern::LessThanOrEqual(value) => {
+                format!("a number of at most {}", value)
+            }
+            DCBORNumberPattern::NaN => "NaN".to_string(),
+            DCBORNumberPattern::Infinity => "infinity".to_string(),
+            DCBORNumberPattern::NegInfinity => "negative infinity".to_string(),
+        },
+        NumberPattern::Infinite => "an infinite number".to_string(),
+        NumberPattern::Finite => "a finite number".to_string(),
+        NumberPattern::DivisibleBy(divisor) => {
+            format!("a number divisible by {}", divisor)
+        }
+        NumberPattern::OneOf(values) => format!(
+            "one of the numbers {}",
+            values
+                .iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn integer_summary(integer: &IntegerPattern) -> String {
+    match integer {
+        IntegerPattern::Any => "any integer".to_string(),
+        IntegerPattern::Value(value) => format!("the integer {}", value),
+        IntegerPattern::Range(range) => format!(
+            "an integer between {} and {}",
+            range.start(),
+            range.end()
+        ),
+        IntegerPattern::GreaterThan(value) => {
+            format!("an integer greater than {}", value)
+        }
+        IntegerPattern::LessThan(value) => {
+            format!("an integer less than {}", value)
+        }
+    }
+}
+
+fn text_summary(text: &TextPattern) -> String {
+    match text {
+        TextPattern::Dcbor(text) => match text {
+            DCBORTextPattern::Any => "any text".to_string(),
+            DCBORTextPattern::Value(value) => format!("the text {:?}", value),
+            DCBORTextPattern::Regex(regex) => {
+                format!("text matching /{}/", regex)
+            }
+        },
+        TextPattern::OneOf(values) => format!(
+            "one of the texts {}",
+            values
+                .iter()
+                .map(|value| format!("{:?}", value))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        TextPattern::Glob { glob, .. } => {
+            format!("text matching the glob {:?}", glob)
+        }
+        TextPattern::StartsWith(prefix) => {
+            format!("text starting with {:?}", prefix)
+        }
+        TextPattern::EndsWith(suffix) => {
+            format!("text ending with {:?}", suffix)
+        }
+        TextPattern::Length(interval) => format!(
+            "text of {} characters",
+            count_summary(interval.min(), interval.max())
+        ),
+    }
+}
+
+fn structure_summary(structure: &StructurePattern) -> String {
+    match structure {
+        StructurePattern::Assertions(assertions) => match assertions {
+            AssertionsPattern::Any => "any assertion".to_string(),
+            AssertionsPattern::WithPredicate(predicate) => format!(
+                "an assertion whose predicate is {}",
+                predicate.node_summary()
+            ),
+            AssertionsPattern::WithObject(object) => format!(
+                "an assertion whose object is {}",
+                object.node_summary()
+            ),
+            AssertionsPattern::WithBoth(predicate, object) => format!(
+                "an assertion whose predicate is {} and whose object is {}",
+                predicate.node_summary(),
+                object.node_summary()
+            ),
+            AssertionsPattern::AtIndex { n, inner } => format!(
+                "assertion number {} that is {}",
+                n,
+                inner.node_summary()
+            ),
+            #[cfg(feature = "custom-predicates")]
+            AssertionsPattern::Where(_) => {
+                "an assertion accepted by a custom predicate".to_string()
+            }
+        },
+        StructurePattern::Digest(digest) => match digest {
+            DigestPattern::Digest(digest) => format!(
+                "the envelope with digest {}",
+                digest.short_description()
+            ),
+            DigestPattern::Prefix(prefix) => format!(
+                "an envelope whose digest starts with {}",
+                hex::encode(prefix)
+            ),
+            DigestPattern::BinaryRegex(regex) => {
+                format!("an envelope whose digest matches /{}/", regex)
+            }
+        },
+        StructurePattern::Leaf(_) => "any leaf".to_string(),
+        StructurePattern::Node(node) => match node {
+            NodePattern::Any => "any node".to_string(),
+            NodePattern::AssertionsInterval(interval) => format!(
+                "a node with {} assertions",
+                count_summary(interval.min(), interval.max())
+            ),
+            NodePattern::AllAssertions(pattern) => format!(
+                "a node whose every assertion is {}",
+                pattern.node_summary()
+            ),
+            NodePattern::AllPredsIn(patterns) => format!(
+                "a node whose every predicate is {}",
+                join_summaries(patterns, " or ")
+            ),
+            NodePattern::PredicateCount { pred, interval } => format!(
+                "a node with {} assertions whose predicate is {}",
+                count_summary(interval.min(), interval.max()),
+                pred.node_summary()
+            ),
+            NodePattern::Exact(patterns) => format!(
+                "a node whose assertions are exactly {}",
+                join_summaries(patterns, " and ")
+            ),
+        },
+        StructurePattern::Object(object) => match object {
+            ObjectPattern::Any => "any object".to_string(),
+            ObjectPattern::Pattern(pattern) => {
+                format!("an object that is {}", pattern.node_summary())
+            }
+        },
+        StructurePattern::Obscured(obscured) => match obscured {
+            ObscuredPattern::Any => "any obscured element".to_string(),
+            ObscuredPattern::Elided => "any elided element".to_string(),
+            ObscuredPattern::Encrypted => "any encrypted element".to_string(),
+            ObscuredPattern::Compressed => "any compressed element".to_string(),
+        },
+        StructurePattern::Predicate(predicate) => match predicate {
+            PredicatePattern::Any => "any predicate".to_string(),
+            PredicatePattern::Pattern(pattern) => {
+                format!("a predicate that is {}", pattern.node_summary())
+            }
+        },
+        StructurePattern::Subject(subject) => match subject {
+            SubjectPattern::Any => "any subject".to_string(),
+            SubjectPattern::Pattern(pattern) => {
+                format!("a subject that is {}", pattern.node_summary())
+            }
+        },
+        StructurePattern::TaggedContent(tagged) => format!(
+            "{} inside a value tagged {}",
+            tagged.pattern().node_summary(),
+            tagged.tag()
+        ),
+        StructurePattern::Wrapped(wrapped) => match wrapped {
+            WrappedPattern::Any => "any wrapped envelope".to_string(),
+            WrappedPattern::Unwrap(pattern) => {
+                format!("{} inside a wrapped envelope", pattern.node_summary())
+            }
+            WrappedPattern::ContentMatching(pattern) => format!(
+                "a wrapped envelope containing {}",
+                pattern.node_summary()
+            ),
+        },
+    }
+}
+
+fn meta_summary(meta: &MetaPattern) -> String {
+    match meta {
+        MetaPattern::Any(_) => "anything".to_string(),
+        MetaPattern::And(and) => join_summaries(and.patterns(), " and "),
+        MetaPattern::Or(or) => join_summaries(or.patterns(), " or "),
+        MetaPattern::PriorityOr(or) => or
+            .branches()
+            .iter()
+            .map(|(priority, p)| {
+                format!("{} (priority {})", p.node_summary(), priority)
+            })
+            .collect::<Vec<_>>()
+            .join(" or "),
+        MetaPattern::Not(not) => {
+            format!("anything except {}", not.pattern().node_summary())
+        }
+        MetaPattern::Search(search) => match search.limit() {
+            Some(limit) => format!(
+                "up to {} of {} anywhere",
+                limit,
+                search.pattern().node_summary()
+            ),
+            None => format!("{} anywhere", search.pattern().node_summary()),
+        },
+        MetaPattern::SearchCount(count) => format!(
+            "{} matches of {}",
+            count_summary(count.interval().min(), count.interval().max()),
+            count.pattern().node_summary()
+        ),
+        MetaPattern::Traverse(traverse) => {
+            let steps = join_summaries(&traverse.patterns(), ", then ");
+            match traverse.order() {
+                TraversalOrder::DepthFirst => steps,
+                TraversalOrder::BreadthFirst => {
+                    format!("{}, breadth-first", steps)
+                }
+            }
+        }
+        MetaPattern::TraverseBack(back) => format!(
+            "{} containing {}",
+            back.parent().node_summary(),
+            back.child().node_summary()
+        ),
+        MetaPattern::Group(group) => {
+            let quantifier = group.quantifier();
+            format!(
+                "{}, {} times",
+                group.pattern().node_summary(),
+                count_summary(quantifier.min(), quantifier.max())
+            )
+        }
+        MetaPattern::Capture(capture) => format!(
+            "{} (captured as @{})",
+            capture.pattern().node_summary(),
+            capture.name()
+        ),
+        MetaPattern::Dedup(dedup) => {
+            format!("{}, without duplicates", dedup.pattern().node_summary())
+        }
+        MetaPattern::Unique(unique) => format!(
+            "{}, once per envelope",
+            unique.pattern().node_summary()
+        ),
+        MetaPattern::PathFilter(filter) => {
+            format!("{}, filtered", filter.pattern().node_summary())
+        }
+        MetaPattern::While(w) => format!(
+            "{}, repeated while {}",
+            w.body().node_summary(),
+            w.condition().node_summary()
+        ),
+        MetaPattern::Explained(explained) => explained.pattern().node_summary(),
+    }
+}
+
+fn join_summaries(patterns: &[Pattern], separator: &str) -> String {
+    patterns
+        .iter()
+        .map(Pattern::node_summary)
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Describes a count bounded by `min` and an optional `max`.
+fn count_summary(min: usize, max: Option<usize>) -> String {
+    match max {
+        None if min == 0 => "any number of".to_string(),
+        Some(max) if max == min => format!("exactly {}", min),
+        Some(max) => format!("between {} and {}", min, max),
+        None => format!("at least {}", min),
+    }
+}
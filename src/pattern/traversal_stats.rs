@@ -0,0 +1,68 @@
+use crate::{
+    Quantifier,
+    pattern::{Pattern, meta::MetaPattern},
+};
+
+/// Static statistics about a pattern, computed without running it.
+///
+/// These give a rough idea of how much work the VM will do when the pattern
+/// is matched against an envelope:
+///
+/// - `literal_count` is the number of leaf and structure patterns. Each one is
+///   a predicate the VM tests against an envelope, so work grows linearly with
+///   it.
+/// - `max_depth` is the nesting depth of the pattern tree. Deeper patterns push
+///   more threads and axes onto the VM's stacks.
+/// - `has_search` means the pattern visits every node of the envelope tree, so
+///   its cost grows with the size of the envelope rather than the pattern.
+/// - `has_repeat` means the pattern contains a quantifier other than exactly
+///   once, which can cause the VM to backtrack.
+/// - `capture_count` is the number of capture patterns. Each capture records
+///   its paths, which costs memory but little time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TraversalStats {
+    /// The number of leaf and structure patterns.
+    pub literal_count: usize,
+    /// The maximum nesting depth of the pattern tree; a lone leaf is 1.
+    pub max_depth: usize,
+    /// Whether the pattern contains a `search`.
+    pub has_search: bool,
+    /// Whether the pattern contains a non-trivial repeat quantifier.
+    pub has_repeat: bool,
+    /// The number of capture patterns.
+    pub capture_count: usize,
+}
+
+impl Pattern {
+    /// Returns static statistics about this pattern, useful for estimating
+    /// how expensive it will be to match.
+    pub fn traversal_stats(&self) -> TraversalStats {
+        let mut stats = TraversalStats::default();
+        self.collect_traversal_stats(1, &mut stats);
+        stats
+    }
+
+    fn collect_traversal_stats(
+        &self,
+        depth: usize,
+        stats: &mut TraversalStats,
+    ) {
+        stats.max_depth = stats.max_depth.max(depth);
+        match self {
+            Pattern::Leaf(_) | Pattern::Structure(_) => {
+                stats.literal_count += 1
+            }
+            Pattern::Meta(MetaPattern::Search(_)) => stats.has_search = true,
+            Pattern::Meta(MetaPattern::Group(group)) => {
+                if *group.quantifier() != Quantifier::default() {
+                    stats.has_repeat = true;
+                }
+            }
+            Pattern::Meta(MetaPattern::Capture(_)) => stats.capture_count += 1,
+            Pattern::Meta(_) => {}
+        }
+        for child in self.child_patterns() {
+            child.collect_traversal_stats(depth + 1, stats);
+        }
+    }
+}
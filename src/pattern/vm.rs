@@ -2,20 +2,26 @@
 //!
 //! The VM runs byte-code produced by `Pattern::compile` (implemented later).
 
+use std::collections::{HashSet, VecDeque};
+
 use bc_components::DigestProvider;
 use bc_envelope::prelude::*;
 
-use super::{Matcher, Path, Pattern};
+use super::{Matcher, Path, Pattern, structure::tagged_content};
 use crate::{Quantifier, Reluctance};
 
+/// A direction the VM can step from an envelope to its children. New axes
+/// may be added, so matches on `Axis` need a wildcard arm.
 #[allow(dead_code)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Axis {
     Subject,
     Assertion,
     Predicate,
     Object,
     Wrapped,
+    TaggedContent(u64),
 }
 
 impl Axis {
@@ -51,14 +57,20 @@ impl Axis {
             (Axis::Wrapped, EnvelopeCase::Wrapped { envelope, .. }) => {
                 vec![(envelope.clone(), EdgeType::Content)]
             }
+            (Axis::TaggedContent(tag), _) => tagged_content(env, *tag)
+                .map(|content| (content, EdgeType::Content))
+                .into_iter()
+                .collect(),
             _ => Vec::new(),
         }
     }
 }
 
-/// Bytecode instructions for the pattern VM.
+/// Bytecode instructions for the pattern VM. New instructions may be added,
+/// so matches on `Instr` need a wildcard arm.
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum Instr {
     /// Match predicate: `literals[idx].matches(env)`
     MatchPredicate(usize),
@@ -70,6 +82,9 @@ pub enum Instr {
     Jump(usize),
     /// Descend to children via axis, one thread per child
     PushAxis(Axis),
+    /// Descend to children via axis, queueing each child's thread behind
+    /// all pending threads so that shallower paths run first
+    PushAxisBFS(Axis),
     /// Pop one envelope from the path
     Pop,
     /// Emit current path
@@ -80,6 +95,7 @@ pub enum Instr {
     Search {
         pat_idx: usize,
         capture_map: Vec<(String, usize)>,
+        limit: Option<usize>,
     },
     /// Save current path and start new traversal from last envelope
     ExtendTraversal,
@@ -87,8 +103,16 @@ pub enum Instr {
     CombineTraversal,
     /// Navigate to subject of current envelope
     NavigateSubject,
+    /// Navigate back to the parent of the current envelope in the path
+    NavigateParent,
     /// Match only if pattern at `pat_idx` does not match
     NotMatch { pat_idx: usize },
+    /// Continue at the current envelope only if pattern at `pat_idx`
+    /// matches it, keeping any captures named in `capture_map`
+    Lookahead {
+        pat_idx: usize,
+        capture_map: Vec<(String, usize)>,
+    },
     /// Repeat a sub pattern according to range and greediness
     Repeat {
         pat_idx: usize,
@@ -98,6 +122,18 @@ pub enum Instr {
     CaptureStart(usize),
     /// Mark the end of a capture group
     CaptureEnd(usize),
+    /// Continue only if the current path has not already reached this
+    /// point in the current run
+    Dedup,
+    /// Continue only if no path ending at the current envelope has already
+    /// reached this point in the current run
+    DedupTip,
+    /// Continue only if the path filter at `literals[idx]` accepts the
+    /// current path
+    FilterPath(usize),
+    /// Continue only if the match count at `literals[idx]` accepts the
+    /// current envelope
+    CountMatches(usize),
 }
 
 #[derive(Debug, Clone)]
@@ -165,6 +201,10 @@ fn repeat_paths(
     path: &Path,
     quantifier: Quantifier,
 ) -> Vec<(Envelope, Path)> {
+    if matches!(quantifier.reluctance(), Reluctance::Possessive) {
+        return possessive_repeat_paths(pat, env, path, quantifier);
+    }
+
     // Build states for all possible repetition counts
     let mut states: Vec<Vec<(Envelope, Path)>> =
         vec![vec![(env.clone(), path.clone())]];
@@ -172,23 +212,7 @@ fn repeat_paths(
 
     // Try matching the pattern repeatedly
     for _ in 0..bound {
-        let mut next = Vec::new();
-        for (e, pth) in states.last().unwrap().iter() {
-            for sub_path in pat.paths(e) {
-                if let Some(last) = sub_path.last() {
-                    if last.digest() == e.digest() {
-                        continue; // Avoid infinite loops
-                    }
-                    let mut combined = pth.clone();
-                    if sub_path.first() == Some(e) {
-                        combined.extend(sub_path.iter().skip(1).cloned());
-                    } else {
-                        combined.extend(sub_path.iter().cloned());
-                    }
-                    next.push((last.clone(), combined));
-                }
-            }
-        }
+        let next = repeat_step(pat, states.last().unwrap());
         if next.is_empty() {
             break; // No more matches possible
         }
@@ -232,11 +256,7 @@ fn repeat_paths(
         Reluctance::Greedy => count_range.rev().collect(),
         Reluctance::Lazy => count_range.collect(),
         Reluctance::Possessive => {
-            if max_count >= min_count {
-                vec![max_count]
-            } else {
-                vec![]
-            }
+            unreachable!("possessive repeats use possessive_repeat_paths")
         }
     };
 
@@ -258,7 +278,7 @@ fn repeat_paths(
             out.push((env.clone(), path.clone()));
         }
     } else {
-        // For lazy/possessive, include zero repetition first if applicable
+        // For lazy, include zero repetition first if applicable
         if has_zero_rep {
             out.push((env.clone(), path.clone()));
         }
@@ -274,6 +294,111 @@ fn repeat_paths(
     out
 }
 
+/// Extends each `(envelope, path)` state by one more match of `pat`.
+fn repeat_step(
+    pat: &Pattern,
+    states: &[(Envelope, Path)],
+) -> Vec<(Envelope, Path)> {
+    let mut next = Vec::new();
+    for (e, pth) in states {
+        for sub_path in pat.paths(e) {
+            if let Some(last) = sub_path.last() {
+                if last.digest() == e.digest() {
+                    continue; // Avoid infinite loops
+                }
+                let mut combined = pth.clone();
+                if sub_path.first() == Some(e) {
+                    combined.extend(sub_path.iter().skip(1).cloned());
+                } else {
+                    combined.extend(sub_path.iter().cloned());
+                }
+                next.push((last.clone(), combined));
+            }
+        }
+    }
+    next
+}
+
+/// Possessive repetition: advance as far as possible, then either yield the
+/// states reached at that count or fail. Fewer repetitions (including zero)
+/// are never offered to the rest of the pattern.
+fn possessive_repeat_paths(
+    pat: &Pattern,
+    env: &Envelope,
+    path: &Path,
+    quantifier: Quantifier,
+) -> Vec<(Envelope, Path)> {
+    let bound = quantifier.max().unwrap_or(usize::MAX);
+    let mut current = vec![(env.clone(), path.clone())];
+    let mut count = 0;
+    while count < bound {
+        let next = repeat_step(pat, &current);
+        if next.is_empty() {
+            break;
+        }
+        current = next;
+        count += 1;
+    }
+    if count < quantifier.min() {
+        Vec::new()
+    } else {
+        current
+    }
+}
+
+/// Matches a search's inner pattern at `env`, returning each result as a
+/// path extending `path` along with the thread's updated captures.
+fn search_matches(
+    inner: &Pattern,
+    env: &Envelope,
+    path: &Path,
+    captures: &[Vec<Path>],
+    capture_map: &[(String, usize)],
+) -> Vec<(Path, Vec<Vec<Path>>)> {
+    let (found_paths, caps) = inner.paths_with_captures(env);
+    let mut results = Vec::new();
+    for found_path in found_paths {
+        let mut result_path = path.clone();
+        if let Some(first) = found_path.first() {
+            if first == env {
+                result_path.extend(found_path.into_iter().skip(1));
+            } else {
+                result_path.extend(found_path);
+            }
+        }
+
+        let mut result_caps = captures.to_vec();
+        for (name, idx) in capture_map {
+            if let Some(pths) = caps.get(name) {
+                result_caps[*idx].extend(pths.clone());
+            }
+        }
+        results.push((result_path, result_caps));
+    }
+    results
+}
+
+/// Returns the children a search visits below `env`, following the same
+/// structure order as `Envelope::walk_structure` so that every envelope is
+/// visited exactly once.
+fn search_children(env: &Envelope) -> Vec<Envelope> {
+    use bc_envelope::base::envelope::EnvelopeCase::*;
+    let mut children = Vec::new();
+    match env.case() {
+        Node { subject, assertions, .. } => {
+            children.push(subject.clone());
+            children.extend(assertions.iter().cloned());
+        }
+        Wrapped { envelope, .. } => children.push(envelope.clone()),
+        Assertion(assertion) => {
+            children.push(assertion.predicate().clone());
+            children.push(assertion.object().clone());
+        }
+        _ => {}
+    }
+    children
+}
+
 /// Execute `prog` starting at `root`.  Every time `SAVE` or `ACCEPT` executes,
 /// current `path` is pushed into result.
 /// Execute a single thread until it halts. Returns true if any paths were
@@ -282,12 +407,15 @@ fn run_thread(
     prog: &Program,
     start: Thread,
     out: &mut Vec<(Path, Vec<Vec<Path>>)>,
+    dedup_seen: &mut HashSet<(usize, Vec<bc_components::Digest>)>,
 ) -> bool {
     use Instr::*;
     let mut produced = false;
-    let mut stack = vec![start];
+    // Threads are popped from the back; breadth-first descents queue theirs
+    // at the front.
+    let mut stack = VecDeque::from([start]);
 
-    while let Some(mut th) = stack.pop() {
+    while let Some(mut th) = stack.pop_back() {
         loop {
             match prog.code[th.pc] {
                 MatchPredicate(idx) => {
@@ -418,7 +546,7 @@ fn run_thread(
                             }
                         }
 
-                        stack.push(fork);
+                        stack.push_back(fork);
                     }
                 }
                 MatchStructure(idx) => {
@@ -460,14 +588,14 @@ fn run_thread(
                             if let Some(last_env) = structure_path.last() {
                                 fork.env = last_env.clone();
                             }
-                            stack.push(fork);
+                            stack.push_back(fork);
                         }
                     }
                 }
                 Split { a, b } => {
                     let mut fork = th.clone();
                     fork.pc = a;
-                    stack.push(fork);
+                    stack.push_back(fork);
                     th.pc = b;
                 }
                 Jump(t) => th.pc = t,
@@ -477,7 +605,17 @@ fn run_thread(
                         let mut fork = th.clone();
                         fork.env = child.clone();
                         fork.path.push(child);
-                        stack.push(fork);
+                        stack.push_back(fork);
+                    }
+                    break; // parent path stops here
+                }
+                PushAxisBFS(axis) => {
+                    th.pc += 1;
+                    for (child, _edge) in axis.children(&th.env) {
+                        let mut fork = th.clone();
+                        fork.env = child.clone();
+                        fork.path.push(child);
+                        stack.push_front(fork);
                     }
                     break; // parent path stops here
                 }
@@ -485,6 +623,53 @@ fn run_thread(
                     th.path.pop();
                     th.pc += 1;
                 }
+                Dedup => {
+                    // Key on the instruction and the full path, including
+                    // segments saved by enclosing traversals.
+                    let key: Vec<_> = th
+                        .saved_paths
+                        .iter()
+                        .chain(std::iter::once(&th.path))
+                        .flatten()
+                        .map(|e| e.digest())
+                        .collect();
+                    if !dedup_seen.insert((th.pc, key)) {
+                        break;
+                    }
+                    th.pc += 1;
+                }
+                DedupTip => {
+                    // Key on the instruction and the tip alone, so different
+                    // routes to the same envelope collapse into the first.
+                    if !dedup_seen.insert((th.pc, vec![th.env.digest()])) {
+                        break;
+                    }
+                    th.pc += 1;
+                }
+                FilterPath(idx) => {
+                    let Pattern::Meta(
+                        crate::pattern::meta::MetaPattern::PathFilter(filter),
+                    ) = &prog.literals[idx]
+                    else {
+                        panic!("FilterPath used with non-filter pattern");
+                    };
+                    if !filter.accepts(&th.path) {
+                        break;
+                    }
+                    th.pc += 1;
+                }
+                CountMatches(idx) => {
+                    let Pattern::Meta(
+                        crate::pattern::meta::MetaPattern::SearchCount(count),
+                    ) = &prog.literals[idx]
+                    else {
+                        panic!("CountMatches used with non-count pattern");
+                    };
+                    if !count.accepts(&th.env) {
+                        break;
+                    }
+                    th.pc += 1;
+                }
                 Save => {
                     out.push((th.path.clone(), th.captures.clone()));
                     produced = true;
@@ -495,80 +680,94 @@ fn run_thread(
                     produced = true;
                     break;
                 }
-                Search { pat_idx, ref capture_map } => {
+                Search { pat_idx, ref capture_map, limit } => {
                     let inner = &prog.literals[pat_idx];
-                    let (found_paths, caps) =
-                        inner.paths_with_captures(&th.env);
-
-                    if !found_paths.is_empty() {
-                        produced = true;
-                        for found_path in found_paths {
-                            let mut result_path = th.path.clone();
-                            if let Some(first) = found_path.first() {
-                                if first == &th.env {
-                                    result_path
-                                        .extend(found_path.into_iter().skip(1));
-                                } else {
-                                    result_path.extend(found_path);
-                                }
+                    // Each search result continues with the rest of the
+                    // program from the matched envelope.
+                    let mut results = Vec::new();
+
+                    if let Some(limit) = limit {
+                        // A limited search walks its subtree in place so
+                        // that it can stop as soon as `limit` paths have
+                        // been found.
+                        let mut pending =
+                            vec![(th.env.clone(), th.path.clone())];
+                        while let Some((env, path)) = pending.pop() {
+                            if results.len() >= limit {
+                                break;
                             }
-
-                            let mut result_caps = th.captures.clone();
-                            for (name, idx) in capture_map {
-                                if let Some(pths) = caps.get(name) {
-                                    result_caps[*idx].extend(pths.clone());
+                            for (result_path, result_caps) in search_matches(
+                                inner,
+                                &env,
+                                &path,
+                                &th.captures,
+                                capture_map,
+                            ) {
+                                if results.len() >= limit {
+                                    break;
+                                }
+                                let digests: Vec<_> = result_path
+                                    .iter()
+                                    .map(|e| e.digest())
+                                    .collect();
+                                if th.seen.insert(digests) {
+                                    results.push((result_path, result_caps));
                                 }
                             }
+                            for child in search_children(&env).into_iter().rev()
+                            {
+                                let mut child_path = path.clone();
+                                child_path.push(child.clone());
+                                pending.push((child, child_path));
+                            }
+                        }
+                    } else {
+                        for (result_path, result_caps) in search_matches(
+                            inner,
+                            &th.env,
+                            &th.path,
+                            &th.captures,
+                            capture_map,
+                        ) {
                             let digests: Vec<_> = result_path
                                 .iter()
                                 .map(|e| e.digest())
                                 .collect();
                             if th.seen.insert(digests) {
-                                out.push((result_path, result_caps));
+                                results.push((result_path, result_caps));
                             }
                         }
-                    }
 
-                    // 2) always walk children (same traversal as
-                    //    Envelope::walk)
-                    // Collect all children first, then push in reverse order to
-                    // maintain the same traversal order as
-                    // the original recursive implementation
-                    // Build child list following the same structure order as
-                    // `Envelope::walk_structure`. This ensures every envelope
-                    // is visited exactly once.
-                    let mut all_children = Vec::new();
-                    use bc_envelope::base::envelope::EnvelopeCase::*;
-                    match th.env.case() {
-                        Node { subject, assertions, .. } => {
-                            all_children.push(subject.clone());
-                            for assertion in assertions {
-                                all_children.push(assertion.clone());
-                            }
-                        }
-                        Wrapped { envelope, .. } => {
-                            all_children.push(envelope.clone());
-                        }
-                        Assertion(assertion) => {
-                            all_children.push(assertion.predicate().clone());
-                            all_children.push(assertion.object().clone());
+                        // Always walk children, one forked thread per child.
+                        // Push child threads in reverse order so stack
+                        // processes them in forward order
+                        for child in search_children(&th.env).into_iter().rev()
+                        {
+                            let mut fork = th.clone();
+                            fork.env = child.clone();
+                            fork.path.push(child);
+                            // fork continues with same PC to re-execute Search
+                            // at child
+                            stack.push_back(fork);
                         }
-                        _ => {}
                     }
 
-                    // Push child threads in reverse order so stack processes
-                    // them in forward order
-                    for child in all_children.into_iter().rev() {
-                        let mut fork = th.clone();
-                        fork.env = child.clone();
-                        fork.path.push(child);
-                        // fork continues with same PC to re-execute Search at
-                        // child
-                        stack.push(fork);
+                    // Push result threads last so they run before the
+                    // children are searched, preserving walk order.
+                    for (result_path, result_caps) in results.into_iter().rev()
+                    {
+                        let mut next = th.clone();
+                        next.pc += 1;
+                        if let Some(last) = result_path.last() {
+                            next.env = last.clone();
+                        }
+                        next.path = result_path;
+                        next.captures = result_caps;
+                        stack.push_back(next);
                     }
 
-                    // This thread is done - either it emitted results or it
-                    // didn't
+                    // This thread is done; its results and children continue
+                    // on their own threads.
                     break;
                 }
                 ExtendTraversal => {
@@ -632,7 +831,7 @@ fn run_thread(
                         fork.pc = next_pc;
                         fork.env = env_after;
                         fork.path = path_after;
-                        if run_thread(prog, fork, out) {
+                        if run_thread(prog, fork, out, dedup_seen) {
                             produced = true;
                             success = true;
                             break;
@@ -654,6 +853,30 @@ fn run_thread(
                     }
                     th.pc += 1;
                 }
+                NavigateParent => {
+                    // Envelopes have no parent links, so the parent is the
+                    // element before the tip of the current path. A path
+                    // with no such element ends the thread.
+                    if th.path.len() < 2 {
+                        break;
+                    }
+                    th.path.pop();
+                    th.env = th.path.last().cloned().unwrap();
+                    th.pc += 1;
+                }
+                Lookahead { pat_idx, ref capture_map } => {
+                    let (paths, caps) =
+                        prog.literals[pat_idx].paths_with_captures(&th.env);
+                    if paths.is_empty() {
+                        break;
+                    }
+                    for (name, idx) in capture_map {
+                        if let Some(pths) = caps.get(name) {
+                            th.captures[*idx].extend(pths.clone());
+                        }
+                    }
+                    th.pc += 1;
+                }
                 NotMatch { pat_idx } => {
                     // Check if the pattern matches. If it doesn't match, the
                     // NOT pattern succeeds. If it does
@@ -727,7 +950,7 @@ pub fn run(
         capture_stack: vec![Vec::new(); prog.capture_names.len()],
         seen: std::collections::HashSet::new(),
     };
-    run_thread(prog, start, &mut out);
+    run_thread(prog, start, &mut out, &mut HashSet::new());
     out.into_iter()
         .map(|(path, caps)| {
             let mut map = std::collections::HashMap::new();
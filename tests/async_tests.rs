@@ -0,0 +1,29 @@
+#![cfg(feature = "async")]
+
+use bc_envelope::prelude::*;
+use bc_envelope_pattern::{Matcher, Pattern};
+
+#[tokio::test]
+async fn test_async_paths() {
+    let envelope = Envelope::new("Alice")
+        .add_assertion("knows", "Bob")
+        .add_assertion("age", 30);
+
+    let pattern = Pattern::search(Pattern::any_text());
+    let paths = pattern.async_paths(envelope.clone()).await;
+    assert_eq!(paths, pattern.paths(&envelope));
+    assert!(!paths.is_empty());
+
+    let pattern = Pattern::search(Pattern::any_bool());
+    assert!(pattern.async_paths(envelope).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_async_paths_with_captures() {
+    let envelope = Envelope::new("Alice").add_assertion("knows", "Bob");
+    let pattern = Pattern::parse(r#"search(@name("Bob"))"#).unwrap();
+
+    let (paths, captures) =
+        pattern.async_paths_with_captures(envelope.clone()).await;
+    assert_eq!((paths, captures), pattern.paths_with_captures(&envelope));
+}
@@ -1,7 +1,12 @@
 mod common;
 
+use std::collections::HashMap;
+
 use bc_envelope::prelude::*;
-use bc_envelope_pattern::{Matcher, Pattern, format_paths_with_captures};
+use bc_envelope_pattern::{
+    Matcher, Pattern, coerce_captures_to_leaf_paths, coerce_to_root_paths,
+    format_paths_with_captures,
+};
 use indoc::indoc;
 
 #[test]
@@ -83,3 +88,173 @@ fn capture_no_match() {
         "No match capture test"
     );
 }
+
+#[test]
+fn capture_renamed() {
+    let env = Envelope::new("Alice").add_assertion("knows", "Bob");
+    let pat =
+        Pattern::parse(r#"search(@p(pred(text)) | @o(obj(text)))"#).unwrap();
+
+    let map = HashMap::from([
+        ("o".to_string(), "object".to_string()),
+        ("unused".to_string(), "ignored".to_string()),
+    ]);
+    let renamed = pat.clone().clone_with_new_captures(&map);
+    assert_actual_expected!(
+        renamed.to_string(),
+        "search(@p(pred(text)) | @object(obj(text)))"
+    );
+
+    let (paths, caps) = renamed.paths_with_captures(&env);
+    assert_eq!(paths, pat.paths(&env));
+    let mut names: Vec<_> = caps.keys().cloned().collect();
+    names.sort();
+    assert_eq!(names, vec!["object", "p"]);
+    assert_eq!(caps["object"], pat.paths_with_captures(&env).1["o"]);
+}
+
+#[test]
+fn capture_equivalence() {
+    let a = Pattern::capture("a", Pattern::text("x"));
+    let b = Pattern::capture("b", Pattern::text("x"));
+    let c = Pattern::parse(r#"@c("x")"#).unwrap();
+    assert_ne!(a, b);
+    assert!(a.equivalent_to(&b));
+
+    // Reflexive, symmetric and transitive.
+    assert!(a.equivalent_to(&a));
+    assert!(b.equivalent_to(&a));
+    assert!(b.equivalent_to(&c) && a.equivalent_to(&c));
+
+    // Nested captures are compared the same way.
+    let nested_a =
+        Pattern::parse(r#"search(@outer(node & @inner(text)))"#).unwrap();
+    let nested_b = Pattern::parse(r#"search(@x(node & @y(text)))"#).unwrap();
+    assert!(nested_a.equivalent_to(&nested_b));
+
+    // Differences outside capture names still matter.
+    assert!(!a.equivalent_to(&Pattern::capture("a", Pattern::text("y"))));
+    assert!(!a.equivalent_to(&Pattern::text("x")));
+}
+
+#[test]
+fn capture_structural_hash() {
+    let a = Pattern::capture("a", Pattern::text("x"));
+    let b = Pattern::capture("b", Pattern::text("x"));
+    assert_eq!(a.structural_hash(), b.structural_hash());
+    assert_eq!(a.structural_hash(), a.clone().structural_hash());
+
+    let nested_a =
+        Pattern::parse(r#"search(@outer(node & @inner(text)))"#).unwrap();
+    let nested_b = Pattern::parse(r#"search(@x(node & @y(text)))"#).unwrap();
+    assert_eq!(nested_a.structural_hash(), nested_b.structural_hash());
+
+    // A capture is still part of the structure, whatever its name.
+    assert_ne!(a.structural_hash(), Pattern::text("x").structural_hash());
+    assert_ne!(
+        a.structural_hash(),
+        Pattern::capture("a", Pattern::text("y")).structural_hash()
+    );
+}
+
+#[test]
+fn capture_accessors() {
+    let env = Envelope::new("Alice")
+        .add_assertion("knows", "Bob")
+        .add_assertion("knows", "Carol");
+    let pat = Pattern::search(Pattern::and(vec![
+        Pattern::capture("name", Pattern::any_text()),
+        Pattern::not_matching(Pattern::text("Alice")),
+    ]));
+
+    let (_, caps) = pat.paths_with_captures(&env);
+    assert_eq!(pat.all_captures(&env), caps);
+    assert_eq!(caps["name"].len(), 4);
+
+    let first = pat.first_capture(&env, "name").unwrap();
+    assert_eq!(first, caps["name"][0]);
+
+    assert_eq!(pat.first_capture(&env, "other"), None);
+    assert!(pat.all_captures(&Envelope::new(42)).is_empty());
+    assert_eq!(pat.first_capture(&Envelope::new(42), "name"), None);
+}
+
+#[test]
+fn capture_presence_and_depth() {
+    let none = Pattern::parse(r#"search(node & text)"#).unwrap();
+    assert!(!none.has_captures());
+    assert_eq!(none.capture_depth(), 0);
+
+    let one = Pattern::parse(r#"search(@name(text))"#).unwrap();
+    assert!(one.has_captures());
+    assert_eq!(one.capture_depth(), 1);
+
+    // Sibling captures don't add depth.
+    let siblings = Pattern::parse(r#"@a(text) | @b(number)"#).unwrap();
+    assert_eq!(siblings.capture_depth(), 1);
+
+    let nested =
+        Pattern::parse(r#"search(@outer(node & @inner(subj(@name(text)))))"#)
+            .unwrap();
+    assert!(nested.has_captures());
+    assert_eq!(nested.capture_depth(), 3);
+
+    // Captures inside structure patterns are found too.
+    let in_structure = Pattern::subject(Pattern::capture("s", Pattern::any()));
+    assert!(in_structure.has_captures());
+    let env = Envelope::new("Alice");
+    assert!(!in_structure.paths_with_captures(&env).1.is_empty());
+
+    // Captures inside dcbor patterns count as well, nested or not.
+    let in_cbor = Pattern::parse(r#"cbor(/@n(number)/)"#).unwrap();
+    assert!(in_cbor.has_captures());
+    assert_eq!(in_cbor.capture_depth(), 1);
+    assert!(!in_cbor.paths_with_captures(&Envelope::new(42)).1.is_empty());
+    let nested_cbor =
+        Pattern::parse(r#"@env(cbor(/@outer([@inner(number), (text)])/))"#)
+            .unwrap();
+    assert_eq!(nested_cbor.capture_depth(), 3);
+}
+
+#[test]
+fn capture_and_keeps_all_captures() {
+    let env = Envelope::new(42);
+    let pat = Pattern::and(vec![
+        Pattern::capture("a", Pattern::number(42)),
+        Pattern::capture("b", Pattern::number_greater_than(40)),
+    ]);
+
+    let (paths, caps) = pat.paths_with_captures(&env);
+    assert_eq!(paths.len(), 1);
+    assert_eq!(caps.len(), 2);
+    assert_eq!(caps["a"], vec![vec![env.clone()]]);
+    assert_eq!(caps["b"], vec![vec![env.clone()]]);
+}
+
+#[test]
+fn capture_coerce_paths() {
+    let env = Envelope::new("Alice").add_assertion("knows", "Bob");
+    let pat = Pattern::and(vec![
+        Pattern::capture("name", Pattern::search(Pattern::text("Bob"))),
+        Pattern::capture("root", Pattern::any_node()),
+    ]);
+    let (_, caps) = pat.paths_with_captures(&env);
+    assert_eq!(caps["name"].len(), 1);
+    assert_eq!(caps["name"][0].len(), 3);
+    assert_eq!(caps["root"], vec![vec![env.clone()]]);
+
+    // Longer paths are cut down to their tip or root; paths of one
+    // element are unchanged.
+    let leaves = coerce_captures_to_leaf_paths(caps.clone());
+    assert_eq!(leaves["name"], vec![vec![Envelope::new("Bob")]]);
+    assert_eq!(leaves["root"], caps["root"]);
+
+    let roots = coerce_to_root_paths(caps.clone());
+    assert_eq!(roots["name"], vec![vec![env.clone()]]);
+    assert_eq!(roots["root"], caps["root"]);
+
+    // Empty paths stay empty.
+    let empty = HashMap::from([("e".to_string(), vec![Vec::new()])]);
+    assert_eq!(coerce_captures_to_leaf_paths(empty.clone()), empty);
+    assert_eq!(coerce_to_root_paths(empty.clone()), empty);
+}
@@ -87,6 +87,42 @@ fn test_cbor_pattern_complex_structures() {
     );
 }
 
+#[test]
+fn test_cbor_bytes_pattern() {
+    // 42 is encoded as `18 2a`, but 5 fits in the initial byte.
+    let pattern =
+        Pattern::cbor_bytes_pattern(regex::bytes::Regex::new(r"^\x18").unwrap());
+    assert_eq!(pattern.to_string(), r"cbor_bytes(h'/^\x18/')");
+    assert_eq!(Pattern::parse(pattern.to_string()).unwrap(), pattern);
+    assert!(pattern.matches(&Envelope::new(42)));
+    assert!(!pattern.matches(&Envelope::new(5)));
+
+    // The subject of a node is matched; a wrapped envelope has no CBOR
+    // subject.
+    let node = Envelope::new(42).add_assertion("knows", "Bob");
+    assert!(pattern.matches(&node));
+    assert!(!pattern.matches(&Envelope::new(42).wrap()));
+
+    // Three-element arrays begin with `83`.
+    let pattern = Pattern::parse(r"cbor_bytes(h'/(?-u)^\x83/')").unwrap();
+    assert!(pattern.matches(&Envelope::new(vec![1, 2, 3])));
+    assert!(!pattern.matches(&Envelope::new(vec![1, 2])));
+
+    // A five-byte text string starting with "hel".
+    let pattern = Pattern::parse(r"cbor_bytes(h'/^\x65hel/')").unwrap();
+    assert!(pattern.matches(&Envelope::new("hello")));
+    assert!(!pattern.matches(&Envelope::new("help")));
+
+    // Known values are tagged with 40000 (`d9 9c 40`).
+    let pattern =
+        Pattern::parse(r"cbor_bytes(h'/(?-u)^\xd9\x9c\x40/')").unwrap();
+    assert!(pattern.matches(&Envelope::new(known_values::IS_A)));
+    assert!(!pattern.matches(&Envelope::new(1)));
+
+    assert!(Pattern::parse("cbor_bytes(h'/(/')").is_err());
+    assert!(Pattern::parse("cbor_bytes").is_err());
+}
+
 #[test]
 fn test_cbor_pattern_debug_parser() {
     // Let's test what the parser actually supports
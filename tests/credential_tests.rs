@@ -2,7 +2,11 @@ mod common;
 
 use bc_envelope::prelude::*;
 use bc_envelope_pattern::{
-    FormatPathsOpts, Matcher, PathElementFormat, Pattern, format_paths_opt,
+    FormatPathsOpts, Matcher, PathElementFormat, Pattern,
+    count_distinct_assertion_paths, count_distinct_objects_in_paths,
+    count_distinct_subjects_in_paths, format_paths_opt, search_all_dates,
+    search_all_known_values, search_all_numbers, search_all_text_values,
+    try_extract_bool, try_extract_date, try_extract_number, try_extract_text,
 };
 use indoc::indoc;
 
@@ -112,6 +116,305 @@ fn test_parsed_firstname_capture() {
     assert_eq!(paths.len(), 1);
 }
 
+#[test]
+fn test_search_assertion_exact() {
+    let env = credential();
+    let pattern =
+        Pattern::search(Pattern::assertion_exact("firstName", "James"));
+    let paths = pattern.paths(&env);
+    assert_eq!(paths.len(), 1);
+    let assertion = paths[0].last().unwrap();
+    assert_eq!(
+        assertion.as_predicate().unwrap(),
+        Envelope::new("firstName")
+    );
+    assert_eq!(assertion.as_object().unwrap(), Envelope::new("James"));
+
+    // Predicate and object must belong to the same assertion.
+    let pattern =
+        Pattern::search(Pattern::assertion_exact("firstName", "Maxwell"));
+    assert!(!pattern.matches(&env));
+
+    // Both values must match exactly.
+    let pattern = Pattern::search(Pattern::assertion_exact("firstName", "Jim"));
+    assert!(!pattern.matches(&env));
+
+    let parsed =
+        Pattern::parse(r#"search(assert(pred: "firstName", obj: "James"))"#)
+            .unwrap();
+    assert_eq!(parsed.paths(&env), paths);
+}
+
+#[test]
+fn test_try_extract_values() {
+    let env = credential();
+
+    let paths =
+        Pattern::parse(r#"search(assertpred("firstName"|"lastName")->obj)"#)
+            .unwrap()
+            .paths(&env);
+    let mut names = try_extract_text(&paths);
+    names.sort();
+    assert_eq!(names, vec!["James", "Maxwell"]);
+
+    let paths = Pattern::search(Pattern::any_number()).paths(&env);
+    let mut numbers = try_extract_number(&paths);
+    numbers.sort_by(f64::total_cmp);
+    assert_eq!(numbers, vec![1.0, 15.0]);
+
+    let paths = Pattern::search(Pattern::any_date()).paths(&env);
+    let mut dates = try_extract_date(&paths);
+    dates.sort_by_key(|d| d.datetime());
+    assert_eq!(
+        dates,
+        vec![
+            Date::from_string("2020-01-01").unwrap(),
+            Date::from_string("2028-01-01").unwrap(),
+        ]
+    );
+
+    // Tips of the wrong type are skipped.
+    assert!(try_extract_bool(&paths).is_empty());
+    assert!(try_extract_text(&paths).is_empty());
+
+    let env = Envelope::new("Alice").add_assertion("verified", true);
+    let paths = Pattern::search(Pattern::any_bool()).paths(&env);
+    assert_eq!(try_extract_bool(&paths), vec![true]);
+}
+
+#[test]
+fn test_extract_first_and_all() {
+    let env = credential();
+
+    let pattern =
+        Pattern::parse(r#"search(assertpred("firstName")->obj)"#).unwrap();
+    assert_eq!(pattern.extract_first::<String>(&env), Some("James".into()));
+    // The match is not a number.
+    assert_eq!(pattern.extract_first::<i64>(&env), None);
+
+    let pattern = Pattern::parse(
+        r#"search(assertpred("professionalDevelopmentHours")->obj)"#,
+    )
+    .unwrap();
+    assert_eq!(pattern.extract_first::<i64>(&env), Some(15));
+
+    let pattern =
+        Pattern::parse(r#"search(assertpred("issueDate")->obj)"#).unwrap();
+    assert_eq!(
+        pattern.extract_first::<Date>(&env),
+        Some(Date::from_string("2020-01-01").unwrap())
+    );
+
+    // No match at all.
+    let pattern = Pattern::search_text("Nobody");
+    assert_eq!(pattern.extract_first::<String>(&env), None);
+
+    let mut hours =
+        Pattern::search(Pattern::any_number()).extract_all::<i64>(&env);
+    hours.sort();
+    assert_eq!(hours, vec![1, 15]);
+}
+
+#[test]
+fn test_search_all_values() {
+    let env = credential();
+
+    // Text predicates are values too, and a value that appears twice is
+    // returned twice.
+    let texts = search_all_text_values(&env);
+    assert_eq!(
+        texts,
+        vec![
+            "certificateNumber",
+            "123-456-789",
+            "expirationDate",
+            "lastName",
+            "Maxwell",
+            "issueDate",
+            "Certificate of Completion",
+            "photo",
+            "This is James Maxwell's photo.",
+            "professionalDevelopmentHours",
+            "firstName",
+            "James",
+            "topics",
+            "continuingEducationUnits",
+            "Example Electrical Engineering Board",
+            "subject",
+            "RF and Microwave Engineering",
+            "Example Electrical Engineering Board",
+            "Signed by Example Electrical Engineering Board",
+        ]
+    );
+    assert_eq!(
+        texts,
+        Pattern::search(Pattern::any_text()).extract_all::<String>(&env)
+    );
+
+    assert_eq!(search_all_numbers(&env), vec![15.0, 1.0]);
+    assert_eq!(
+        search_all_dates(&env),
+        vec![Date::from_ymd(2028, 1, 1), Date::from_ymd(2020, 1, 1)]
+    );
+    assert_eq!(
+        search_all_known_values(&env),
+        vec![
+            known_values::IS_A,
+            known_values::CONTROLLER,
+            known_values::ISSUER,
+            known_values::SIGNED,
+            known_values::NOTE,
+        ]
+    );
+
+    let empty = Envelope::new(true);
+    assert!(search_all_text_values(&empty).is_empty());
+    assert!(search_all_known_values(&empty).is_empty());
+}
+
+#[test]
+#[cfg(feature = "domain")]
+fn test_signed_patterns() {
+    use bc_envelope_pattern::{DCBORPattern, format_paths};
+
+    bc_envelope::register_tags();
+    let env = credential();
+
+    // The signature is on the outer node, not the wrapped credential.
+    assert!(Pattern::any_signed().matches(&env));
+    let content = env.subject().try_unwrap().unwrap();
+    assert!(!Pattern::any_signed().matches(&content));
+    assert!(!Pattern::any_signed().matches(&redacted_credential().subject()));
+
+    let signature = Pattern::tagged_name("signature", DCBORPattern::any());
+    let paths = Pattern::signed_by(signature).paths(&env);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        46a02aaf ASSERTION 'signed': Signature
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+    assert!(!Pattern::signed_by(Pattern::any_text()).matches(&env));
+
+    let unsigned = Envelope::new("Alice").add_assertion("knows", "Bob");
+    assert!(!Pattern::any_signed().matches(&unsigned));
+}
+
+#[test]
+#[cfg(feature = "domain")]
+fn test_vc_patterns() {
+    let env = credential();
+
+    // The issuer is on the wrapped credential, not the signed outer node.
+    let issuer = Pattern::search(Pattern::vc_issuer());
+    assert_eq!(
+        Pattern::search(Pattern::assertion_with_predicate_and_object(
+            Pattern::known_value_named("issuer"),
+            Pattern::any_text()
+        ))
+        .paths(&env),
+        issuer.paths(&env)
+    );
+    assert_eq!(issuer.paths(&env).len(), 1);
+    assert!(!Pattern::vc_issuer().matches(&env));
+
+    // The fixture uses text predicates for its dates and has no `id`.
+    assert!(!Pattern::search(Pattern::vc_subject_id()).matches(&env));
+    assert!(!Pattern::search(Pattern::vc_issuance_date()).matches(&env));
+
+    let vc = Envelope::new("Alice")
+        .add_assertion(known_values::ID, "did:example:alice")
+        .add_assertion(known_values::VALID_FROM, Date::from_ymd(2020, 1, 1))
+        .add_assertion(known_values::VALID_UNTIL, Date::from_ymd(2028, 1, 1));
+    assert!(Pattern::vc_subject_id().matches(&vc));
+    assert!(Pattern::vc_issuance_date().matches(&vc));
+    assert!(Pattern::vc_expiration_date().matches(&vc));
+
+    assert!(Pattern::vc_is_valid_at(Date::from_ymd(2024, 6, 1)).matches(&vc));
+    assert!(!Pattern::vc_is_valid_at(Date::from_ymd(2019, 6, 1)).matches(&vc));
+    assert!(!Pattern::vc_is_valid_at(Date::from_ymd(2029, 6, 1)).matches(&vc));
+
+    // Both dates are required.
+    let open_ended = Envelope::new("Alice")
+        .add_assertion(known_values::VALID_FROM, Date::from_ymd(2020, 1, 1));
+    assert!(
+        !Pattern::vc_is_valid_at(Date::from_ymd(2024, 6, 1))
+            .matches(&open_ended)
+    );
+}
+
+#[test]
+fn test_known_value_constructors() {
+    let env = credential();
+
+    let is_a = Pattern::search(Pattern::assertion_pred_known_value(
+        known_values::IS_A,
+    ));
+    assert_eq!(
+        is_a,
+        Pattern::search(Pattern::assertion_with_predicate(
+            Pattern::known_value(known_values::IS_A)
+        ))
+    );
+    let paths = is_a.paths(&env);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        0b721f78 NODE { ARID(4676635a) [ 'isA': "Certificate of Completion", "certifica…
+            397a2d4c WRAPPED { ARID(4676635a) [ 'isA': "Certificate of Completion", "certif…
+                8122ffa9 NODE ARID(4676635a) [ 'isA': "Certificate of Completion", "certificate…
+                    4d67bba0 ASSERTION 'isA': "Certificate of Completion"
+    "#}.trim();
+    assert_actual_expected!(
+        format_paths_opt(
+            &paths,
+            FormatPathsOpts::default()
+                .element_format(PathElementFormat::Summary(Some(80)))
+        ),
+        expected
+    );
+
+    let issuer = Pattern::search(Pattern::traverse(vec![
+        Pattern::any_assertion(),
+        Pattern::predicate_known_value(known_values::ISSUER),
+    ]));
+    assert_eq!(issuer.paths(&env).len(), 1);
+    assert!(
+        !Pattern::search(Pattern::assertion_pred_known_value(
+            known_values::HOLDER
+        ))
+        .matches(&env)
+    );
+
+    let seed = Envelope::new("seed")
+        .add_assertion(known_values::IS_A, known_values::SEED_TYPE)
+        .add_assertion(known_values::NOTE, "A seed");
+    let is_seed = Pattern::traverse(vec![
+        Pattern::assertion_pred_known_value(known_values::IS_A),
+        Pattern::object_known_value(known_values::SEED_TYPE),
+    ]);
+    assert_eq!(
+        Pattern::object_known_value(known_values::SEED_TYPE),
+        Pattern::object(Pattern::known_value(known_values::SEED_TYPE))
+    );
+    assert!(is_seed.matches(&seed));
+    assert!(!is_seed.matches(&env));
+    assert!(
+        Pattern::has_known_value_assertion_with_object(
+            known_values::IS_A,
+            Pattern::known_value(known_values::SEED_TYPE),
+        )
+        .matches(&seed)
+    );
+    assert!(
+        !Pattern::traverse(vec![
+            Pattern::any_assertion(),
+            Pattern::predicate_known_value(known_values::SEED_TYPE),
+        ])
+        .matches(&seed)
+    );
+}
+
 #[test]
 fn test_search_capture_propagation() {
     let env = credential();
@@ -272,3 +575,171 @@ fn test_search_elided() {
     let paths = pat.paths(&env);
     assert_eq!(paths.len(), 7);
 }
+
+#[test]
+fn test_known_value_assertions() {
+    let env = credential();
+
+    // The credential is wrapped and signed, so search for the assertions.
+    let is_a =
+        Pattern::search(Pattern::has_known_value_assertion(known_values::IS_A));
+    assert_eq!(
+        is_a,
+        Pattern::search(Pattern::assertion_pred_known_value(
+            known_values::IS_A
+        ))
+    );
+    assert_eq!(is_a.paths(&env).len(), 1);
+    let signed = Pattern::search(Pattern::has_known_value_assertion(
+        known_values::SIGNED,
+    ));
+    assert_eq!(signed.paths(&env).len(), 1);
+    assert!(
+        !Pattern::search(Pattern::has_known_value_assertion(
+            known_values::HOLDER
+        ))
+        .matches(&env)
+    );
+
+    let issuer =
+        Pattern::search(Pattern::has_known_value_assertion_with_object(
+            known_values::ISSUER,
+            Pattern::text("Example Electrical Engineering Board"),
+        ));
+    assert_eq!(issuer.paths(&env).len(), 1);
+    let wrong_issuer =
+        Pattern::search(Pattern::has_known_value_assertion_with_object(
+            known_values::ISSUER,
+            Pattern::text("Someone Else"),
+        ));
+    assert!(!wrong_issuer.matches(&env));
+
+    let certificate =
+        Pattern::search(Pattern::has_type("Certificate of Completion"));
+    let paths = certificate.paths(&env);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        0b721f78 NODE { ARID(4676635a) [ 'isA': "Certificate of Completion", "certifica…
+            397a2d4c WRAPPED { ARID(4676635a) [ 'isA': "Certificate of Completion", "certif…
+                8122ffa9 NODE ARID(4676635a) [ 'isA': "Certificate of Completion", "certificate…
+                    4d67bba0 ASSERTION 'isA': "Certificate of Completion"
+    "#}.trim();
+    assert_actual_expected!(
+        format_paths_opt(
+            &paths,
+            FormatPathsOpts::default()
+                .element_format(PathElementFormat::Summary(Some(80)))
+        ),
+        expected
+    );
+    assert!(!Pattern::search(Pattern::has_type("Passport")).matches(&env));
+
+    // The type assertion is on the inner node, so it matches once unwrapped.
+    let inner = env.try_unwrap().unwrap();
+    assert!(!Pattern::has_type("Certificate of Completion").matches(&env));
+    assert!(Pattern::has_type("Certificate of Completion").matches(&inner));
+}
+
+#[test]
+fn test_count_distinct_in_paths() {
+    let env = credential();
+
+    // 13 assertions on the inner node plus 'note' and 'signed' outside.
+    let assertions = Pattern::search(Pattern::any_assertion()).paths(&env);
+    assert_eq!(count_distinct_assertion_paths(&assertions), 15);
+    // 'controller' and 'issuer' share the same object.
+    assert_eq!(count_distinct_objects_in_paths(&assertions), 14);
+    // An assertion is its own subject.
+    assert_eq!(count_distinct_subjects_in_paths(&assertions), 15);
+
+    // Both paths end at the same leaf, so it is counted once.
+    let board =
+        Pattern::search(Pattern::text("Example Electrical Engineering Board"))
+            .paths(&env);
+    assert_eq!(board.len(), 2);
+    assert_eq!(count_distinct_subjects_in_paths(&board), 1);
+    assert_eq!(count_distinct_assertion_paths(&board), 0);
+    assert_eq!(count_distinct_objects_in_paths(&board), 0);
+
+    assert_eq!(count_distinct_assertion_paths(&[]), 0);
+}
+
+#[test]
+fn test_search_predicate_object_subject() {
+    let env = credential();
+
+    // Every assertion with a text predicate; the rest use known values.
+    let pattern = Pattern::search_predicate(Pattern::any_text());
+    assert_eq!(
+        pattern,
+        Pattern::search(Pattern::assertion_with_predicate(Pattern::any_text()))
+    );
+    assert_eq!(pattern.to_string(), "search(assertpred(text))");
+    let paths = pattern.paths(&env);
+    assert_eq!(paths.len(), 10);
+    let mut predicates: Vec<String> = paths
+        .iter()
+        .map(|path| {
+            path.last()
+                .unwrap()
+                .as_predicate()
+                .unwrap()
+                .extract_subject::<String>()
+                .unwrap()
+        })
+        .collect();
+    predicates.sort();
+    assert_eq!(
+        predicates,
+        vec![
+            "certificateNumber",
+            "continuingEducationUnits",
+            "expirationDate",
+            "firstName",
+            "issueDate",
+            "lastName",
+            "photo",
+            "professionalDevelopmentHours",
+            "subject",
+            "topics",
+        ]
+    );
+    let known = Pattern::search_predicate(Pattern::any_known_value());
+    assert_eq!(known.paths(&env).len(), 5);
+
+    // 'controller' and 'issuer' share their object.
+    let pattern = Pattern::search_object(Pattern::text(
+        "Example Electrical Engineering Board",
+    ));
+    assert_eq!(
+        pattern.to_string(),
+        r#"search(assertobj("Example Electrical Engineering Board"))"#
+    );
+    assert_eq!(pattern.paths(&env).len(), 2);
+    assert_eq!(
+        Pattern::search_object(Pattern::any_date()).paths(&env).len(),
+        2
+    );
+
+    // The signed credential's subject is the wrapped credential.
+    let pattern = Pattern::search_subject(Pattern::wrapped());
+    assert_eq!(pattern, Pattern::search(Pattern::subject(Pattern::wrapped())));
+    assert_eq!(pattern.to_string(), "search(subj(wrapped))");
+    let paths = pattern.paths(&env);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        0b721f78 NODE { ARID(4676635a) [ 'isA': "Certificate of Com…
+            397a2d4c WRAPPED { ARID(4676635a) [ 'isA': "Certificate of …
+    "#}.trim();
+    assert_actual_expected!(
+        format_paths_opt(
+            &paths,
+            FormatPathsOpts::default()
+                .element_format(PathElementFormat::Summary(Some(60)))
+        ),
+        expected
+    );
+    assert!(!Pattern::search_subject(Pattern::text("Nobody")).matches(&env));
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "custom-predicates")]
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use bc_envelope::prelude::*;
+use bc_envelope_pattern::{Matcher, Pattern};
+
+fn envelope() -> Envelope {
+    Envelope::new("Alice")
+        .add_assertion("age", 30)
+        .add_assertion("retires", 65)
+        .add_assertion("knows", "Bob")
+}
+
+/// Accepts assertions whose object is a number greater than `threshold`.
+fn number_above(threshold: i32) -> Pattern {
+    Pattern::assertion_where(move |_, object| {
+        object.extract_subject::<i32>().is_ok_and(|n| n > threshold)
+    })
+}
+
+#[test]
+fn test_assertion_where() {
+    let envelope = envelope();
+
+    let pattern = number_above(40);
+    assert_eq!(pattern.to_string(), "assert(custom)");
+    assert!(Pattern::parse(pattern.to_string()).is_err());
+    let paths = pattern.paths(&envelope);
+    assert_eq!(paths, vec![vec![Envelope::new_assertion("retires", 65)]]);
+
+    assert_eq!(number_above(18).paths(&envelope).len(), 2);
+    assert!(!number_above(100).matches(&envelope));
+
+    // The predicate envelope is passed too.
+    let pattern = Pattern::assertion_where(|predicate, object| {
+        predicate.extract_subject::<String>().is_ok_and(|p| p == "knows")
+            && object.extract_subject::<String>().is_ok()
+    });
+    let paths = pattern.paths(&envelope);
+    assert_eq!(paths, vec![vec![Envelope::new_assertion("knows", "Bob")]]);
+
+    // Patterns are equal only when they share a closure.
+    let pattern = number_above(40);
+    assert_eq!(pattern, pattern.clone());
+    assert_ne!(number_above(40), number_above(40));
+}
+
+#[test]
+fn test_assertion_where_runs_once_per_assertion() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let pattern = Pattern::assertion_where(move |_, _| {
+        counter.fetch_add(1, Ordering::Relaxed);
+        true
+    });
+    assert_eq!(pattern.paths(&envelope()).len(), 3);
+    assert_eq!(calls.load(Ordering::Relaxed), 3);
+}
+
+#[test]
+fn test_assertion_where_with_captures() {
+    let envelope = envelope();
+
+    let retires = Envelope::new_assertion("retires", 65);
+    let pattern = Pattern::capture("assertion", number_above(40));
+    let (paths, captures) = pattern.paths_with_captures(&envelope);
+    assert_eq!(paths, vec![vec![retires.clone()]]);
+    assert_eq!(captures["assertion"], paths);
+
+    let pattern = Pattern::traverse(vec![
+        number_above(40),
+        Pattern::capture("object", Pattern::any_object()),
+    ]);
+    let (paths, captures) = pattern.paths_with_captures(&envelope);
+    assert_eq!(paths, vec![vec![retires.clone(), Envelope::new(65)]]);
+    assert_eq!(captures["object"], vec![vec![Envelope::new(65)]]);
+
+    // Inside a search, the capture holds the matched assertion.
+    let pattern = Pattern::search(Pattern::capture("found", number_above(40)));
+    let (paths, captures) = pattern.paths_with_captures(&envelope);
+    assert_eq!(paths.len(), 1);
+    assert_eq!(captures["found"], vec![vec![retires]]);
+}
+
+#[test]
+fn test_assertion_where_cannot_be_encoded() {
+    // The function has no expression syntax, so it does not survive a CBOR
+    // round trip.
+    assert!(matches!(
+        number_above(40).to_cbor(),
+        Err(bc_envelope_pattern::Error::UnencodablePattern(_))
+    ));
+}
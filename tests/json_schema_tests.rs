@@ -0,0 +1,122 @@
+#![cfg(feature = "interop-json-schema")]
+
+use bc_envelope::prelude::*;
+use bc_envelope_pattern::{Error, Matcher, Pattern};
+use indoc::indoc;
+
+#[test]
+fn test_json_schema_scalar_types() {
+    let cases = [
+        (r#"{"type": "string"}"#, Pattern::any_text()),
+        (r#"{"type": "number"}"#, Pattern::any_number()),
+        (r#"{"type": "boolean"}"#, Pattern::any_bool()),
+        (r#"{"type": "null"}"#, Pattern::null()),
+        (
+            r#"{"type": ["string", "null"]}"#,
+            Pattern::or(vec![Pattern::any_text(), Pattern::null()]),
+        ),
+        (
+            r#"{"enum": ["red", "green"]}"#,
+            Pattern::or(vec![Pattern::text("red"), Pattern::text("green")]),
+        ),
+        (r#"{"const": 42}"#, Pattern::number(42)),
+        ("true", Pattern::any()),
+        ("{}", Pattern::any()),
+    ];
+    for (schema, expected) in cases {
+        assert_eq!(Pattern::from_json_schema(schema).unwrap(), expected);
+    }
+
+    let age = Pattern::from_json_schema(
+        r#"{"type": "integer", "minimum": 0, "exclusiveMaximum": 150}"#,
+    )
+    .unwrap();
+    assert_eq!(age.to_string(), "number & >=0 & <150");
+    assert!(age.matches(&Envelope::new(30)));
+    assert!(!age.matches(&Envelope::new(-1)));
+    assert!(!age.matches(&Envelope::new(150)));
+    assert!(!age.matches(&Envelope::new("30")));
+
+    assert!(
+        !Pattern::from_json_schema("false")
+            .unwrap()
+            .matches(&Envelope::new(1))
+    );
+}
+
+#[test]
+fn test_json_schema_object() {
+    let schema = indoc! {r#"
+        {
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "age": { "type": "number", "minimum": 0 },
+                "address": {
+                    "type": "object",
+                    "properties": { "city": { "type": "string" } },
+                    "required": ["city"]
+                }
+            },
+            "required": ["name"]
+        }
+    "#};
+    let pattern = Pattern::from_json_schema(schema).unwrap();
+
+    let alice = Envelope::new("Alice")
+        .add_assertion("name", "Alice")
+        .add_assertion("age", 30);
+    assert!(pattern.matches(&alice));
+    assert_eq!(pattern.paths(&alice), vec![vec![alice.clone()]]);
+
+    // Nested objects are nodes.
+    let address = Envelope::new("home").add_assertion("city", "Paris");
+    assert!(pattern.matches(&alice.clone().add_assertion("address", address)));
+    assert!(!pattern.matches(&alice.clone().add_assertion("address", "Paris")));
+
+    // A required property is missing.
+    assert!(!pattern.matches(&Envelope::new("Bob").add_assertion("age", 30)));
+    // A property has the wrong type or is out of range.
+    assert!(!pattern.matches(&alice.clone().add_assertion("name", 7)));
+    assert!(
+        !pattern.matches(
+            &Envelope::new("Carol")
+                .add_assertion("name", "Carol")
+                .add_assertion("age", -1)
+        )
+    );
+    // Additional properties are allowed by default.
+    assert!(
+        pattern.matches(&alice.clone().add_assertion("email", "a@example.com"))
+    );
+
+    let closed = Pattern::from_json_schema(
+        r#"{"properties": {"name": {"type": "string"}}, "additionalProperties": false}"#,
+    )
+    .unwrap();
+    assert!(closed.matches(&Envelope::new("Dan").add_assertion("name", "Dan")));
+    assert!(!closed.matches(&alice));
+}
+
+#[test]
+fn test_json_schema_errors() {
+    for schema in [
+        "not json",
+        "42",
+        r#"{"type": "date"}"#,
+        r#"{"type": 1}"#,
+        r#"{"required": "name"}"#,
+        r#"{"required": [1]}"#,
+        r#"{"properties": []}"#,
+        r#"{"enum": [[1]]}"#,
+        r#"{"minimum": "0"}"#,
+    ] {
+        assert!(
+            matches!(
+                Pattern::from_json_schema(schema),
+                Err(Error::InvalidJsonSchema(_))
+            ),
+            "{schema}"
+        );
+    }
+}
@@ -7,3 +7,103 @@ fn parse_any() {
     assert_eq!(p, Pattern::any());
     assert_eq!(p.to_string(), src);
 }
+
+#[test]
+fn parse_with_fallback() {
+    let src = r#"search("Bob")"#;
+    let expected = Pattern::parse(src).unwrap();
+    assert_eq!(Pattern::try_parse_or_any(src), expected);
+    assert_eq!(Pattern::try_parse_or_none(src), expected);
+
+    let never = Pattern::not_matching(Pattern::any());
+    for src in ["node(", "@@@", ""] {
+        assert_eq!(Pattern::try_parse_or_any(src), Pattern::any(), "{src:?}");
+        assert_eq!(Pattern::try_parse_or_none(src), never, "{src:?}");
+    }
+}
+
+#[test]
+fn parse_path_spec() {
+    let cases = vec![
+        ("subject", Pattern::any_subject()),
+        ("predicate", Pattern::any_predicate()),
+        ("object", Pattern::any_object()),
+        ("wrapped", Pattern::wrapped()),
+        ("unwrap", Pattern::unwrap()),
+        ("assertion", Pattern::any_assertion()),
+        (
+            "assertion[pred='age']",
+            Pattern::assertion_with_predicate(Pattern::text("age")),
+        ),
+        (
+            r#"assertion[obj="Bob"]"#,
+            Pattern::assertion_with_object(Pattern::text("Bob")),
+        ),
+        (
+            "assertion[obj=>=18]",
+            Pattern::assertion_with_object(
+                Pattern::number_greater_than_or_equal(18),
+            ),
+        ),
+        (
+            "assertion[pred='knows'][obj=text]",
+            Pattern::assertion_with_predicate_and_object(
+                Pattern::text("knows"),
+                Pattern::any_text(),
+            ),
+        ),
+        (
+            "subject/assertion[pred='age']/object",
+            Pattern::traverse(vec![
+                Pattern::any_subject(),
+                Pattern::assertion_with_predicate(Pattern::text("age")),
+                Pattern::any_object(),
+            ]),
+        ),
+        (
+            " unwrap / assertion [ pred = 'a/b' ] / object ",
+            Pattern::traverse(vec![
+                Pattern::unwrap(),
+                Pattern::assertion_with_predicate(Pattern::text("a/b")),
+                Pattern::any_object(),
+            ]),
+        ),
+        (
+            "wrapped/unwrap/subject",
+            Pattern::traverse(vec![
+                Pattern::wrapped(),
+                Pattern::unwrap(),
+                Pattern::any_subject(),
+            ]),
+        ),
+    ];
+    for (spec, expected) in cases {
+        assert_eq!(Pattern::at_path(spec).unwrap(), expected, "{spec}");
+    }
+
+    let invalid = [
+        "",
+        "subject//object",
+        "subject/",
+        "node",
+        "subject[pred='a']",
+        "assertion[subj='a']",
+        "assertion[pred='a'][pred='b']",
+        "assertion[pred]",
+        "assertion[pred='a'",
+        "assertion]",
+        "assertion[pred='a]",
+        "assertion[pred='a']x",
+    ];
+    for spec in invalid {
+        assert!(
+            matches!(
+                Pattern::at_path(spec),
+                Err(bc_envelope_pattern::Error::InvalidPathSpec(_))
+            ),
+            "{spec}"
+        );
+    }
+    // A value that is not a valid pattern expression.
+    assert!(Pattern::at_path("assertion[obj=node(]").is_err());
+}
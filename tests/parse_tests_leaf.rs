@@ -1,5 +1,5 @@
 use bc_envelope::prelude::*;
-use bc_envelope_pattern::{DCBORPattern, Pattern};
+use bc_envelope_pattern::{DCBORPattern, Error, Matcher, Pattern};
 use known_values::KnownValue;
 mod common;
 
@@ -54,6 +54,120 @@ fn parse_text_dcbor_pattern_syntax() {
     assert_actual_expected!(p.to_string(), r#""say \"hello\"""#);
 }
 
+#[test]
+fn parse_text_glob() {
+    let p = Pattern::parse(r#"text_glob("Alice*")"#).unwrap();
+    assert_eq!(p, Pattern::text_matches_glob("Alice*").unwrap());
+    assert_actual_expected!(p.to_string(), r#"text_glob("Alice*")"#);
+    assert!(p.matches(&Envelope::new("Alice Smith")));
+
+    let p = Pattern::parse(r#"text_glob ( "a\"?" )"#).unwrap();
+    assert_actual_expected!(p.to_string(), r#"text_glob("a\"?")"#);
+    assert!(p.matches(&Envelope::new("a\"b")));
+
+    assert!(Pattern::parse("text_glob").is_err());
+    assert!(Pattern::parse("text_glob(Alice)").is_err());
+}
+
+#[test]
+fn parse_text_prefix_and_suffix() {
+    let p = Pattern::parse(r#"text(^"Al")"#).unwrap();
+    assert_eq!(p, Pattern::text_starts_with("Al"));
+    assert_actual_expected!(p.to_string(), r#"text(^"Al")"#);
+
+    let p = Pattern::parse(r#"text ( "ce" $ )"#).unwrap();
+    assert_eq!(p, Pattern::text_ends_with("ce"));
+    assert_actual_expected!(p.to_string(), r#"text("ce"$)"#);
+
+    let p = Pattern::parse(r#"text(^"a\"^")"#).unwrap();
+    assert_eq!(p, Pattern::text_starts_with("a\"^"));
+    assert_eq!(Pattern::parse(p.to_string()).unwrap(), p);
+
+    assert!(Pattern::parse(r#"text(^"Al""#).is_err());
+    assert!(Pattern::parse(r#"text(^Al)"#).is_err());
+    assert!(Pattern::parse(r#"text("ce")"#).is_err());
+    assert!(Pattern::parse(r#"text("ce"$"#).is_err());
+}
+
+#[test]
+fn parse_text_length() {
+    let p = Pattern::parse("text(len: 5)").unwrap();
+    assert_eq!(p, Pattern::text_length(5));
+    assert_actual_expected!(p.to_string(), "text(len: 5)");
+
+    let p = Pattern::parse("text( len:{3, 10} )").unwrap();
+    assert_eq!(p, Pattern::text_length_range(3..=10).unwrap());
+    assert_actual_expected!(p.to_string(), "text(len: {3,10})");
+
+    let p = Pattern::parse("text(len: {4,})").unwrap();
+    assert_eq!(p, Pattern::text_length_range(4..).unwrap());
+    assert_actual_expected!(p.to_string(), "text(len: {4,})");
+
+    assert!(Pattern::parse("text(len: -1)").is_err());
+    assert!(Pattern::parse("text(len: 5").is_err());
+    assert!(Pattern::parse(r#"text(len: "5")"#).is_err());
+    // An inverted range is rejected.
+    assert!(Pattern::parse("text(len: {5,3})").is_err());
+}
+
+#[test]
+fn parse_text_one_of() {
+    let expected = Pattern::text_matches_any(["a", "b", "c"]);
+    let p = Pattern::parse(r#"text(one_of: ["a", "b", "c"])"#).unwrap();
+    assert_eq!(p, expected);
+    assert_actual_expected!(p.to_string(), r#"text(one_of: ["a", "b", "c"])"#);
+
+    // Brackets are optional, and values are kept as a set.
+    let p = Pattern::parse(r#"text(one_of:"c","a","b","a")"#).unwrap();
+    assert_eq!(p, expected);
+
+    let p = Pattern::parse("text(one_of: [])").unwrap();
+    assert_eq!(p, Pattern::text_matches_any(Vec::<String>::new()));
+    assert_eq!(Pattern::parse(p.to_string()).unwrap(), p);
+
+    assert!(Pattern::parse(r#"text("a")"#).is_err());
+    assert!(Pattern::parse(r#"text(one_of: "a", 1)"#).is_err());
+    assert!(Pattern::parse(r#"text(one_of: ["a")"#).is_err());
+}
+
+#[test]
+fn parse_number_one_of() {
+    let expected = Pattern::number_matches_any([1.0, 2.0, 3.0]);
+    let p = Pattern::parse("number(one_of: [1, 2, 3])").unwrap();
+    assert_eq!(p, expected);
+    assert_actual_expected!(p.to_string(), "number(one_of: [1, 2, 3])");
+
+    // Brackets are optional.
+    let p = Pattern::parse("number(one_of:1,2,3)").unwrap();
+    assert_eq!(p, expected);
+
+    let p =
+        Pattern::parse("number(one_of: [-1, 2.5, NaN, Infinity, -Infinity])")
+            .unwrap();
+    assert_eq!(
+        p,
+        Pattern::number_matches_any([
+            -1.0,
+            2.5,
+            f64::NAN,
+            f64::INFINITY,
+            f64::NEG_INFINITY
+        ])
+    );
+    assert_eq!(Pattern::parse(p.to_string()).unwrap(), p);
+
+    assert!(Pattern::parse("number(1)").is_err());
+    assert!(Pattern::parse(r#"number(one_of: 1, "a")"#).is_err());
+
+    // `<-` before a digit lexes as `<` and a negative number, not as a
+    // back traversal.
+    assert_eq!(
+        Pattern::parse("number(<-5)"),
+        Pattern::parse("number(< -5)")
+    );
+    assert_eq!(Pattern::parse("<-5").unwrap(), Pattern::number_less_than(-5));
+}
+
 #[test]
 fn parse_number_patterns() {
     // Test dcbor-pattern syntax
@@ -94,14 +208,83 @@ fn parse_number_patterns() {
     assert_actual_expected!(p.to_string(), "NaN");
 
     let p = Pattern::parse("Infinity").unwrap();
-    assert_eq!(p, Pattern::number(f64::INFINITY));
-    // Note: dcbor-pattern displays infinity as "inf"
-    assert_actual_expected!(p.to_string(), "inf");
+    assert_eq!(p, Pattern::number_positive_infinity());
+    assert_actual_expected!(p.to_string(), "Infinity");
+    assert_eq!(Pattern::parse(p.to_string()).unwrap(), p);
 
     let p = Pattern::parse("-Infinity").unwrap();
-    assert_eq!(p, Pattern::number(f64::NEG_INFINITY));
-    // Note: dcbor-pattern displays negative infinity as "-inf"
-    assert_actual_expected!(p.to_string(), "-inf");
+    assert_eq!(p, Pattern::number_negative_infinity());
+    assert_actual_expected!(p.to_string(), "-Infinity");
+    assert_eq!(Pattern::parse(p.to_string()).unwrap(), p);
+
+    let p = Pattern::parse("number(infinite)").unwrap();
+    assert_eq!(p, Pattern::number_is_infinite());
+    assert_actual_expected!(p.to_string(), "number(infinite)");
+    assert_eq!(Pattern::parse("number( infinite )").unwrap(), p);
+    assert!(Pattern::parse("number(infinite").is_err());
+
+    let p = Pattern::parse("number(finite)").unwrap();
+    assert_eq!(p, Pattern::number_is_finite());
+    assert_actual_expected!(p.to_string(), "number(finite)");
+    assert!(Pattern::parse("number(finite").is_err());
+}
+
+#[test]
+fn parse_number_divisible_by() {
+    let p = Pattern::parse("number(div: 7)").unwrap();
+    assert_eq!(p, Pattern::number_divisible_by(7).unwrap());
+    assert_actual_expected!(p.to_string(), "number(div: 7)");
+
+    let p = Pattern::parse("number( div:0.5 )").unwrap();
+    assert_eq!(p, Pattern::number_divisible_by(0.5).unwrap());
+    assert_actual_expected!(p.to_string(), "number(div: 0.5)");
+
+    for source in [
+        "number(div: 0)",
+        "number(div: 0.0)",
+        "number(div: -0.0)",
+        "number(div: NaN)",
+        "number(div: Infinity)",
+        "number(div: -Infinity)",
+    ] {
+        assert!(
+            matches!(Pattern::parse(source), Err(Error::InvalidDivisor(_))),
+            "{source}"
+        );
+    }
+    assert!(Pattern::parse("number(div: 7").is_err());
+}
+
+#[test]
+fn parse_integer_patterns() {
+    let p = Pattern::parse("integer").unwrap();
+    assert_eq!(p, Pattern::any_integer());
+    assert_actual_expected!(p.to_string(), "integer");
+
+    let p = Pattern::parse("integer(42)").unwrap();
+    assert_eq!(p, Pattern::integer(42));
+    assert_actual_expected!(p.to_string(), "integer(42)");
+
+    let p = Pattern::parse("integer(-7)").unwrap();
+    assert_eq!(p, Pattern::integer(-7));
+    assert_actual_expected!(p.to_string(), "integer(-7)");
+
+    let p = Pattern::parse("integer(-5...5)").unwrap();
+    assert_eq!(p, Pattern::integer_range(-5..=5));
+    assert_actual_expected!(p.to_string(), "integer(-5...5)");
+
+    let p = Pattern::parse("integer(>0)").unwrap();
+    assert_eq!(p, Pattern::integer_greater_than(0));
+    assert_actual_expected!(p.to_string(), "integer(>0)");
+
+    let p = Pattern::parse("integer(<-3)").unwrap();
+    assert_eq!(p, Pattern::integer_less_than(-3));
+    assert_actual_expected!(p.to_string(), "integer(<-3)");
+
+    assert!(Pattern::parse("integer(2.5)").is_err());
+    assert!(Pattern::parse("integer(>=1)").is_err());
+    assert!(Pattern::parse("integer(42").is_err());
+    assert!(Pattern::parse("integer(18446744073709551615)").is_err());
 }
 
 #[test]
@@ -109,6 +292,15 @@ fn parse_leaf_pattern() {
     let p = Pattern::parse("leaf").unwrap();
     assert_eq!(p, Pattern::leaf());
     assert_actual_expected!(p.to_string(), "leaf");
+
+    let p = Pattern::parse("leaf(42)").unwrap();
+    assert_eq!(p, Pattern::leaf_with_value(42));
+    assert_actual_expected!(p.to_string(), "leaf & cbor(42)");
+    assert_eq!(Pattern::parse(p.to_string()).unwrap(), p);
+
+    let p = Pattern::parse(r#"leaf ( "Alice" )"#).unwrap();
+    assert_eq!(p, Pattern::leaf_with_value("Alice"));
+    assert_actual_expected!(p.to_string(), r#"leaf & cbor("Alice")"#);
 }
 
 #[test]
@@ -130,6 +322,34 @@ fn parse_array_patterns() {
     assert_actual_expected!(p.to_string(), "[{2,}]");
 }
 
+#[test]
+fn parse_array_at_patterns() {
+    let p = Pattern::parse("arr[0: text]").unwrap();
+    assert_eq!(p, Pattern::cbor_array_at(0, DCBORPattern::any_text()));
+    assert_actual_expected!(p.to_string(), "arr[0:text]");
+    assert_eq!(Pattern::parse(p.to_string()).unwrap(), p);
+
+    let p = Pattern::parse(r#"arr[2: "x"]"#).unwrap();
+    assert_eq!(p, Pattern::cbor_array_at(2, DCBORPattern::text("x")));
+
+    // Nested array elements
+    let p = Pattern::parse("arr[1: [number, [text]]]").unwrap();
+    assert_actual_expected!(p.to_string(), "arr[1:[number, [text]]]");
+    assert!(p.matches(&Envelope::new(
+        vec![0.to_cbor(), vec![1.to_cbor(), vec!["a"].to_cbor()].to_cbor()]
+            .to_cbor()
+    )));
+
+    // A `]` inside a regex does not close the element pattern.
+    let p = Pattern::parse("arr[0: /a]b/]").unwrap();
+    assert_actual_expected!(p.to_string(), "arr[0:/a]b/]");
+    assert_eq!(Pattern::parse(p.to_string()).unwrap(), p);
+    assert!(p.matches(&Envelope::new(vec!["a]b"].to_cbor())));
+
+    assert!(Pattern::parse("arr[0]").is_err());
+    assert!(Pattern::parse("arr[0: text").is_err());
+}
+
 #[test]
 fn parse_bstr_patterns() {
     let p = Pattern::parse("bstr").unwrap();
@@ -177,6 +397,28 @@ fn parse_date_patterns() {
     let regex = regex::Regex::new("2023-.*").unwrap();
     assert_eq!(p, Pattern::date_regex(regex));
     assert_actual_expected!(p.to_string(), "date'/2023-.*/'");
+
+    let p = Pattern::parse("date'year:2024'").unwrap();
+    assert_eq!(p, Pattern::date_in_year(2024));
+    assert_actual_expected!(p.to_string(), "date'/^2024-/'");
+    assert_eq!(Pattern::parse(p.to_string()).unwrap(), p);
+
+    let p = Pattern::parse("date'month:01'").unwrap();
+    assert_eq!(p, Pattern::date_in_month(1));
+    assert_actual_expected!(p.to_string(), r"date'/^-?\d{4,}-01-/'");
+    assert_eq!(Pattern::parse(p.to_string()).unwrap(), p);
+
+    for src in [
+        "date'year:'",
+        "date'year:0'",
+        "date'year:10000'",
+        "date'year:-1'",
+        "date'month:00'",
+        "date'month:13'",
+        "date'month:x'",
+    ] {
+        assert!(Pattern::parse(src).is_err(), "{src} should not parse");
+    }
 }
 
 #[test]
@@ -196,6 +438,44 @@ fn parse_map_patterns() {
     // dcbor-pattern map syntax - at least N
     let p = Pattern::parse("{{2,}}").unwrap();
     assert_actual_expected!(p.to_string(), "{{2,}}");
+
+    let p = Pattern::parse(r#"map(key:"age")"#).unwrap();
+    assert_eq!(p, Pattern::map_with_required_key(DCBORPattern::text("age")));
+    assert_actual_expected!(p.to_string(), r#"map(key: "age")"#);
+
+    let p = Pattern::parse(r#"map(key: "age", val: number)"#).unwrap();
+    assert_eq!(
+        p,
+        Pattern::map_with_required_key_value(
+            DCBORPattern::text("age"),
+            DCBORPattern::any_number()
+        )
+    );
+    assert_actual_expected!(p.to_string(), r#"map(key: "age", val: number)"#);
+
+    // Key and value patterns can contain commas and parentheses.
+    let p = Pattern::parse(r#"map(key: "a, b", val: tagged(1, [text]))"#)
+        .unwrap();
+    assert_actual_expected!(
+        p.to_string(),
+        r#"map(key: "a, b", val: tagged(1, [text]))"#
+    );
+    assert_eq!(Pattern::parse(p.to_string()).unwrap(), p);
+
+    // Delimiters inside regex, known value and byte string literals do not
+    // end the pattern.
+    for src in [
+        r"map(key: /a\)b/)",
+        "map(key: 'a)b')",
+        "map(key: h'29', val: /x,y/)",
+    ] {
+        let p = Pattern::parse(src).unwrap();
+        assert_actual_expected!(p.to_string(), src);
+        assert_eq!(Pattern::parse(p.to_string()).unwrap(), p);
+    }
+
+    assert!(Pattern::parse(r#"map(val: number)"#).is_err());
+    assert!(Pattern::parse(r#"map(key: "age""#).is_err());
 }
 
 #[test]
@@ -256,12 +536,47 @@ fn parse_known_value_patterns() {
     assert_eq!(p, Pattern::known_value_named("date"));
     assert_actual_expected!(p.to_string(), "'date'");
 
+    // Unregistered names still parse, matching by name at match time.
+    let p = Pattern::parse("'unknown_name'").unwrap();
+    assert_eq!(p, Pattern::known_value_named("unknown_name"));
+    assert_actual_expected!(p.to_string(), "'unknown_name'");
+
     let p = Pattern::parse("'/da.*/'").unwrap();
     let regex = regex::Regex::new("da.*").unwrap();
     assert_eq!(p, Pattern::known_value_regex(regex));
     assert_actual_expected!(p.to_string(), "'/da.*/'");
 }
 
+#[test]
+fn parse_known_value_or_text_patterns() {
+    let p = Pattern::parse(r#"kv_or_text("isA")"#).unwrap();
+    assert_eq!(p, Pattern::known_value_or_text("isA"));
+    assert_actual_expected!(p.to_string(), r#"'isA' | "isA""#);
+    assert_eq!(Pattern::parse(p.to_string()).unwrap(), p);
+
+    // Nested in a traversal, the alternation is parenthesized.
+    let p = Pattern::parse(r#"node -> kv_or_text("x")"#).unwrap();
+    assert_eq!(
+        p,
+        Pattern::traverse(vec![
+            Pattern::any_node(),
+            Pattern::known_value_or_text("x"),
+        ])
+    );
+    assert_actual_expected!(p.to_string(), r#"node -> ('x' | "x")"#);
+    assert_eq!(Pattern::parse(p.to_string()).unwrap(), p);
+    let p = Pattern::and(vec![
+        Pattern::known_value_or_text("x"),
+        Pattern::leaf(),
+    ]);
+    assert_actual_expected!(p.to_string(), r#"('x' | "x") & leaf"#);
+    assert_eq!(Pattern::parse(p.to_string()).unwrap(), p);
+
+    assert!(Pattern::parse("kv_or_text('isA')").is_err());
+    assert!(Pattern::parse(r#"kv_or_text("isA""#).is_err());
+    assert!(Pattern::parse("kv_or_text").is_err());
+}
+
 #[test]
 fn parse_cbor_patterns() {
     let cases: Vec<(&str, CBOR)> = vec![
@@ -284,6 +599,57 @@ fn parse_cbor_patterns() {
     }
 }
 
+#[test]
+fn parse_cbor_dcbor_patterns() {
+    bc_envelope::register_tags();
+
+    let mut map = Map::new();
+    map.insert("a", 1);
+    let cases: Vec<(&str, CBOR, CBOR)> = vec![
+        // Number values, ranges, and comparisons
+        ("number", 42.into(), "42".into()),
+        ("42", 42.into(), 43.into()),
+        ("1...10", 5.into(), 11.into()),
+        (">5", 6.into(), 5.into()),
+        ("<=5", 5.into(), 6.into()),
+        // Text values and regexes, whose delimiters are escaped
+        (r#""hi""#, "hi".into(), "ho".into()),
+        ("/^h/", "hi".into(), "oh".into()),
+        // Arrays
+        ("array", vec![1].into(), 1.into()),
+        ("[*]", vec![1].into(), vec![1, 2].into()),
+        (
+            "[number, text]",
+            vec![1.to_cbor(), "a".to_cbor()].into(),
+            vec![1, 2].into(),
+        ),
+        // Maps
+        ("map", map.clone().into(), vec![1].into()),
+        (r#"{"a": number}"#, map.clone().into(), Map::new().into()),
+        // Tags
+        ("tagged(1, number)", CBOR::to_tagged_value(1, 2), 2.into()),
+        // Captures and meta patterns
+        ("@n(number)", 42.into(), "42".into()),
+        ("search(text)", vec!["a"].into(), vec![1].into()),
+        ("number | text", "a".into(), true.into()),
+        ("!number", "a".into(), 1.into()),
+    ];
+
+    for (inner, hit, miss) in cases {
+        let src = format!("cbor(/{}/)", inner.replace('/', "\\/"));
+        let p = Pattern::parse(&src).unwrap_or_else(|e| panic!("{src}: {e:?}"));
+        let expected =
+            Pattern::cbor_pattern(DCBORPattern::parse(inner).unwrap());
+        assert_eq!(p, expected, "{src}");
+        assert_actual_expected!(p.to_string(), src);
+        assert!(p.matches(&Envelope::new(hit)), "{src}");
+        assert!(!p.matches(&Envelope::new(miss)), "{src}");
+    }
+
+    assert!(Pattern::parse("cbor(/number").is_err());
+    assert!(Pattern::parse("cbor(/uint/)").is_err());
+}
+
 #[test]
 fn parse_cbor_patterns_2() {
     bc_envelope::register_tags();
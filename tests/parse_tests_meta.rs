@@ -1,4 +1,4 @@
-use bc_envelope_pattern::{Pattern, Reluctance};
+use bc_envelope_pattern::{Pattern, Reluctance, parse_pattern_lenient};
 
 #[test]
 fn parse_bool_or() {
@@ -81,6 +81,75 @@ fn parse_operator_precedence() {
     );
 }
 
+#[test]
+fn parse_priority_or_patterns() {
+    let src = "prio(10, text) | prio(1, number)";
+    let p = Pattern::parse(src).unwrap();
+    assert_eq!(
+        p,
+        Pattern::or_with_priority(vec![
+            (10, Pattern::any_text()),
+            (1, Pattern::any_number()),
+        ])
+    );
+    assert_eq!(p.to_string(), src);
+
+    // Branches are sorted, and a branch without a priority gets priority 0.
+    let p = Pattern::parse("bool | prio(1, number) | prio(5, text)").unwrap();
+    assert_eq!(
+        p.to_string(),
+        "prio(5, text) | prio(1, number) | prio(0, bool)"
+    );
+
+    // A branch may itself be an alternation.
+    let p = Pattern::parse("prio(2, text | number)").unwrap();
+    assert_eq!(
+        p,
+        Pattern::or_with_priority(vec![(
+            2,
+            Pattern::or(vec![Pattern::any_text(), Pattern::any_number()])
+        )])
+    );
+    assert_eq!(p.to_string(), "prio(2, text | number)");
+
+    assert!(Pattern::parse("prio(-1, text)").is_err());
+    assert!(Pattern::parse("prio(text)").is_err());
+    assert!(Pattern::parse("prio(1, text").is_err());
+}
+
+#[test]
+fn parse_while_patterns() {
+    let src = "while(wrapped, unwrap)";
+    let p = Pattern::parse(src).unwrap();
+    assert_eq!(
+        p,
+        Pattern::traverse_while(Pattern::wrapped(), Pattern::unwrap())
+    );
+    assert_eq!(p.to_string(), src);
+
+    // Either argument may be an alternation or a traversal.
+    let src = "while(node | wrapped, unwrap | assert -> obj)";
+    let p = Pattern::parse(src).unwrap();
+    assert_eq!(
+        p,
+        Pattern::traverse_while(
+            Pattern::or(vec![Pattern::any_node(), Pattern::wrapped()]),
+            Pattern::or(vec![
+                Pattern::unwrap(),
+                Pattern::traverse(vec![
+                    Pattern::any_assertion(),
+                    Pattern::any_object(),
+                ]),
+            ]),
+        )
+    );
+    assert_eq!(p.to_string(), src);
+
+    assert!(Pattern::parse("while(wrapped)").is_err());
+    assert!(Pattern::parse("while(wrapped, unwrap").is_err());
+    assert!(Pattern::parse("while wrapped").is_err());
+}
+
 #[test]
 fn parse_not_patterns() {
     let p = Pattern::parse(r#"!"hi""#).unwrap();
@@ -104,6 +173,31 @@ fn parse_search_pattern() {
     assert_eq!(p.to_string(), "search(text)");
 }
 
+#[test]
+fn parse_count_pattern() {
+    let p = Pattern::parse("count(2, search(text))").unwrap();
+    assert_eq!(p, Pattern::search_count_equals(Pattern::any_text(), 2));
+    assert_eq!(p.to_string(), "count(2, search(text))");
+
+    let p = Pattern::parse("count({1,3}, search(number))").unwrap();
+    assert_eq!(
+        p,
+        Pattern::search_count(
+            Pattern::search(Pattern::any_number()),
+            1,
+            Some(3)
+        )
+    );
+    assert_eq!(p.to_string(), "count({1,3}, search(number))");
+
+    let p = Pattern::parse("count({2,}, search(text))").unwrap();
+    assert_eq!(p.to_string(), "count({2,}, search(text))");
+
+    assert!(Pattern::parse("count(search(text))").is_err());
+    assert!(Pattern::parse("count(-1, search(text))").is_err());
+    assert!(Pattern::parse("count(2, search(text)").is_err());
+}
+
 #[test]
 fn parse_repeat_patterns() {
     let p = Pattern::parse("(wrapped)*").unwrap();
@@ -178,3 +272,118 @@ fn parse_any_with_star_syntax() {
     );
     assert_eq!(complex.to_string(), "* & true");
 }
+
+#[test]
+fn parse_batch_patterns() {
+    let sources = ["text", "number & ", r#""Alice""#, "date'nope'", "*"];
+    let results = Pattern::parse_batch(&sources);
+    assert_eq!(results.len(), sources.len());
+    let failed: Vec<usize> = results
+        .iter()
+        .enumerate()
+        .filter_map(|(i, result)| result.is_err().then_some(i))
+        .collect();
+    assert_eq!(failed, vec![1, 3]);
+    assert_eq!(results[0].as_ref().unwrap(), &Pattern::any_text());
+    assert_eq!(results[2].as_ref().unwrap(), &Pattern::text("Alice"));
+    assert_eq!(results[4].as_ref().unwrap(), &Pattern::any());
+
+    assert!(Pattern::parse_batch(&[]).is_empty());
+
+    let patterns =
+        Pattern::parse_batch_all_ok(&["text", r#""Alice""#, "*"]).unwrap();
+    assert_eq!(
+        patterns,
+        vec![Pattern::any_text(), Pattern::text("Alice"), Pattern::any()]
+    );
+    let error = Pattern::parse_batch_all_ok(&sources).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        results[1].as_ref().unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn parse_pattern_lenient_recovers() {
+    // Well-formed input parses exactly as `Pattern::parse` does.
+    let (pattern, errors) = parse_pattern_lenient("text & \"Alice\"");
+    assert_eq!(
+        pattern,
+        Some(Pattern::and(vec![
+            Pattern::any_text(),
+            Pattern::text("Alice")
+        ]))
+    );
+    assert!(errors.is_empty());
+
+    // One bad operand in an `and`-list is skipped and reported.
+    let (pattern, errors) =
+        parse_pattern_lenient("text & date'not-a-date' & \"Alice\"");
+    assert_eq!(
+        pattern,
+        Some(Pattern::and(vec![
+            Pattern::any_text(),
+            Pattern::text("Alice")
+        ]))
+    );
+    assert_eq!(errors.len(), 1);
+
+    // Recovery also works within an `or`-list.
+    let (pattern, errors) = parse_pattern_lenient("42 | ) | true");
+    assert_eq!(
+        pattern,
+        Some(Pattern::or(vec![Pattern::number(42), Pattern::bool(true)]))
+    );
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn parse_pattern_lenient_unparseable() {
+    let (pattern, errors) = parse_pattern_lenient(")");
+    assert!(pattern.is_none());
+    assert!(!errors.is_empty());
+
+    let (pattern, errors) = parse_pattern_lenient("");
+    assert!(pattern.is_none());
+    assert!(!errors.is_empty());
+}
+
+#[test]
+fn parse_traverse_back_pattern() {
+    let p = Pattern::parse("text <- node").unwrap();
+    assert_eq!(
+        p,
+        Pattern::traverse_back(Pattern::any_text(), Pattern::any_node())
+    );
+    assert_eq!(p.to_string(), "text <- node");
+
+    // `<-` groups everything on its left.
+    let p = Pattern::parse("node -> assert <- node").unwrap();
+    assert_eq!(
+        p,
+        Pattern::traverse_back(
+            Pattern::traverse(vec![
+                Pattern::any_node(),
+                Pattern::any_assertion()
+            ]),
+            Pattern::any_node()
+        )
+    );
+
+    let p = Pattern::parse("text <- node -> subj").unwrap();
+    assert_eq!(
+        p,
+        Pattern::traverse(vec![
+            Pattern::traverse_back(Pattern::any_text(), Pattern::any_node()),
+            Pattern::any_subject(),
+        ])
+    );
+    assert_eq!(Pattern::parse(p.to_string()).unwrap(), p);
+
+    // A leading `<-` is still a comparison with a negative number.
+    let p = Pattern::parse("<-5").unwrap();
+    assert_eq!(p, Pattern::number_less_than(-5));
+    assert_eq!(Pattern::parse(p.to_string()).unwrap(), p);
+
+    assert!(Pattern::parse("text <-").is_err());
+}
@@ -11,6 +11,92 @@ fn parse_node_patterns() {
     let p = Pattern::parse("node({1,3})").unwrap();
     assert_eq!(p, Pattern::node_with_assertions_range(1..=3));
     assert_eq!(p.to_string(), "node({1,3})");
+
+    let p = Pattern::parse("node(all: assert)").unwrap();
+    assert_eq!(
+        p,
+        Pattern::node_matching_all_assertions(Pattern::any_assertion())
+    );
+    assert_eq!(p.to_string(), "node(all: assert)");
+
+    let p = Pattern::parse(r#"node ( all : pred("knows") )"#).unwrap();
+    assert_eq!(
+        p,
+        Pattern::node_matching_all_assertions(Pattern::predicate(
+            Pattern::text("knows")
+        ))
+    );
+    assert_eq!(p.to_string(), r#"node(all: pred("knows"))"#);
+
+    let p = Pattern::parse(r#"node(preds_in: ["age", text])"#).unwrap();
+    assert_eq!(
+        p,
+        Pattern::all_assertions_have_predicate_in(vec![
+            Pattern::text("age"),
+            Pattern::any_text()
+        ])
+    );
+    assert_eq!(p.to_string(), r#"node(preds_in: ["age", text])"#);
+
+    let p = Pattern::parse("node(preds_in: [])").unwrap();
+    assert_eq!(p, Pattern::all_assertions_have_predicate_in(vec![]));
+    assert!(Pattern::parse(r#"node(preds_in: "age")"#).is_err());
+    assert!(Pattern::parse(r#"node(preds_in: ["age")"#).is_err());
+
+    let p = Pattern::parse("node(count(text): {2,5})").unwrap();
+    assert_eq!(
+        p,
+        Pattern::node_assertion_predicate_count(
+            Pattern::any_text(),
+            2,
+            Some(5)
+        )
+    );
+    assert_eq!(p.to_string(), "node(count(text): {2,5})");
+
+    let p = Pattern::parse(r#"node ( count ( "knows" ) : {2,} )"#).unwrap();
+    assert_eq!(
+        p,
+        Pattern::node_assertion_predicate_count(
+            Pattern::text("knows"),
+            2,
+            None
+        )
+    );
+    assert_eq!(p.to_string(), r#"node(count("knows"): {2,})"#);
+    assert!(Pattern::parse("node(count(text))").is_err());
+
+    let p = Pattern::parse(r#"node(exactly: [pred("age"), *])"#).unwrap();
+    assert_eq!(
+        p,
+        Pattern::node_with_exact_assertions(vec![
+            Pattern::predicate(Pattern::text("age")),
+            Pattern::any(),
+        ])
+    );
+    assert_eq!(p.to_string(), r#"node(exactly: [pred("age"), *])"#);
+    assert!(Pattern::parse("node(exactly: *)").is_err());
+    assert!(Pattern::parse("node(count(text): 2)").is_err());
+}
+
+#[test]
+fn parse_assertion_with_predicate_and_object() {
+    let p = Pattern::parse(r#"assert(pred: "name", obj: text)"#).unwrap();
+    assert_eq!(
+        p,
+        Pattern::assertion_with_predicate_and_object(
+            Pattern::text("name"),
+            Pattern::any_text()
+        )
+    );
+    assert_eq!(p.to_string(), r#"assert(pred: "name", obj: text)"#);
+
+    let p = Pattern::assertion_exact("age", 42);
+    assert_eq!(p.to_string(), r#"assert(pred: cbor("age"), obj: cbor(42))"#);
+    assert_eq!(Pattern::parse(p.to_string()).unwrap(), p);
+
+    assert!(Pattern::parse(r#"assert("name", text)"#).is_err());
+    assert!(Pattern::parse(r#"assert(pred: "name")"#).is_err());
 }
 
 #[test]
@@ -18,6 +104,30 @@ fn parse_wrapped_pattern() {
     let p = Pattern::parse("wrapped").unwrap();
     assert_eq!(p, Pattern::wrapped());
     assert_eq!(p.to_string(), "wrapped");
+
+    let p = Pattern::parse("wrapped(content: text)").unwrap();
+    assert_eq!(p, Pattern::unwrap_matching(Pattern::any_text()));
+    assert_eq!(p.to_string(), "unwrap(text)");
+
+    let p = Pattern::parse("wrapped(match: text)").unwrap();
+    assert_eq!(p, Pattern::wrapped_content_matching(Pattern::any_text()));
+    assert_eq!(p.to_string(), "wrapped(match: text)");
+
+    let p = Pattern::parse("wrapped ( match:node )").unwrap();
+    assert_eq!(p, Pattern::wrapped_content_matching(Pattern::any_node()));
+    assert_eq!(p.to_string(), "wrapped(match: node)");
+
+    // Display output parses back to the same pattern.
+    for src in [
+        "wrapped(content: node)",
+        "wrapped(match: text | 42)",
+        r#"wrapped(match: wrapped(content: "Alice"))"#,
+    ] {
+        let p = Pattern::parse(src).unwrap();
+        assert_eq!(Pattern::parse(p.to_string()).unwrap(), p);
+    }
+
+    assert!(Pattern::parse("wrapped(text)").is_err());
 }
 
 #[test]
@@ -31,6 +141,25 @@ fn parse_unwrap_pattern() {
     assert_eq!(p.to_string(), "unwrap(node)");
 }
 
+#[test]
+fn parse_untag_pattern() {
+    let p = Pattern::parse(r#"untag(100, "Alice")"#).unwrap();
+    assert_eq!(
+        p,
+        Pattern::tag_with_envelope_content(100, Pattern::text("Alice"))
+    );
+    assert_eq!(p.to_string(), r#"untag(100, "Alice")"#);
+
+    let p = Pattern::parse("untag( 40000 , node -> subj )").unwrap();
+    assert_eq!(p.to_string(), "untag(40000, node -> subj)");
+    assert_eq!(Pattern::parse(p.to_string()).unwrap(), p);
+
+    assert!(Pattern::parse("untag").is_err());
+    assert!(Pattern::parse("untag(text)").is_err());
+    assert!(Pattern::parse("untag(-1, text)").is_err());
+    assert!(Pattern::parse("untag(100, text").is_err());
+}
+
 #[test]
 fn parse_subject_patterns() {
     let p = Pattern::parse("subj").unwrap();
@@ -81,6 +210,35 @@ fn parse_object_patterns() {
     assert_eq!(p_spaced.to_string(), r#"obj("hi")"#);
 }
 
+#[test]
+fn parse_nth_assertion_patterns() {
+    let src = r#"assert[0](pred("age"))"#;
+    let p = Pattern::parse(src).unwrap();
+    assert_eq!(
+        p,
+        Pattern::nth_assertion(0, Pattern::predicate(Pattern::text("age")))
+    );
+    assert_eq!(p.to_string(), src);
+
+    let p = Pattern::parse("assert [ 2 ] ( obj(number) | * )").unwrap();
+    assert_eq!(
+        p,
+        Pattern::nth_assertion(
+            2,
+            Pattern::or(vec![
+                Pattern::object(Pattern::any_number()),
+                Pattern::any()
+            ])
+        )
+    );
+    assert_eq!(p.to_string(), "assert[2](obj(number) | *)");
+
+    assert!(Pattern::parse("assert[-1](*)").is_err());
+    assert!(Pattern::parse("assert[1]").is_err());
+    assert!(Pattern::parse("assert[text](*)").is_err());
+    assert!(Pattern::parse("assert[0](*").is_err());
+}
+
 #[test]
 fn parse_predicate_patterns() {
     let p = Pattern::parse("pred").unwrap();
@@ -97,6 +255,35 @@ fn parse_predicate_patterns() {
     assert_eq!(p_spaced.to_string(), "pred(1)");
 }
 
+#[test]
+fn parse_predicate_or_subject_patterns() {
+    let p = Pattern::parse(r#"pred_or_subj("Alice")"#).unwrap();
+    assert_eq!(p, Pattern::predicate_or_subject(Pattern::text("Alice")));
+    assert_eq!(p.to_string(), r#"pred_or_subj("Alice")"#);
+    assert_eq!(Pattern::parse(p.to_string()).unwrap(), p);
+
+    // The spelled-out alternation is the same pattern.
+    let spelled = Pattern::parse(r#"pred("Alice") | subj("Alice")"#).unwrap();
+    assert_eq!(spelled, p);
+
+    // It needs no parentheses when nested.
+    let p = Pattern::parse(r#"node -> pred_or_subj("Alice") & leaf"#).unwrap();
+    assert_eq!(p.to_string(), r#"node -> pred_or_subj("Alice") & leaf"#);
+    assert_eq!(Pattern::parse(p.to_string()).unwrap(), p);
+
+    let p = Pattern::parse("pred_or_subj ( text | known )").unwrap();
+    assert_eq!(
+        p,
+        Pattern::predicate_or_subject(Pattern::or(vec![
+            Pattern::any_text(),
+            Pattern::any_known_value(),
+        ]))
+    );
+
+    assert!(Pattern::parse("pred_or_subj").is_err());
+    assert!(Pattern::parse("pred_or_subj(text").is_err());
+}
+
 #[test]
 fn parse_obscured_patterns() {
     let p = Pattern::parse("obscured").unwrap();
@@ -116,6 +303,33 @@ fn parse_obscured_patterns() {
     assert_eq!(p.to_string(), "compressed");
 }
 
+#[test]
+fn parse_case_patterns() {
+    for (name, expected) in [
+        ("node", Pattern::any_node()),
+        ("leaf", Pattern::leaf()),
+        ("wrapped", Pattern::wrapped()),
+        ("assertion", Pattern::any_assertion()),
+        ("elided", Pattern::elided()),
+        ("encrypted", Pattern::encrypted()),
+        ("compressed", Pattern::compressed()),
+    ] {
+        let p = Pattern::parse(format!(r#"case("{name}")"#)).unwrap();
+        assert_eq!(p, expected);
+        assert_eq!(p, Pattern::matches_case(name).unwrap());
+    }
+
+    let p = Pattern::parse(r#"search(case( "elided" ))"#).unwrap();
+    assert_eq!(p, Pattern::search(Pattern::elided()));
+
+    assert!(matches!(
+        Pattern::parse(r#"case("knownvalue")"#),
+        Err(bc_envelope_pattern::Error::UnknownEnvelopeCase(name)) if name == "knownvalue"
+    ));
+    assert!(Pattern::parse("case(leaf)").is_err());
+    assert!(Pattern::parse("case").is_err());
+}
+
 #[test]
 fn parse_digest_patterns() {
     let p = Pattern::parse("digest(a1b2c3)").unwrap();
@@ -0,0 +1,142 @@
+use bc_envelope::prelude::*;
+use bc_envelope_pattern::{Error, Pattern};
+
+#[test]
+fn test_pattern_cbor_round_trip() {
+    let sources = [
+        // Leaf patterns
+        "cbor",
+        "cbor([1, 2, {3: 4}])",
+        "cbor(/@num(number)/)",
+        r"cbor_bytes(h'/(?-u)^\x83/')",
+        "number",
+        "1...10",
+        ">=18",
+        "number(one_of: [1, 2.5, NaN])",
+        "number(div: 0.25)",
+        "number(finite) | number(infinite)",
+        "text",
+        r#""Alice""#,
+        "/^h.*o$/",
+        r#"text(one_of: ["a", "b"])"#,
+        r#"text(^"Al") | text("ce"$)"#,
+        "text(len: 5) | text(len: {2,})",
+        "bstr",
+        "h'0102ff'",
+        "tagged(1, number)",
+        "[number, text]",
+        "bool",
+        "true",
+        "null",
+        "date",
+        "date'2023-01-01...2023-12-31'",
+        "known",
+        "'isA'",
+        "'/is.*/'",
+        // Structure patterns
+        "leaf",
+        "node",
+        "node({1,3})",
+        "node(all: assert)",
+        r#"node(preds_in: ["age", text])"#,
+        "node(count(text): {2,5})",
+        r#"node(exactly: [pred("age"), obj(number)])"#,
+        "subj",
+        r#"subj("Alice")"#,
+        "assert",
+        r#"assertpred("knows")"#,
+        "assertobj(number)",
+        r#"assert(pred: "name", obj: text)"#,
+        "pred(known)",
+        "obj(>=18)",
+        "wrapped",
+        "unwrap(node)",
+        "obscured | elided | encrypted | compressed",
+        "digest(a1b2c3)",
+        "untag(100, @name(text))",
+        // Meta patterns
+        "*",
+        "!*",
+        "text & !\"Alice\"",
+        "text | number",
+        "prio(10, text) | prio(1, number)",
+        "node -> subj -> text",
+        r#"search("Bob") <- *"#,
+        "search(text, limit: 2)",
+        "dedup(search(number))",
+        "unique(search(number))",
+        "count({1,3}, search(text))",
+        "integer(-5...5) | integer(>0) | integer(<-3)",
+        "@name(text)",
+        "(assert){1, 5}+",
+        "(unwrap)? -> node",
+        "bfs(unwrap | unwrap -> node)",
+    ];
+    for source in sources {
+        let pattern = Pattern::parse(source).unwrap();
+        let cbor = pattern.to_cbor().unwrap();
+        let decoded = Pattern::from_cbor(cbor).unwrap();
+        assert_eq!(decoded, pattern, "round trip of {source:?}");
+        // The decoded pattern displays the same as the original.
+        assert_eq!(decoded.to_string(), pattern.to_string());
+    }
+}
+
+#[test]
+fn test_pattern_cbor_encoding() {
+    let pattern = Pattern::or(vec![Pattern::any_text(), Pattern::number(42)]);
+    let cbor = pattern.to_cbor().unwrap();
+    assert_eq!(cbor.diagnostic_flat(), r#""text | 42""#);
+    assert_eq!(cbor, CBOR::from(pattern.to_string()));
+}
+
+#[test]
+fn test_pattern_cbor_unencodable() {
+    let envelope = Envelope::new("Alice");
+    let unencodable = [
+        // Displays as `text | !text & number`, which parses as
+        // `text | !(text & number)`.
+        Pattern::any_text().or_default(Pattern::any_number()),
+        Pattern::has_same_digest_as(&envelope),
+        Pattern::deep_equal(&envelope),
+        Pattern::any_text().with_explanation("expected text"),
+        Pattern::path_filter(Pattern::any_text(), |_| true),
+    ];
+    for pattern in unencodable {
+        assert!(
+            matches!(pattern.to_cbor(), Err(Error::UnencodablePattern(_))),
+            "{pattern} should not encode"
+        );
+    }
+
+    // Patterns built in code encode when their display text parses back,
+    // including the alternation shorthands nested in other operators.
+    for pattern in [
+        Pattern::number_is_finite(),
+        Pattern::tag_with_envelope_content(100, Pattern::any_text()),
+        Pattern::traverse(vec![
+            Pattern::any_node(),
+            Pattern::known_value_or_text("isA"),
+        ]),
+        Pattern::and(vec![
+            Pattern::known_value_or_text("x"),
+            Pattern::predicate_or_subject(Pattern::any_text()),
+        ]),
+    ] {
+        let decoded = Pattern::from_cbor(pattern.to_cbor().unwrap()).unwrap();
+        assert_eq!(decoded, pattern);
+    }
+}
+
+#[test]
+fn test_pattern_cbor_errors() {
+    assert!(matches!(
+        Pattern::from_cbor(CBOR::from(42)),
+        Err(Error::InvalidPatternCBOR(_))
+    ));
+    assert!(matches!(
+        Pattern::from_cbor(CBOR::to_tagged_value(1, "text")),
+        Err(Error::InvalidPatternCBOR(_))
+    ));
+    assert!(Pattern::from_cbor(CBOR::from("node(")).is_err());
+}
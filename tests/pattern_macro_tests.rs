@@ -0,0 +1,60 @@
+use bc_envelope_pattern::{Pattern, pattern};
+
+#[test]
+fn test_pattern_macro_leaves() {
+    assert_eq!(pattern!(text "Alice"), Pattern::text("Alice"));
+    assert_eq!(pattern!(number 42), Pattern::number(42));
+    assert_eq!(pattern!(any), Pattern::any());
+    assert_eq!(pattern!(any_subject), Pattern::any_subject());
+}
+
+#[test]
+fn test_pattern_macro_combinators() {
+    assert_eq!(
+        pattern!(and [text "a", text "b"]),
+        Pattern::and(vec![Pattern::text("a"), Pattern::text("b")])
+    );
+    assert_eq!(
+        pattern!(or [text "a", number 42]),
+        Pattern::or(vec![Pattern::text("a"), Pattern::number(42)])
+    );
+    assert_eq!(
+        pattern!(traverse [any_subject, any_object]),
+        Pattern::traverse(vec![Pattern::any_subject(), Pattern::any_object()])
+    );
+    assert_eq!(
+        pattern!(capture "name" any_text),
+        Pattern::capture("name", Pattern::any_text())
+    );
+    assert_eq!(
+        pattern!(search not number 1),
+        Pattern::search(Pattern::not_matching(Pattern::number(1)))
+    );
+    // Trailing commas are allowed.
+    assert_eq!(
+        pattern!(or [any_text,]),
+        Pattern::or(vec![Pattern::any_text()])
+    );
+}
+
+#[test]
+fn test_pattern_macro_nesting() {
+    assert_eq!(
+        pattern!(traverse [
+            any_node,
+            or [capture "a" text "Alice", and [any_number, not number 0]],
+            any_object,
+        ]),
+        Pattern::traverse(vec![
+            Pattern::any_node(),
+            Pattern::or(vec![
+                Pattern::capture("a", Pattern::text("Alice")),
+                Pattern::and(vec![
+                    Pattern::any_number(),
+                    Pattern::not_matching(Pattern::number(0)),
+                ]),
+            ]),
+            Pattern::any_object(),
+        ])
+    );
+}
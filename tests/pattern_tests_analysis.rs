@@ -0,0 +1,404 @@
+use bc_envelope::prelude::*;
+use bc_envelope_pattern::{Error, Matcher, Pattern, PatternKind, Reluctance};
+
+#[test]
+fn test_unsatisfiable_patterns() {
+    let never = || Pattern::not_matching(Pattern::any());
+    let patterns = vec![
+        never(),
+        Pattern::and(vec![Pattern::text("x"), Pattern::number(1)]),
+        Pattern::and(vec![Pattern::any_bool(), Pattern::any_date()]),
+        Pattern::and(vec![Pattern::null(), Pattern::any_known_value()]),
+        Pattern::and(vec![Pattern::any_array(), Pattern::any_map()]),
+        Pattern::and(vec![
+            Pattern::capture("t", Pattern::any_text()),
+            Pattern::any_byte_string(),
+        ]),
+        Pattern::or(vec![never(), never()]),
+        Pattern::traverse(vec![Pattern::any_node(), never()]),
+        Pattern::search(never()),
+        Pattern::subject(Pattern::and(vec![
+            Pattern::any_number(),
+            Pattern::any_text(),
+        ])),
+        Pattern::repeat(never(), 1.., Reluctance::Greedy),
+        Pattern::capture("c", never()),
+    ];
+    for pattern in patterns {
+        assert!(
+            !pattern.is_satisfiable(),
+            "{pattern} should be unsatisfiable"
+        );
+    }
+}
+
+#[test]
+fn test_satisfiable_patterns() {
+    let never = || Pattern::not_matching(Pattern::any());
+    let patterns = vec![
+        Pattern::any(),
+        Pattern::text("x"),
+        Pattern::and(vec![Pattern::any_text(), Pattern::text("x")]),
+        Pattern::and(vec![
+            Pattern::number(1),
+            Pattern::number_range(0.0..=2.0),
+        ]),
+        Pattern::and(vec![Pattern::any_date(), Pattern::any_tag()]),
+        Pattern::and(vec![Pattern::any_cbor(), Pattern::any_bool()]),
+        Pattern::or(vec![never(), Pattern::null()]),
+        Pattern::not_matching(Pattern::text("x")),
+        Pattern::traverse(vec![Pattern::any_node(), Pattern::any_assertion()]),
+        Pattern::search(Pattern::any_text()),
+        Pattern::repeat(never(), 0.., Reluctance::Greedy),
+        Pattern::subject(Pattern::any_text()),
+        // Conservatively satisfiable, even though no value equals both.
+        Pattern::and(vec![Pattern::text("x"), Pattern::text("y")]),
+    ];
+    for pattern in patterns {
+        assert!(pattern.is_satisfiable(), "{pattern} should be satisfiable");
+    }
+}
+
+#[test]
+fn test_disjoint_or() {
+    let pattern = Pattern::disjoint_or(vec![
+        Pattern::any_text(),
+        Pattern::number(1),
+        Pattern::any_bool(),
+    ])
+    .unwrap();
+    assert_eq!(
+        pattern,
+        Pattern::or(vec![
+            Pattern::any_text(),
+            Pattern::number(1),
+            Pattern::any_bool(),
+        ])
+    );
+    assert!(pattern.matches(&Envelope::new("x")));
+    assert!(pattern.matches(&Envelope::new(1)));
+    assert!(Pattern::disjoint_or(vec![]).is_ok());
+
+    // Alternatives that may overlap are rejected, naming the first pair.
+    let result = Pattern::disjoint_or(vec![
+        Pattern::number(1),
+        Pattern::any(),
+        Pattern::any_text(),
+    ]);
+    assert!(matches!(
+        result,
+        Err(Error::OverlappingAlternatives(ref a, ref b))
+            if a == "1" && b == "*"
+    ));
+    assert!(
+        Pattern::disjoint_or(vec![Pattern::any_text(), Pattern::any_text()])
+            .is_err()
+    );
+    assert!(
+        Pattern::disjoint_or(vec![Pattern::text("a"), Pattern::text("b")])
+            .is_err()
+    );
+}
+
+#[test]
+fn test_empty_and_universal_patterns() {
+    let never = Pattern::not_matching(Pattern::any());
+    assert!(never.is_empty_pattern());
+    assert!(
+        Pattern::and(vec![Pattern::text("x"), Pattern::number(1)])
+            .is_empty_pattern()
+    );
+    assert!(!Pattern::any_text().is_empty_pattern());
+
+    assert!(Pattern::any().is_universal_pattern());
+    assert!(
+        Pattern::or(vec![Pattern::any_text(), Pattern::any()])
+            .is_universal_pattern()
+    );
+    assert!(!Pattern::any_text().is_universal_pattern());
+    assert!(!never.is_universal_pattern());
+}
+
+#[test]
+fn test_optimize_prunes_trivial_branches() {
+    let never = || Pattern::not_matching(Pattern::any());
+    let disjoint =
+        || Pattern::and(vec![Pattern::text("x"), Pattern::number(1)]);
+
+    assert_eq!(
+        Pattern::or(vec![Pattern::any_text(), disjoint(), never()]).optimize(),
+        Pattern::any_text()
+    );
+    assert_eq!(
+        Pattern::or(vec![Pattern::any_text(), never(), Pattern::null()])
+            .optimize(),
+        Pattern::or(vec![Pattern::any_text(), Pattern::null()])
+    );
+    assert_eq!(Pattern::or(vec![disjoint(), never()]).optimize(), never());
+    assert_eq!(
+        Pattern::and(vec![Pattern::any(), Pattern::any_text()]).optimize(),
+        Pattern::any_text()
+    );
+    assert_eq!(
+        Pattern::and(vec![Pattern::any_text(), Pattern::any()]).optimize(),
+        Pattern::any_text()
+    );
+    // A trailing `*` decides the paths, so it stays after a pattern that
+    // moves elsewhere.
+    let envelope = Envelope::new("Alice")
+        .add_assertion("knows", "Bob")
+        .add_assertion("age", 30);
+    for pattern in [
+        Pattern::and(vec![Pattern::any_assertion(), Pattern::any()]),
+        Pattern::and(vec![
+            Pattern::any(),
+            Pattern::any_assertion(),
+            Pattern::any(),
+        ]),
+    ] {
+        let optimized = pattern.clone().optimize();
+        assert_eq!(
+            optimized,
+            Pattern::and(vec![Pattern::any_assertion(), Pattern::any()])
+        );
+        assert_eq!(optimized.paths(&envelope), pattern.paths(&envelope));
+    }
+    assert_eq!(
+        Pattern::traverse(vec![Pattern::any_node(), disjoint()]).optimize(),
+        never()
+    );
+    // Nested branches are optimized too.
+    assert_eq!(
+        Pattern::search(Pattern::or(vec![never(), Pattern::any_text()]))
+            .optimize(),
+        Pattern::search(Pattern::any_text())
+    );
+    // Universal alternatives are kept because their paths may differ.
+    let universal = Pattern::or(vec![Pattern::any(), Pattern::unwrap()]);
+    assert_eq!(universal.clone().optimize(), universal);
+}
+
+#[test]
+fn test_double_negation() {
+    let p = Pattern::text("x");
+    let not = || Pattern::not_matching(Pattern::text("x"));
+
+    // The constructor cancels double negations.
+    assert_eq!(Pattern::not_matching(not()), p);
+    assert_eq!(Pattern::not_matching(Pattern::not_matching(not())), not());
+    assert_eq!(Pattern::parse("!!\"x\"").unwrap(), p);
+    assert_eq!(Pattern::parse("!!!\"x\"").unwrap(), not());
+
+    assert_eq!(Pattern::none(), Pattern::parse("!*").unwrap());
+    assert!(Pattern::none().is_empty_pattern());
+    assert_eq!(Pattern::not_matching(Pattern::none()), Pattern::any());
+
+    // A pattern that can move keeps its double negation, which only
+    // returns the envelope being matched.
+    let envelope = Envelope::new("Alice").add_assertion("knows", "Bob");
+    let search = Pattern::search(Pattern::text("Bob"));
+    let double =
+        Pattern::not_matching(Pattern::not_matching(search.clone()));
+    assert_ne!(double, search);
+    assert_eq!(double.to_string(), r#"!!search("Bob")"#);
+    assert_eq!(Pattern::parse(double.to_string()).unwrap(), double);
+    assert_eq!(double.clone().optimize(), double);
+    assert_eq!(double.paths(&envelope), vec![vec![envelope.clone()]]);
+
+    // `optimize` negates an empty pattern to `*`, and keeps `!*` as it is.
+    assert_eq!(
+        Pattern::not_matching(Pattern::any()).optimize(),
+        Pattern::none()
+    );
+    let disjoint =
+        Pattern::and(vec![Pattern::text("x"), Pattern::number(1)]);
+    assert_eq!(Pattern::not_matching(disjoint).optimize(), Pattern::any());
+    // Negations exposed by optimizing a child cancel too.
+    let nested =
+        Pattern::not_matching(Pattern::or(vec![Pattern::none(), not()]));
+    assert_eq!(nested.optimize(), p);
+    assert!(!Pattern::none().matches(&Envelope::new("x")));
+}
+
+#[test]
+fn test_flatten_traversal() {
+    let node = Pattern::any_node;
+    let subj = Pattern::any_subject;
+
+    // Nested traversals are merged into the enclosing one.
+    let nested = Pattern::traverse(vec![
+        Pattern::traverse(vec![node(), Pattern::any_assertion()]),
+        Pattern::any_object(),
+    ]);
+    let flat = Pattern::traverse(vec![
+        node(),
+        Pattern::any_assertion(),
+        Pattern::any_object(),
+    ]);
+    assert_eq!(nested.flatten_traversal(), flat);
+
+    // A triply-nested single-step traversal is just its step.
+    let nested =
+        Pattern::traverse(vec![Pattern::traverse(vec![Pattern::traverse(
+            vec![subj()],
+        )])]);
+    assert_eq!(nested.flatten_traversal(), subj());
+
+    // Traversals nested in other patterns are flattened too.
+    let nested = Pattern::search(Pattern::traverse(vec![
+        node(),
+        Pattern::traverse(vec![Pattern::traverse(vec![subj()])]),
+    ]));
+    assert_eq!(
+        nested.flatten_traversal(),
+        Pattern::search(Pattern::traverse(vec![node(), subj()]))
+    );
+
+    // The flattened pattern matches the same paths.
+    let envelope = Envelope::new("Alice").add_assertion("knows", "Bob");
+    assert_eq!(
+        nested.flatten_traversal().paths(&envelope),
+        nested.paths(&envelope)
+    );
+
+    // `optimize` flattens traversals as well.
+    assert_eq!(nested.clone().optimize(), nested.flatten_traversal());
+
+    // Breadth-first traversals keep their order, and are only merged with
+    // traversals of the same order.
+    let bfs = Pattern::parse("bfs(unwrap -> node)").unwrap();
+    assert_eq!(bfs.flatten_traversal(), bfs);
+    assert_eq!(bfs.clone().optimize(), bfs);
+    let mixed = Pattern::traverse(vec![node(), bfs.clone()]);
+    assert_eq!(mixed.flatten_traversal(), mixed);
+    let nested = Pattern::traverse_breadth_first(vec![
+        Pattern::traverse_breadth_first(vec![node(), subj()]),
+        Pattern::any_assertion(),
+    ]);
+    assert_eq!(
+        nested.flatten_traversal(),
+        Pattern::traverse_breadth_first(vec![
+            node(),
+            subj(),
+            Pattern::any_assertion(),
+        ])
+    );
+}
+
+#[test]
+fn test_node_summary() {
+    let cases = vec![
+        (Pattern::any(), "anything"),
+        (Pattern::any_number(), "any number"),
+        (Pattern::number(42), "the number 42"),
+        (
+            Pattern::number_range(1.0..=100.0),
+            "a number between 1 and 100",
+        ),
+        (Pattern::number_greater_than(5), "a number greater than 5"),
+        (
+            Pattern::number_matches_any([1.0, 2.5]),
+            "one of the numbers 1, 2.5",
+        ),
+        (Pattern::text("Alice"), "the text \"Alice\""),
+        (
+            Pattern::text_regex(regex::Regex::new("^A").unwrap()),
+            "text matching /^A/",
+        ),
+        (Pattern::any_bool(), "any boolean"),
+        (Pattern::bool(true), "the boolean true"),
+        (Pattern::null(), "null"),
+        (Pattern::any_date(), "any date"),
+        (
+            Pattern::known_value(known_values::IS_A),
+            "the known value 'isA'",
+        ),
+        (Pattern::any_byte_string(), "any byte string"),
+        (Pattern::any_array(), "any array"),
+        (Pattern::any_assertion(), "any assertion"),
+        (
+            Pattern::assertion_with_predicate(Pattern::text("knows")),
+            "an assertion whose predicate is the text \"knows\"",
+        ),
+        (
+            Pattern::and(vec![
+                Pattern::any_node(),
+                Pattern::subject(Pattern::text("Alice")),
+            ]),
+            "any node and a subject that is the text \"Alice\"",
+        ),
+        (
+            Pattern::node_with_assertions_range(1..=3),
+            "a node with between 1 and 3 assertions",
+        ),
+        (Pattern::leaf(), "any leaf"),
+        (
+            Pattern::unwrap_matching(Pattern::any_node()),
+            "any node inside a wrapped envelope",
+        ),
+        (Pattern::elided(), "any elided element"),
+        (
+            Pattern::search(Pattern::text("Bob")),
+            "the text \"Bob\" anywhere",
+        ),
+        (
+            Pattern::traverse(vec![
+                Pattern::any_assertion(),
+                Pattern::any_object(),
+            ]),
+            "any assertion, then any object",
+        ),
+        (
+            Pattern::or(vec![Pattern::any_text(), Pattern::any_number()]),
+            "any text or any number",
+        ),
+        (
+            Pattern::not_matching(Pattern::any_text()),
+            "anything except any text",
+        ),
+        (
+            Pattern::capture("name", Pattern::any_text()),
+            "any text (captured as @name)",
+        ),
+        (
+            Pattern::repeat(Pattern::wrapped(), 0.., Reluctance::Greedy),
+            "any wrapped envelope, any number of times",
+        ),
+    ];
+    for (pattern, expected) in cases {
+        assert_eq!(pattern.node_summary(), expected, "summary of {pattern}");
+    }
+}
+
+#[test]
+fn test_pattern_kind() {
+    let leaf = Pattern::text("Alice");
+    assert_eq!(leaf.kind(), PatternKind::Leaf);
+    assert!(leaf.is_pure_leaf());
+    assert!(!leaf.is_pure_structure() && !leaf.is_meta());
+
+    let structure = Pattern::subject(Pattern::text("Alice"));
+    assert_eq!(structure.kind(), PatternKind::Structure);
+    assert!(structure.is_pure_structure());
+    assert!(!structure.is_pure_leaf() && !structure.is_meta());
+
+    let meta = Pattern::any();
+    assert_eq!(meta.kind(), PatternKind::Meta);
+    assert!(meta.is_meta());
+    assert!(!meta.is_pure_leaf() && !meta.is_pure_structure());
+
+    // Only the outermost pattern counts.
+    for nested in [
+        Pattern::search(Pattern::text("Alice")),
+        Pattern::capture("name", Pattern::text("Alice")),
+        Pattern::or(vec![Pattern::any_node(), Pattern::leaf()]),
+        Pattern::parse("(node)?").unwrap(),
+    ] {
+        assert_eq!(nested.kind(), PatternKind::Meta, "{nested}");
+        assert!(!nested.is_pure_leaf() && !nested.is_pure_structure());
+    }
+    assert!(
+        Pattern::wrapped_content_matching(Pattern::search(Pattern::any_text()))
+            .is_pure_structure()
+    );
+}
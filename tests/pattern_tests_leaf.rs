@@ -1,7 +1,9 @@
 mod common;
 
 use bc_envelope::prelude::*;
-use bc_envelope_pattern::{DCBORPattern, Matcher, Pattern, format_paths};
+use bc_envelope_pattern::{
+    DCBORPattern, Error, Matcher, Pattern, format_paths,
+};
 use indoc::indoc;
 
 #[test]
@@ -154,6 +156,363 @@ fn test_text_pattern() {
     assert_actual_expected!(format_paths(&paths), expected);
 }
 
+#[test]
+fn test_text_matches_glob_pattern() {
+    let matches = |glob: &str, text: &str| {
+        Pattern::text_matches_glob(glob)
+            .unwrap()
+            .matches(&Envelope::new(text))
+    };
+
+    // Suffix wildcard
+    assert!(matches("Alice*", "Alice"));
+    assert!(matches("Alice*", "Alice Smith"));
+    assert!(!matches("Alice*", "Mary Alice"));
+
+    // Prefix wildcard
+    assert!(matches("*Smith", "Alice Smith"));
+    assert!(!matches("*Smith", "Smithers"));
+
+    // Middle wildcard
+    assert!(matches("A*e", "Alice"));
+    assert!(matches("A*e", "Ae"));
+    assert!(!matches("A*e", "Alicia"));
+
+    // Multiple wildcards, including `?` for exactly one character
+    assert!(matches("*li?e*", "Alice Smith"));
+    assert!(matches("?lice", "Alice"));
+    assert!(!matches("?lice", "lice"));
+    assert!(matches("*", ""));
+
+    // Regex metacharacters match themselves, and newlines are ordinary
+    // characters.
+    assert!(matches("a.b+(c)*", "a.b+(c) and more"));
+    assert!(!matches("a.b", "axb"));
+    assert!(matches("line*", "line one\nline two"));
+
+    // Non-text values do not match.
+    let pattern = Pattern::text_matches_glob("*").unwrap();
+    assert!(!pattern.matches(&Envelope::new(42)));
+    assert_actual_expected!(pattern.to_string(), r#"text_glob("*")"#);
+}
+
+#[test]
+fn test_text_prefix_and_suffix_patterns() {
+    let alice = Envelope::new("Alice Smith");
+    assert!(Pattern::text_starts_with("Alice").matches(&alice));
+    assert!(!Pattern::text_starts_with("Smith").matches(&alice));
+    assert!(Pattern::text_ends_with("Smith").matches(&alice));
+    assert!(!Pattern::text_ends_with("Alice").matches(&alice));
+
+    // An empty prefix or suffix matches any text, but only text.
+    let bytes = Envelope::new(CBOR::to_byte_string([0x41]));
+    let empty = [Pattern::text_starts_with(""), Pattern::text_ends_with("")];
+    for pattern in empty {
+        assert!(pattern.matches(&alice));
+        assert!(pattern.matches(&Envelope::new("")));
+        assert!(!pattern.matches(&Envelope::new(42)));
+        assert!(!pattern.matches(&bytes));
+    }
+
+    // Multi-byte prefixes and suffixes compare whole characters.
+    let greeting = Envelope::new("日本語 👋");
+    assert!(Pattern::text_starts_with("日本").matches(&greeting));
+    assert!(Pattern::text_ends_with("👋").matches(&greeting));
+    assert!(!Pattern::text_starts_with("本").matches(&greeting));
+
+    // Combines with a regex on the same text.
+    let pattern = Pattern::and(vec![
+        Pattern::text_starts_with("Alice"),
+        Pattern::text_regex(regex::Regex::new(r"\s\w+$").unwrap()),
+    ]);
+    assert!(pattern.matches(&alice));
+    assert!(!pattern.matches(&Envelope::new("Alice")));
+    assert_actual_expected!(
+        pattern.to_string(),
+        r#"text(^"Alice") & /\s\w+$/"#
+    );
+}
+
+#[test]
+fn test_text_length_pattern() {
+    let alice = Envelope::new("Alice");
+    assert!(Pattern::text_length(5).matches(&alice));
+    assert!(!Pattern::text_length(4).matches(&alice));
+    let bob = Envelope::new("Bob");
+    assert!(Pattern::text_length_range(3..=10).unwrap().matches(&alice));
+    assert!(!Pattern::text_length_range(6..=10).unwrap().matches(&alice));
+    assert!(Pattern::text_length_range(..5).unwrap().matches(&bob));
+    assert!(!Pattern::text_length_range(..5).unwrap().matches(&alice));
+
+    // Unbounded ranges set only a minimum.
+    assert!(Pattern::text_length_range(4..).unwrap().matches(&alice));
+    assert!(!Pattern::text_length_range(6..).unwrap().matches(&alice));
+
+    // An empty range is an error.
+    assert_eq!(Pattern::text_length_range(..0), Err(Error::EmptyLengthRange));
+    #[allow(clippy::reversed_empty_ranges)]
+    let inverted = Pattern::text_length_range(6..=5);
+    assert_eq!(inverted, Err(Error::EmptyLengthRange));
+
+    // Zero length matches only the empty string.
+    assert!(Pattern::text_length(0).matches(&Envelope::new("")));
+    assert!(!Pattern::text_length(0).matches(&alice));
+
+    // Length counts characters, not bytes.
+    assert!(Pattern::text_length(4).matches(&Envelope::new("café")));
+    assert!(Pattern::text_length(1).matches(&Envelope::new("👋")));
+
+    // Non-text leaves do not match.
+    assert!(!Pattern::text_length(2).matches(&Envelope::new(42)));
+
+    // As with other leaf patterns, a node matches through its subject...
+    let node = alice.clone().add_assertion("knows", "Bob");
+    assert!(Pattern::text_length(5).matches(&node));
+    let node = Envelope::new(12345).add_assertion("knows", "Bob");
+    assert!(!Pattern::text_length(5).matches(&node));
+
+    // ...but a wrapped envelope does not, until it is unwrapped.
+    let wrapped = alice.wrap();
+    assert!(!Pattern::text_length(5).matches(&wrapped));
+    let pattern =
+        Pattern::traverse(vec![Pattern::unwrap(), Pattern::text_length(5)]);
+    assert!(pattern.matches(&wrapped));
+}
+
+#[test]
+fn test_text_matches_any_pattern() {
+    let pattern = Pattern::text_matches_any(["Alice", "Bob", "Carol"]);
+    assert!(pattern.matches(&Envelope::new("Bob")));
+    assert!(!pattern.matches(&Envelope::new("Dave")));
+    assert!(!pattern.matches(&Envelope::new(42)));
+
+    // Matches the subject of a node, like `text`.
+    let envelope = Envelope::new("Alice").add_assertion("knows", "Dave");
+    assert_eq!(pattern.paths(&envelope), vec![vec![envelope.clone()]]);
+
+    // An `or` of the same values matches exactly the same envelopes.
+    let or = Pattern::or(["Alice", "Bob", "Carol"].map(Pattern::text).to_vec());
+    for name in ["Alice", "Bob", "Carol", "Dave"] {
+        let envelope = Envelope::new(name);
+        assert_eq!(or.matches(&envelope), pattern.matches(&envelope));
+    }
+
+    // An empty set never matches.
+    let empty = Pattern::text_matches_any(Vec::<String>::new());
+    assert!(!empty.matches(&Envelope::new("Alice")));
+}
+
+#[test]
+#[ignore]
+fn bench_text_matches_any_vs_or() {
+    // Run with `cargo test --release -- --ignored --nocapture`.
+    let values: Vec<String> = (0..100).map(|i| format!("value-{i}")).collect();
+    let set = Pattern::text_matches_any(values.clone());
+    let or = Pattern::or(values.iter().map(Pattern::text).collect());
+    let envelopes: Vec<Envelope> = (0..200)
+        .map(|i| Envelope::new(format!("value-{i}")))
+        .collect();
+
+    for (name, pattern) in [("one_of", &set), ("or", &or)] {
+        let start = std::time::Instant::now();
+        let mut count = 0;
+        for _ in 0..10 {
+            count += envelopes.iter().filter(|e| pattern.matches(e)).count();
+        }
+        println!("{name}: {count} matches in {:?}", start.elapsed());
+        assert_eq!(count, 1000);
+    }
+}
+
+#[test]
+fn test_number_matches_any_pattern() {
+    let pattern = Pattern::number_matches_any([1.0, 2.5, 42.0]);
+    assert!(pattern.matches(&Envelope::new(42)));
+    assert!(pattern.matches(&Envelope::new(2.5)));
+    assert!(!pattern.matches(&Envelope::new(3)));
+    assert!(!pattern.matches(&Envelope::new("42")));
+    assert!(!pattern.matches(&Envelope::new(f64::NAN)));
+
+    // NaN in the values matches NaN, and only NaN.
+    let pattern = Pattern::number_matches_any([f64::NAN, f64::INFINITY]);
+    assert!(pattern.matches(&Envelope::new(f64::NAN)));
+    assert!(pattern.matches(&Envelope::new(f64::INFINITY)));
+    assert!(!pattern.matches(&Envelope::new(f64::NEG_INFINITY)));
+    assert!(!pattern.matches(&Envelope::new(0)));
+
+    // An empty list never matches.
+    let empty = Pattern::number_matches_any(Vec::<f64>::new());
+    assert!(!empty.matches(&Envelope::new(0)));
+}
+
+#[test]
+fn test_number_infinity_patterns() {
+    let finite =
+        [Envelope::new(0), Envelope::new(-3.5), Envelope::new(f64::MAX)];
+    let inf = Envelope::new(f64::INFINITY);
+    let neg_inf = Envelope::new(f64::NEG_INFINITY);
+    let nan = Envelope::new(f64::NAN);
+
+    for envelope in &finite {
+        assert!(Pattern::number_is_finite().matches(envelope));
+        assert!(!Pattern::number_is_infinite().matches(envelope));
+    }
+    for envelope in [&inf, &neg_inf] {
+        assert!(!Pattern::number_is_finite().matches(envelope));
+        assert!(Pattern::number_is_infinite().matches(envelope));
+    }
+    // NaN is neither finite nor infinite.
+    assert!(!Pattern::number_is_finite().matches(&nan));
+    assert!(!Pattern::number_is_infinite().matches(&nan));
+    // Non-numbers are neither.
+    assert!(!Pattern::number_is_finite().matches(&Envelope::new("1")));
+    assert!(!Pattern::number_is_infinite().matches(&Envelope::new("1")));
+
+    assert!(Pattern::number_positive_infinity().matches(&inf));
+    assert!(!Pattern::number_positive_infinity().matches(&neg_inf));
+    assert!(Pattern::number_negative_infinity().matches(&neg_inf));
+    assert!(!Pattern::number_negative_infinity().matches(&inf));
+    assert!(!Pattern::number_positive_infinity().matches(&finite[2]));
+
+    assert_eq!(
+        Pattern::number_is_finite().to_string(),
+        "number(finite)"
+    );
+    assert_eq!(
+        Pattern::parse(Pattern::number_is_finite().to_string()).unwrap(),
+        Pattern::number_is_finite()
+    );
+}
+
+#[test]
+fn test_number_divisible_by_pattern() {
+    let pattern = Pattern::number_divisible_by(100).unwrap();
+    assert!(pattern.matches(&Envelope::new(0)));
+    assert!(pattern.matches(&Envelope::new(300)));
+    assert!(pattern.matches(&Envelope::new(-1200)));
+    assert!(!pattern.matches(&Envelope::new(150)));
+    assert!(!pattern.matches(&Envelope::new("300")));
+
+    // Every integer is divisible by 1, but fractions are not.
+    let pattern = Pattern::number_divisible_by(1).unwrap();
+    assert!(pattern.matches(&Envelope::new(7)));
+    assert!(pattern.matches(&Envelope::new(-7)));
+    assert!(!pattern.matches(&Envelope::new(7.5)));
+
+    // Floating-point rounding is tolerated.
+    let divisible = |divisor: f64, value: f64| {
+        Pattern::number_divisible_by(divisor)
+            .unwrap()
+            .matches(&Envelope::new(value))
+    };
+    assert!(divisible(0.1, 0.3));
+    assert!(divisible(0.25, 1.75));
+    assert!(!divisible(0.25, 1.8));
+    assert!(!divisible(0.1, 0.35));
+    assert!(!divisible(0.01, 0.015));
+    for n in [1, 2, 3, 7, 12, 20, 30, 99, 100, 1000, 12345, -17] {
+        let n = f64::from(n);
+        assert!(divisible(0.1, n), "{n}");
+        assert!(divisible(0.1, n / 10.0), "{n} / 10");
+        assert!(divisible(0.01, n), "{n}");
+        assert!(divisible(0.01, n / 100.0), "{n} / 100");
+    }
+
+    // Integral divisors are checked exactly: 1e17 leaves a remainder of 5
+    // when divided by 7.
+    assert!(!divisible(7.0, 1e17));
+    assert!(divisible(7.0, 7e16));
+    assert!(divisible(100.0, 1e17));
+
+    // NaN and infinities are never divisible.
+    let pattern = Pattern::number_divisible_by(1).unwrap();
+    assert!(!pattern.matches(&Envelope::new(f64::NAN)));
+    assert!(!pattern.matches(&Envelope::new(f64::INFINITY)));
+
+    // Finds assertion objects that are multiples of 100.
+    let envelope = Envelope::new("Alice")
+        .add_assertion("balance", 2500)
+        .add_assertion("age", 42);
+    let paths = Pattern::search(Pattern::traverse(vec![
+        Pattern::any_assertion(),
+        Pattern::any_object(),
+        Pattern::number_divisible_by(100).unwrap(),
+    ]))
+    .paths(&envelope);
+    assert_eq!(paths.len(), 1);
+    assert_eq!(paths[0].last().unwrap(), &Envelope::new(2500));
+}
+
+#[test]
+fn test_number_divisible_by_invalid_divisor() {
+    for divisor in [0.0, -0.0, f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        assert!(matches!(
+            Pattern::number_divisible_by(divisor),
+            Err(Error::InvalidDivisorValue(_))
+        ));
+    }
+}
+
+#[test]
+fn test_integer_pattern() {
+    // Does not match non-integer subjects.
+    assert!(!Pattern::any_integer().matches(&Envelope::new("42")));
+    assert!(!Pattern::any_integer().matches(&Envelope::new(2.5)));
+    assert!(!Pattern::any_integer().matches(&Envelope::new(f64::NAN)));
+    assert!(!Pattern::any_integer().matches(&Envelope::new(f64::INFINITY)));
+
+    let envelope = Envelope::new(42);
+    assert!(Pattern::any_integer().matches(&envelope));
+    assert!(Pattern::integer(42).matches(&envelope));
+    assert!(!Pattern::integer(43).matches(&envelope));
+    assert!(Pattern::integer_range(40..=42).matches(&envelope));
+    assert!(!Pattern::integer_range(43..=50).matches(&envelope));
+    assert!(Pattern::integer_greater_than(41).matches(&envelope));
+    assert!(!Pattern::integer_greater_than(42).matches(&envelope));
+    assert!(Pattern::integer_less_than(43).matches(&envelope));
+    assert!(!Pattern::integer_less_than(42).matches(&envelope));
+
+    // Negative integers.
+    let envelope = Envelope::new(-5);
+    assert!(Pattern::integer(-5).matches(&envelope));
+    assert!(Pattern::integer_range(-10..=-1).matches(&envelope));
+    assert!(Pattern::integer_less_than(0).matches(&envelope));
+    assert!(!Pattern::integer_greater_than(0).matches(&envelope));
+
+    // dCBOR encodes a float with no fractional part as an integer.
+    assert!(Pattern::integer(42).matches(&Envelope::new(42.0)));
+
+    // Matches the subject of a node.
+    let envelope = Envelope::new(7).add_assertion("an", "assertion");
+    assert!(Pattern::integer(7).matches(&envelope));
+
+    // Integers are also numbers.
+    let pattern =
+        Pattern::and(vec![Pattern::any_integer(), Pattern::number(7)]);
+    assert!(pattern.matches(&envelope));
+    assert!(pattern.is_satisfiable());
+}
+
+#[test]
+#[ignore]
+fn bench_number_matches_any_vs_or() {
+    // Run with `cargo test --release -- --ignored --nocapture`.
+    let values: Vec<f64> = (0..100).map(f64::from).collect();
+    let set = Pattern::number_matches_any(values.clone());
+    let or = Pattern::or(values.iter().map(|&v| Pattern::number(v)).collect());
+    let envelopes: Vec<Envelope> = (0..200).map(Envelope::new).collect();
+
+    for (name, pattern) in [("one_of", &set), ("or", &or)] {
+        let start = std::time::Instant::now();
+        let mut count = 0;
+        for _ in 0..10 {
+            count += envelopes.iter().filter(|e| pattern.matches(e)).count();
+        }
+        println!("{name}: {count} matches in {:?}", start.elapsed());
+        assert_eq!(count, 1000);
+    }
+}
+
 #[test]
 fn test_date_pattern() {
     // Does not match non-date subjects.
@@ -191,6 +550,23 @@ fn test_date_pattern() {
     let regex = regex::Regex::new(r"^2024-.*").unwrap();
     assert!(!Pattern::date_regex(regex).matches(&envelope));
 
+    // Test year and month matching
+    assert!(Pattern::date_in_year(2023).matches(&envelope));
+    assert!(!Pattern::date_in_year(2024).matches(&envelope));
+    assert!(!Pattern::date_in_year(202).matches(&envelope));
+
+    // The whole of the last day counts, and any year can be asked for.
+    let new_years_eve =
+        Envelope::new(Date::from_ymd_hms(2023, 12, 31, 23, 0, 0));
+    assert!(Pattern::date_in_year(2023).matches(&new_years_eve));
+    let early = Envelope::new(Date::from_ymd(500, 6, 1));
+    assert!(Pattern::date_in_year(500).matches(&early));
+    assert!(!Pattern::date_in_year(5000).matches(&early));
+    assert!(!Pattern::date_in_year(i32::MAX).matches(&early));
+    assert!(!Pattern::date_in_year(i32::MIN).matches(&early));
+    assert!(Pattern::date_in_month(12).matches(&envelope));
+    assert!(!Pattern::date_in_month(1).matches(&envelope));
+
     // Matches a subject that is a date with an assertion.
     let envelope = envelope.add_assertion("type", "christmas");
     assert!(Pattern::any_date().matches(&envelope));
@@ -233,6 +609,39 @@ fn test_date_pattern() {
     assert!(Pattern::date_regex(regex).matches(&envelope_with_time));
 }
 
+#[test]
+#[cfg(feature = "domain")]
+fn test_date_age_patterns() {
+    let now = Date::from_ymd(2024, 6, 1);
+    let last_year = Envelope::new(Date::from_ymd(2023, 6, 1));
+    let five_years_ago = Envelope::new(Date::from_ymd(2019, 6, 1));
+    let next_year = Envelope::new(Date::from_ymd(2025, 6, 1));
+
+    let pattern = Pattern::date_between_ages_at(0.0, 3.0, now);
+    assert!(pattern.matches(&last_year));
+    assert!(!pattern.matches(&five_years_ago));
+    assert!(!pattern.matches(&next_year));
+
+    let pattern = Pattern::date_between_ages_at(2.0, 10.0, now);
+    assert!(!pattern.matches(&last_year));
+    assert!(pattern.matches(&five_years_ago));
+
+    let pattern = Pattern::date_not_expired_at(3.0, now);
+    assert!(pattern.matches(&last_year));
+    assert!(pattern.matches(&next_year));
+    assert!(!pattern.matches(&five_years_ago));
+
+    let pattern = Pattern::date_not_future_at(now);
+    assert!(pattern.matches(&last_year));
+    assert!(pattern.matches(&Envelope::new(now)));
+    assert!(!pattern.matches(&next_year));
+
+    // The variants measured from now agree on dates far from the boundaries.
+    assert!(Pattern::date_not_future().matches(&last_year));
+    assert!(!Pattern::date_not_expired(1.0).matches(&five_years_ago));
+    assert!(Pattern::date_between_ages(0.0, 100.0).matches(&five_years_ago));
+}
+
 #[test]
 fn test_known_value_pattern() {
     use known_values;
@@ -300,6 +709,45 @@ fn test_known_value_pattern() {
     assert!(!Pattern::known_value_named("unknown_name").matches(&envelope));
 }
 
+#[test]
+fn test_known_value_from_name_pattern() {
+    // Registered names resolve to the registered known value.
+    let p = Pattern::parse_known_value_from_name("date").unwrap();
+    assert_eq!(p, Pattern::known_value(known_values::DATE));
+    assert!(p.matches(&Envelope::new(known_values::DATE)));
+    assert!(!p.matches(&Envelope::new("date")));
+
+    // Unregistered names are an error.
+    assert_eq!(
+        Pattern::parse_known_value_from_name("unknown_name"),
+        Err(bc_envelope_pattern::Error::UnknownKnownValueName(
+            "unknown_name".to_string()
+        ))
+    );
+
+}
+
+#[test]
+fn test_known_value_or_text_pattern() {
+    // Either representation of the name matches.
+    let p = Pattern::known_value_or_text("date");
+    assert_eq!(p.to_string(), r#"'date' | "date""#);
+    assert!(p.matches(&Envelope::new(known_values::DATE)));
+    assert!(p.matches(&Envelope::new("date")));
+    assert!(p.matches(&Envelope::new("date").add_assertion("note", "x")));
+
+    // Unrelated values do not.
+    assert!(!p.matches(&Envelope::new(known_values::IS_A)));
+    assert!(!p.matches(&Envelope::new("Date")));
+    assert!(!p.matches(&Envelope::new("isA")));
+    assert!(!p.matches(&Envelope::new(42)));
+
+    // A name that is not registered can still match the text.
+    let p = Pattern::known_value_or_text("unknown_name");
+    assert!(p.matches(&Envelope::new("unknown_name")));
+    assert!(!p.matches(&Envelope::new(known_values::DATE)));
+}
+
 #[test]
 fn test_known_value_regex_pattern() {
     use regex::Regex;
@@ -522,6 +970,48 @@ fn test_array_pattern() {
     assert_actual_expected!(format_paths(&paths), expected);
 }
 
+#[test]
+fn test_cbor_array_at_pattern() {
+    let envelope = Envelope::new(vec!["a", "b", "c"]);
+
+    // Matches the element at the given index.
+    assert!(
+        Pattern::cbor_array_at(0, DCBORPattern::text("a")).matches(&envelope)
+    );
+    assert!(
+        Pattern::cbor_array_at(2, DCBORPattern::text("c")).matches(&envelope)
+    );
+    assert!(
+        !Pattern::cbor_array_at(1, DCBORPattern::text("a")).matches(&envelope)
+    );
+
+    // Does not match when the index is out of bounds.
+    assert!(!Pattern::cbor_array_at(3, DCBORPattern::any()).matches(&envelope));
+
+    // Does not match non-array subjects.
+    let text_envelope = Envelope::new("a");
+    assert!(
+        !Pattern::cbor_array_at(0, DCBORPattern::any()).matches(&text_envelope)
+    );
+
+    // The element can itself be an array.
+    let nested = vec![1.to_cbor(), vec![2, 3].to_cbor()].to_cbor();
+    let envelope = Envelope::new(nested);
+    let pattern = Pattern::cbor_array_at(
+        1,
+        DCBORPattern::parse("[number, 3]").unwrap(),
+    );
+    assert!(pattern.matches(&envelope));
+    let pattern =
+        Pattern::cbor_array_at(0, DCBORPattern::parse("[number]").unwrap());
+    assert!(!pattern.matches(&envelope));
+
+    assert_actual_expected!(
+        Pattern::cbor_array_at(0, DCBORPattern::any_text()).to_string(),
+        "arr[0:text]"
+    );
+}
+
 #[test]
 fn test_map_pattern() {
     // Does not match non-map subjects.
@@ -583,6 +1073,62 @@ fn test_map_pattern() {
     assert_actual_expected!(format_paths(&paths), expected);
 }
 
+#[test]
+fn test_map_required_key_pattern() {
+    let mut map = Map::new();
+    map.insert("name", "Alice");
+    map.insert("age", 30);
+    let envelope = Envelope::new(map).add_assertion("type", "person");
+
+    let has_age = Pattern::map_with_required_key(DCBORPattern::text("age"));
+    assert!(has_age.matches(&envelope));
+    assert!(
+        !Pattern::map_with_required_key(DCBORPattern::text("email"))
+            .matches(&envelope)
+    );
+    assert!(
+        Pattern::map_with_required_key(DCBORPattern::any_text())
+            .matches(&envelope)
+    );
+    assert!(!has_age.matches(&Envelope::new("age")));
+    assert!(!has_age.matches(&Envelope::new(Map::new())));
+
+    // The key and value must be in the same entry.
+    assert!(
+        Pattern::map_with_required_key_value(
+            DCBORPattern::text("age"),
+            DCBORPattern::any_number()
+        )
+        .matches(&envelope)
+    );
+    assert!(
+        !Pattern::map_with_required_key_value(
+            DCBORPattern::text("name"),
+            DCBORPattern::any_number()
+        )
+        .matches(&envelope)
+    );
+
+    // Compiled through the VM, as part of a larger pattern.
+    let pattern = Pattern::search(has_age);
+    let paths = pattern.paths(&Envelope::new("Alice").add_assertion(
+        "profile",
+        Envelope::new({
+            let mut map = Map::new();
+            map.insert("age", 30);
+            map
+        }),
+    ));
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        71f6aaef NODE "Alice" [ "profile": {"age": 30} ]
+            2ca193a8 ASSERTION "profile": {"age": 30}
+                587fcd33 LEAF {"age": 30}
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+}
+
 #[test]
 fn test_null_pattern() {
     // Does not match non-null subjects.
@@ -854,3 +1400,32 @@ fn test_tag_pattern_with_bc_components_tags() {
         digest_tag_value, matches_regex
     );
 }
+
+#[test]
+fn test_cbor_from_dcbor_diagnostic() {
+    let cases: Vec<(&str, Envelope)> = vec![
+        ("42", Envelope::new(42)),
+        ("3.5", Envelope::new(3.5)),
+        (r#""hello""#, Envelope::new("hello")),
+        ("h'0102'", Envelope::new(CBOR::to_byte_string([1, 2]))),
+        (
+            "1(1703462400)",
+            Envelope::new(CBOR::to_tagged_value(1, 1703462400)),
+        ),
+    ];
+    for (diag, envelope) in cases {
+        let pattern = Pattern::from_dcbor_diagnostic(diag).unwrap();
+        assert!(pattern.matches(&envelope), "{diag} should match");
+        assert!(!pattern.matches(&Envelope::new("other")));
+        // The `cbor(...)` syntax parses to the same pattern.
+        let parsed = Pattern::parse(format!("cbor({diag})")).unwrap();
+        assert_eq!(parsed, pattern);
+        assert_eq!(pattern.to_string(), format!("cbor({diag})"));
+    }
+
+    assert!(matches!(
+        Pattern::from_dcbor_diagnostic("[1, 2"),
+        Err(Error::InvalidDCBORDiagnostic(_))
+    ));
+    assert!(Pattern::from_dcbor_diagnostic("").is_err());
+}
@@ -1,7 +1,9 @@
 mod common;
 
 use bc_envelope::prelude::*;
-use bc_envelope_pattern::{Matcher, Pattern, Reluctance, format_paths};
+use bc_envelope_pattern::{
+    Matcher, Pattern, Reluctance, TraversalOrder, TraversalStats, format_paths,
+};
 use indoc::indoc;
 
 #[test]
@@ -14,6 +16,19 @@ fn test_empty_traversal_pattern() {
     assert!(paths.is_empty());
 }
 
+#[test]
+fn test_single_step_traversal_pattern() {
+    // A traversal with a single `*` step behaves like `*` itself.
+    let pattern = Pattern::traverse(vec![Pattern::any()]);
+    for envelope in [
+        Envelope::new(42),
+        Envelope::new("Alice").add_assertion("knows", "Bob"),
+        Envelope::new("Alice").wrap(),
+    ] {
+        assert_eq!(pattern.paths(&envelope), vec![vec![envelope.clone()]]);
+    }
+}
+
 #[test]
 fn test_and_pattern() {
     let envelope = Envelope::new(42).add_assertion("an", "assertion");
@@ -59,6 +74,27 @@ fn test_and_pattern() {
         format!("{}", number_range_with_subject_pattern),
         r#">40 & <50 -> subj"#,
     );
+
+    // Patterns that move to an assertion are all matched against the same
+    // envelope, and the last one determines the path.
+    let envelope = envelope.add_assertion("another", "assertion");
+    let pattern = Pattern::and(vec![
+        Pattern::assertion_with_predicate(Pattern::text("an")),
+        Pattern::assertion_with_predicate(Pattern::text("another")),
+    ]);
+    let paths = pattern.paths(&envelope);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        b4d69df8 ASSERTION "another": "assertion"
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+
+    // Earlier operands only test the envelope; they do not move the path.
+    let pattern = Pattern::and(vec![Pattern::any_assertion(), Pattern::any()]);
+    assert_eq!(pattern.paths(&envelope), vec![vec![envelope.clone()]]);
+    let pattern = Pattern::and(vec![Pattern::any(), Pattern::any_assertion()]);
+    assert_eq!(pattern.paths(&envelope).len(), 2);
 }
 
 #[test]
@@ -108,8 +144,420 @@ fn test_or_pattern() {
     assert_actual_expected!(format_paths(&paths), expected);
     assert_eq!(
         format!("{}", foo_or_greater_than_40_with_subject_pattern),
-        r#""foo" | >40 -> subj"#
+        r#"("foo" | >40) -> subj"#
+    );
+    assert_eq!(
+        Pattern::parse(foo_or_greater_than_40_with_subject_pattern.to_string())
+            .unwrap(),
+        foo_or_greater_than_40_with_subject_pattern
+    );
+}
+
+#[test]
+fn test_or_empty_pattern() {
+    let knows = Pattern::assertion_with_predicate(Pattern::text("knows"));
+    let pattern = Pattern::or_empty(knows);
+    assert_eq!(pattern.to_string(), r#"assertpred("knows") | leaf"#);
+    assert_eq!(Pattern::parse(pattern.to_string()).unwrap(), pattern);
+
+    // An enriched node with the assertion matches.
+    let node = Envelope::new("Alice").add_assertion("knows", "Bob");
+    assert_eq!(pattern.paths(&node).len(), 1);
+
+    // A bare leaf matches as the empty case.
+    let leaf = Envelope::new("Alice");
+    assert_eq!(pattern.paths(&leaf), vec![vec![leaf.clone()]]);
+
+    // A node without the assertion does not match.
+    let other = Envelope::new("Alice").add_assertion("age", 30);
+    assert!(!pattern.matches(&other));
+}
+
+#[test]
+fn test_or_default_pattern() {
+    let envelope = Envelope::new("Alice")
+        .add_assertion("knows", "Bob")
+        .add_assertion("knows", "Carol")
+        .add_assertion("age", 30);
+
+    let knows = Pattern::assertion_with_predicate(Pattern::text("knows"));
+    let likes = Pattern::assertion_with_predicate(Pattern::text("likes"));
+    let hates = Pattern::assertion_with_predicate(Pattern::text("hates"));
+
+    // The primary pattern matches, so the fallback is never used.
+    let pattern = knows.clone().or_default(Pattern::any_assertion());
+    assert_eq!(pattern.paths(&envelope).len(), 2);
+    assert_eq!(
+        Pattern::or(vec![knows.clone(), Pattern::any_assertion()])
+            .paths(&envelope)
+            .len(),
+        5
+    );
+
+    // The primary pattern does not match, so the fallback is used.
+    let pattern = likes.clone().or_default(Pattern::any_assertion());
+    assert_eq!(pattern.paths(&envelope).len(), 3);
+
+    // Neither pattern matches.
+    let pattern = likes.or_default(hates);
+    assert!(!pattern.matches(&envelope));
+    assert!(pattern.paths(&envelope).is_empty());
+}
+
+#[test]
+fn test_or_with_priority_pattern() {
+    let envelope = Envelope::new("Alice").add_assertion("knows", "Bob");
+
+    // Branches are tried in descending order of priority.
+    let pattern = Pattern::or_with_priority(vec![
+        (1, Pattern::any_subject()),
+        (10, Pattern::any_assertion()),
+    ]);
+    assert_eq!(pattern.to_string(), "prio(10, assert) | prio(1, subj)");
+    assert_eq!(Pattern::parse(pattern.to_string()).unwrap(), pattern);
+
+    // It matches the same paths as `or`, with the higher-priority branch's
+    // paths first.
+    let paths = pattern.paths(&envelope);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        78d666eb ASSERTION "knows": "Bob"
+        8955db5e NODE "Alice" [ "knows": "Bob" ]
+            13941b48 LEAF "Alice"
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+
+    let or =
+        Pattern::or(vec![Pattern::any_subject(), Pattern::any_assertion()]);
+    assert_eq!(or.paths(&envelope), paths);
+
+    // Branches with equal priority keep their relative order.
+    let pattern = Pattern::or_with_priority(vec![
+        (5, Pattern::text("Alice")),
+        (5, Pattern::text("Bob")),
+        (7, Pattern::any_number()),
+    ]);
+    assert_eq!(
+        pattern.to_string(),
+        r#"prio(7, number) | prio(5, "Alice") | prio(5, "Bob")"#
+    );
+    assert!(pattern.matches(&Envelope::new("Bob")));
+    assert!(!pattern.matches(&Envelope::new(true)));
+}
+
+#[test]
+#[ignore]
+fn bench_or_with_priority() {
+    // Run with `cargo test --release -- --ignored --nocapture`.
+    //
+    // The VM explores every branch of an alternation, so branch order
+    // changes the order of the results rather than the amount of work done.
+    let envelopes: Vec<Envelope> = (0..1000)
+        .map(|i| {
+            if i % 10 == 0 {
+                Envelope::new(i)
+            } else {
+                Envelope::new(format!("item {i}"))
+            }
+        })
+        .collect();
+    let common_first = Pattern::or_with_priority(vec![
+        (10, Pattern::any_text()),
+        (1, Pattern::any_number()),
+    ]);
+    let common_last = Pattern::or_with_priority(vec![
+        (1, Pattern::any_text()),
+        (10, Pattern::any_number()),
+    ]);
+
+    for (name, pattern) in [
+        ("common first", &common_first),
+        ("common last", &common_last),
+    ] {
+        let start = std::time::Instant::now();
+        let mut count = 0;
+        for _ in 0..10 {
+            count += envelopes.iter().filter(|e| pattern.matches(e)).count();
+        }
+        println!("{name}: {count} matches in {:?}", start.elapsed());
+        assert_eq!(count, 10000);
+    }
+}
+
+#[test]
+fn test_operator_patterns() {
+    let p1 = Pattern::any_text();
+    let p2 = Pattern::text("Alice");
+    let p3 = Pattern::number(42);
+
+    let pattern = (p1.clone() & p2.clone()) | !p3.clone();
+    assert_eq!(
+        pattern,
+        Pattern::or(vec![
+            Pattern::and(vec![p1.clone(), p2.clone()]),
+            Pattern::not_matching(p3.clone()),
+        ])
+    );
+    assert_eq!(format!("{}", pattern), r#"text & "Alice" | !42"#);
+
+    // Chained operators flatten into a single combinator.
+    assert_eq!(
+        p1.clone() & p2.clone() & p3.clone(),
+        Pattern::and(vec![p1.clone(), p2.clone(), p3.clone()])
+    );
+    assert_eq!(
+        p1.clone() | p2.clone() | p3.clone(),
+        Pattern::or(vec![p1, p2, p3])
+    );
+
+    assert!(pattern.matches(&Envelope::new("Alice")));
+    assert!(pattern.matches(&Envelope::new(true)));
+    assert!(!pattern.matches(&Envelope::new(42)));
+}
+
+#[test]
+fn test_dedup_pattern() {
+    let envelope = Envelope::new("Alice").add_assertion("knows", "Bob");
+
+    // Both alternatives reach the same assertion.
+    let or = Pattern::or(vec![
+        Pattern::assertion_with_predicate(Pattern::text("knows")),
+        Pattern::assertion_with_object(Pattern::text("Bob")),
+    ]);
+    assert_eq!(or.paths(&envelope).len(), 2);
+
+    let pattern = Pattern::dedup(or);
+    assert_eq!(
+        pattern.to_string(),
+        r#"dedup(assertpred("knows") | assertobj("Bob"))"#
+    );
+    assert_eq!(Pattern::parse(pattern.to_string()).unwrap(), pattern);
+    let paths = pattern.paths(&envelope);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        78d666eb ASSERTION "knows": "Bob"
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+
+    // Deduplication also applies within a traversal.
+    let pattern = Pattern::traverse(vec![
+        Pattern::any_node(),
+        Pattern::dedup(Pattern::or(vec![
+            Pattern::any_subject(),
+            Pattern::subject(Pattern::text("Alice")),
+        ])),
+    ]);
+    assert_eq!(pattern.paths(&envelope).len(), 1);
+}
+
+#[test]
+fn test_unique_by_digest_pattern() {
+    // "Bob" is reached through two different assertions.
+    let envelope = Envelope::new("Alice")
+        .add_assertion("knows", "Bob")
+        .add_assertion("likes", "Bob");
+    let search = Pattern::search(Pattern::text("Bob"));
+    assert_eq!(search.paths(&envelope).len(), 2);
+
+    // The paths differ, so `dedup` keeps both...
+    assert_eq!(Pattern::dedup(search.clone()).paths(&envelope).len(), 2);
+
+    // ...but they end at the same envelope, so `unique` keeps the first.
+    let pattern = Pattern::unique_by_digest(search);
+    assert_eq!(pattern.to_string(), r#"unique(search("Bob"))"#);
+    assert_eq!(Pattern::parse(pattern.to_string()).unwrap(), pattern);
+    let paths = pattern.paths(&envelope);
+    assert_eq!(paths.len(), 1);
+    assert_eq!(paths[0].last().unwrap(), &Envelope::new("Bob"));
+
+    // Distinct tips are all kept: the node (whose subject is text), "Alice",
+    // both predicates, and a single "Bob".
+    let search = Pattern::search(Pattern::any_text());
+    assert_eq!(search.paths(&envelope).len(), 6);
+    let pattern = Pattern::unique_by_digest(search);
+    assert_eq!(pattern.paths(&envelope).len(), 5);
+
+    // Each `unique` keeps its own record of the envelopes it has seen.
+    let pattern = Pattern::parse("unique(*) -> unique(*)").unwrap();
+    assert_eq!(
+        pattern.paths(&envelope),
+        Pattern::parse("* -> *").unwrap().paths(&envelope)
+    );
+    assert_eq!(pattern.paths(&envelope).len(), 1);
+    let pattern = Pattern::parse("dedup(*) -> dedup(*)").unwrap();
+    assert_eq!(pattern.paths(&envelope).len(), 1);
+}
+
+#[test]
+fn test_search_count_pattern() {
+    let envelope = Envelope::new("Alice")
+        .add_assertion("knows", "Bob")
+        .add_assertion("likes", "Bob");
+
+    let pattern = Pattern::search_count_equals(Pattern::text("Bob"), 2);
+    assert_eq!(pattern.to_string(), r#"count(2, search("Bob"))"#);
+    assert_eq!(Pattern::parse(pattern.to_string()).unwrap(), pattern);
+    // A count matches the envelope it was tested against.
+    let paths = pattern.paths(&envelope);
+    assert_eq!(paths, vec![vec![envelope.clone()]]);
+
+    assert!(
+        !Pattern::search_count_equals(Pattern::text("Bob"), 1)
+            .matches(&envelope)
+    );
+    assert!(
+        Pattern::search_count_equals(Pattern::text("Carol"), 0)
+            .matches(&envelope)
+    );
+
+    // Ranges of counts.
+    let bobs = Pattern::search(Pattern::text("Bob"));
+    assert!(Pattern::search_count(bobs.clone(), 1, None).matches(&envelope));
+    assert!(
+        !Pattern::search_count(bobs.clone(), 3, Some(5)).matches(&envelope)
+    );
+
+    // Counts can be tested at each step of a traversal.
+    let pattern = Pattern::traverse(vec![
+        Pattern::any_assertion(),
+        Pattern::search_count_equals(Pattern::text("Bob"), 1),
+    ]);
+    assert_eq!(pattern.paths(&envelope).len(), 2);
+}
+
+#[test]
+fn test_breadth_first_traversal() {
+    let pattern = Pattern::traverse_breadth_first(vec![
+        Pattern::any_assertion(),
+        Pattern::any_object(),
+    ]);
+    assert_eq!(pattern.to_string(), "bfs(assert -> obj)");
+    assert_eq!(Pattern::parse("bfs(assert -> obj)").unwrap(), pattern);
+    assert_eq!(
+        Pattern::traverse_ordered(
+            vec![Pattern::any_assertion(), Pattern::any_object()],
+            TraversalOrder::BreadthFirst,
+        ),
+        pattern
+    );
+    assert_eq!(
+        Pattern::traverse_depth_first(vec![Pattern::unwrap(), Pattern::leaf()]),
+        Pattern::traverse(vec![Pattern::unwrap(), Pattern::leaf()])
+    );
+
+    // `>>` doesn't merge steps into a breadth-first traversal.
+    let chained = pattern.clone() >> Pattern::any_number();
+    assert_eq!(chained.to_string(), "bfs(assert -> obj) -> number");
+
+    // Depth-first, the deeper alternative finishes first.
+    let envelope = Envelope::new(42).wrap().wrap();
+    let pattern = Pattern::parse("unwrap | unwrap -> unwrap").unwrap();
+    let paths = pattern.paths(&envelope);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        81bb1f5e WRAPPED { { 42 } }
+            58b1ac6a WRAPPED { 42 }
+                7f83f7bd LEAF 42
+        81bb1f5e WRAPPED { { 42 } }
+            58b1ac6a WRAPPED { 42 }
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+
+    // Breadth-first, the shallower match comes first.
+    let pattern = Pattern::parse("bfs(unwrap | unwrap -> unwrap)").unwrap();
+    assert_eq!(pattern.to_string(), "bfs(unwrap | unwrap -> unwrap)");
+    let paths = pattern.paths(&envelope);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        81bb1f5e WRAPPED { { 42 } }
+            58b1ac6a WRAPPED { 42 }
+        81bb1f5e WRAPPED { { 42 } }
+            58b1ac6a WRAPPED { 42 }
+                7f83f7bd LEAF 42
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+}
+
+#[test]
+fn test_explained_pattern() {
+    let envelope = Envelope::new("Alice").add_assertion("age", 30);
+
+    // The explanation doesn't change matching or the textual form.
+    let pattern = Pattern::search(Pattern::text("Bob"))
+        .with_explanation("expected someone named Bob");
+    assert_eq!(pattern.to_string(), r#"search("Bob")"#);
+    assert_eq!(
+        Pattern::parse(pattern.to_string()).unwrap(),
+        Pattern::search(Pattern::text("Bob"))
+    );
+
+    // A failed match records the explanation.
+    assert!(!pattern.matches(&envelope));
+    assert_eq!(
+        Pattern::last_failure_explanation().as_deref(),
+        Some("expected someone named Bob")
+    );
+
+    // A successful match clears it.
+    let pattern = Pattern::search(Pattern::number(30))
+        .with_explanation("expected an age of 30");
+    assert!(pattern.matches(&envelope));
+    assert_eq!(Pattern::last_failure_explanation(), None);
+
+    // Matching an unexplained pattern leaves the state alone.
+    let bob = Pattern::text("Bob").with_explanation("expected Bob");
+    assert!(!bob.matches(&envelope));
+    assert!(!Pattern::text("Carol").matches(&envelope));
+    assert_eq!(
+        Pattern::last_failure_explanation().as_deref(),
+        Some("expected Bob")
+    );
+}
+
+#[test]
+fn test_path_filter_pattern() {
+    let envelope = Envelope::new("Alice")
+        .add_assertion("knows", "Bob")
+        .add_assertion("age", 30);
+
+    let all = Pattern::search(Pattern::any_text()).paths(&envelope);
+
+    // Keep only matches directly below the root.
+    let pattern =
+        Pattern::path_filter(Pattern::search(Pattern::any_text()), |path| {
+            path.len() <= 2
+        });
+    assert_eq!(pattern.to_string(), "filter(search(text))");
+    let paths = pattern.paths(&envelope);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        a47bb3d4 NODE "Alice" [ "age": 30, "knows": "Bob" ]
+        a47bb3d4 NODE "Alice" [ "age": 30, "knows": "Bob" ]
+            13941b48 LEAF "Alice"
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+    assert!(paths.iter().all(|path| path.len() <= 2));
+    assert!(paths.len() < all.len());
+
+    // Keep only matches whose tip has a given digest prefix.
+    let bob = Envelope::new("Bob");
+    let prefix = bob.digest().hex()[..8].to_string();
+    let pattern = Pattern::path_filter(
+        Pattern::search(Pattern::any_text()),
+        move |path| path.last().unwrap().digest().hex().starts_with(&prefix),
     );
+    let paths = pattern.paths(&envelope);
+    assert_eq!(paths.len(), 1);
+    assert_eq!(paths[0].last().unwrap(), &bob);
+
+    // A filter rejecting everything never matches.
+    let pattern = Pattern::path_filter(Pattern::any(), |_| false);
+    assert!(!pattern.matches(&envelope));
 }
 
 #[test]
@@ -134,6 +582,37 @@ fn test_one_element_traversal_pattern() {
     assert_eq!(format!("{}", pattern), r#"42"#);
 }
 
+#[test]
+fn test_chain_pattern() {
+    let a = Pattern::any_node();
+    let b = Pattern::any_assertion();
+    let c = Pattern::any_object();
+
+    let expected = Pattern::traverse(vec![a.clone(), b.clone(), c.clone()]);
+    assert_eq!(
+        Pattern::chain(vec![a.clone(), b.clone(), c.clone()]),
+        expected
+    );
+    assert_eq!(a.clone() >> b.clone() >> c.clone(), expected);
+    assert_eq!((a.clone() >> b.clone()) >> c.clone(), expected);
+    assert_eq!(format!("{}", expected), "node -> assert -> obj");
+
+    // Right-nesting keeps the inner traversal as a single step.
+    let nested = a.clone() >> (b.clone() >> c.clone());
+    assert_eq!(
+        nested,
+        Pattern::traverse(vec![a, Pattern::traverse(vec![b, c])])
+    );
+
+    let envelope = Envelope::new("Alice").add_assertion("knows", "Bob");
+    assert_eq!(
+        nested.paths(&envelope),
+        expected.paths(&envelope),
+        "nesting does not change the matched paths"
+    );
+    assert_eq!(expected.paths(&envelope).len(), 1);
+}
+
 #[test]
 fn test_wrapped_traversal() {
     let env_1 = Envelope::new("data");
@@ -469,6 +948,210 @@ fn test_search_pattern_with_wrapped() {
     assert_actual_expected!(format_paths(&secret_regex_search_paths), expected);
 }
 
+#[test]
+fn test_search_pattern_path_context() {
+    let envelope = Envelope::new("Alice").add_assertion("knows", "Bob").wrap();
+
+    let paths = Pattern::search(Pattern::text("Bob")).paths(&envelope);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        fd881a24 WRAPPED { "Alice" [ "knows": "Bob" ] }
+            8955db5e NODE "Alice" [ "knows": "Bob" ]
+                78d666eb ASSERTION "knows": "Bob"
+                    13b74194 LEAF "Bob"
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+
+    // The match is at depth 3, so the path holds the root, the two
+    // intermediate envelopes, and the match itself.
+    assert_eq!(paths.len(), 1);
+    assert_eq!(paths[0].len(), 4);
+    assert_eq!(paths[0][0], envelope);
+}
+
+#[test]
+fn test_compress_path() {
+    let alice = Envelope::new("Alice");
+    let bob = Envelope::new("Bob");
+
+    let path = vec![
+        alice.clone(),
+        alice.clone(),
+        bob.clone(),
+        bob.clone(),
+        alice.clone(),
+    ];
+    assert_eq!(
+        Pattern::compress_path(path),
+        vec![alice.clone(), bob.clone(), alice.clone()]
+    );
+    assert!(Pattern::compress_path(vec![]).is_empty());
+
+    // A search over a flat envelope never repeats the root in its paths.
+    let envelope = Envelope::new("Alice")
+        .add_assertion("knows", "Bob")
+        .add_assertion("age", 30);
+    let paths = Pattern::search(Pattern::any_text()).paths(&envelope);
+    assert!(!paths.is_empty());
+    let compressed = Pattern::paths_compressed(paths.clone());
+    assert_eq!(compressed, paths);
+    for path in compressed {
+        assert_eq!(path[0], envelope);
+        assert!(path.iter().skip(1).all(|e| e.digest() != envelope.digest()));
+    }
+}
+
+#[test]
+fn test_limited_search_pattern() {
+    let envelope = Envelope::new("Alice")
+        .add_assertion("knows", "Bob")
+        .add_assertion("knows", "Carol")
+        .add_assertion("age", 30);
+
+    let all = Pattern::search(Pattern::any_text()).paths(&envelope);
+    assert_eq!(all.len(), 7);
+
+    // The limited search returns the first matches in walk order.
+    let pattern = Pattern::limited_search(Pattern::any_text(), 2);
+    assert_eq!(pattern.to_string(), "search(text, limit: 2)");
+    assert_eq!(pattern.paths(&envelope), all[..2].to_vec());
+
+    // A limit above the number of matches returns them all.
+    let pattern = Pattern::limited_search(Pattern::any_text(), 10);
+    assert_eq!(pattern.paths(&envelope), all);
+
+    let pattern = Pattern::limited_search(Pattern::any_text(), 0);
+    assert!(!pattern.matches(&envelope));
+
+    let parsed = Pattern::parse("search(text, limit:2)").unwrap();
+    assert_eq!(parsed, Pattern::limited_search(Pattern::any_text(), 2));
+    assert!(Pattern::parse("search(text, limit: -1)").is_err());
+    assert!(Pattern::parse("search(text, 2)").is_err());
+}
+
+#[test]
+fn test_traverse_optional_steps_pattern() {
+    let envelope = Envelope::new("Alice").add_assertion("knows", "Bob");
+    let wrapped = envelope.wrap();
+
+    // Optionally unwrap, then require a node.
+    let pattern = Pattern::traverse_optional_steps(vec![
+        (Pattern::unwrap(), false),
+        (Pattern::any_node(), true),
+    ]);
+    assert_eq!(pattern.to_string(), "(unwrap)? -> node");
+    assert_eq!(Pattern::parse("(unwrap)? -> node").unwrap(), pattern);
+
+    // Without a wrapper, the optional step is skipped.
+    assert_eq!(pattern.paths(&envelope), vec![vec![envelope.clone()]]);
+
+    // With a wrapper, the optional step is taken.
+    let paths = pattern.paths(&wrapped);
+    assert_eq!(paths, vec![vec![wrapped.clone(), envelope.clone()]]);
+
+    // The required step must still match.
+    assert!(!pattern.matches(&Envelope::new("Alice")));
+
+    // All steps optional matches the starting envelope.
+    let pattern = Pattern::traverse_optional_steps(vec![
+        (Pattern::unwrap(), false),
+        (Pattern::subject(Pattern::any()), false),
+    ]);
+    assert!(pattern.matches(&Envelope::new("Alice")));
+}
+
+#[test]
+fn test_traverse_back_pattern() {
+    let envelope = Envelope::new("Alice")
+        .add_assertion("knows", "Bob")
+        .add_assertion("age", 30);
+
+    // Step back from "Bob" to the assertion containing it.
+    let pattern =
+        Pattern::traverse_back(Pattern::search_text("Bob"), Pattern::any());
+    assert_eq!(pattern.to_string(), r#"search("Bob") <- *"#);
+    let paths = pattern.paths(&envelope);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        a47bb3d4 NODE "Alice" [ "age": 30, "knows": "Bob" ]
+            78d666eb ASSERTION "knows": "Bob"
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+
+    // Step back from the assertion to the node holding it.
+    let pattern = Pattern::traverse_back(
+        Pattern::search(Pattern::assertion_with_object(Pattern::text("Bob"))),
+        Pattern::any_node(),
+    );
+    assert_eq!(pattern.paths(&envelope), vec![vec![envelope.clone()]]);
+
+    // The parent must match the second pattern.
+    let pattern = Pattern::traverse_back(
+        Pattern::search_text("Bob"),
+        Pattern::any_node(),
+    );
+    assert!(!pattern.matches(&envelope));
+
+    // A match at the root has no parent.
+    let pattern = Pattern::traverse_back(Pattern::any(), Pattern::any());
+    assert!(!pattern.matches(&envelope));
+
+    let parsed = Pattern::parse(r#"search("Bob") <- *"#).unwrap();
+    assert_eq!(parsed.paths(&envelope), paths);
+
+    // Steps after a search apply to each search result.
+    let pattern = Pattern::parse(r#"search(text) -> "Bob""#).unwrap();
+    let paths = pattern.paths(&envelope);
+    assert_eq!(paths.len(), 1);
+    assert_eq!(paths[0].last().unwrap(), &Envelope::new("Bob"));
+}
+
+#[test]
+fn test_search_shorthands() {
+    let date = Date::from_ymd(2024, 1, 15);
+    let envelope = Envelope::new("Alice")
+        .add_assertion("knows", "Bob")
+        .add_assertion("age", 30)
+        .add_assertion("born", date);
+
+    let regex = || regex::Regex::new("^B").unwrap();
+    let date_regex = || regex::Regex::new("^2024").unwrap();
+    let cases = vec![
+        (
+            Pattern::search_text("Bob"),
+            Pattern::search(Pattern::text("Bob")),
+        ),
+        (
+            Pattern::search_text_regex(regex()),
+            Pattern::search(Pattern::text_regex(regex())),
+        ),
+        (
+            Pattern::search_number(30),
+            Pattern::search(Pattern::number(30)),
+        ),
+        (
+            Pattern::search_number_range(18..=65),
+            Pattern::search(Pattern::number_range(18..=65)),
+        ),
+        (
+            Pattern::search_date(date),
+            Pattern::search(Pattern::date(date)),
+        ),
+        (
+            Pattern::search_date_regex(date_regex()),
+            Pattern::search(Pattern::date_regex(date_regex())),
+        ),
+    ];
+    for (shorthand, explicit) in cases {
+        assert_eq!(shorthand, explicit);
+        assert_eq!(shorthand.to_string(), explicit.to_string());
+        assert_eq!(shorthand.paths(&envelope), explicit.paths(&envelope));
+        assert!(shorthand.matches(&envelope), "{shorthand} should match");
+    }
+}
+
 #[test]
 fn test_search_pattern_credential() {
     use bc_envelope_pattern::Path;
@@ -777,3 +1460,162 @@ fn test_capture_in_traversal_failure() {
     assert!(paths.is_empty());
     assert!(!captures.contains_key("num"));
 }
+
+#[test]
+fn test_capture_in_traverse_back() {
+    let envelope = Envelope::new("Alice").add_assertion("age", 30);
+
+    let pattern = Pattern::parse("search(@x(number)) <- @y(*)").unwrap();
+    let (paths, captures) = pattern.paths_with_captures(&envelope);
+    assert_eq!(paths.len(), 1);
+    let assertion = paths[0].last().unwrap().clone();
+    assert_eq!(captures["x"], vec![vec![Envelope::new(30)]]);
+    assert_eq!(captures["y"], vec![vec![assertion]]);
+
+    // Captures are dropped when the parent does not match.
+    let pattern = Pattern::parse("search(@x(number)) <- node").unwrap();
+    let (paths, captures) = pattern.paths_with_captures(&envelope);
+    assert!(paths.is_empty());
+    assert!(!captures.contains_key("x"));
+}
+
+#[test]
+fn test_traversal_stats() {
+    assert_eq!(
+        Pattern::number(42).traversal_stats(),
+        TraversalStats {
+            literal_count: 1,
+            max_depth: 1,
+            has_search: false,
+            has_repeat: false,
+            capture_count: 0,
+        }
+    );
+
+    // `search(@name(text))` nests three deep.
+    let pattern =
+        Pattern::search(Pattern::capture("name", Pattern::any_text()));
+    assert_eq!(
+        pattern.traversal_stats(),
+        TraversalStats {
+            literal_count: 1,
+            max_depth: 3,
+            has_search: true,
+            has_repeat: false,
+            capture_count: 1,
+        }
+    );
+
+    // `node -> (wrapped)* -> subj(@a("Alice") | @b(42))`
+    let pattern = Pattern::traverse(vec![
+        Pattern::any_node(),
+        Pattern::repeat(Pattern::wrapped(), .., Reluctance::Greedy),
+        Pattern::subject(Pattern::or(vec![
+            Pattern::capture("a", Pattern::text("Alice")),
+            Pattern::capture("b", Pattern::number(42)),
+        ])),
+    ]);
+    assert_eq!(
+        pattern.traversal_stats(),
+        TraversalStats {
+            literal_count: 5,
+            max_depth: 5,
+            has_search: false,
+            has_repeat: true,
+            capture_count: 2,
+        }
+    );
+
+    // A plain group is not a repeat.
+    let pattern = Pattern::group(Pattern::any_text());
+    assert!(!pattern.traversal_stats().has_repeat);
+}
+
+#[test]
+fn test_program_cache_distinct_patterns() {
+    // Each pattern must run its own compiled program, however many are
+    // cached.
+    for i in 0..10_000 {
+        let pattern = Pattern::number(i);
+        assert!(pattern.matches(&Envelope::new(i)));
+        assert!(!pattern.matches(&Envelope::new(i + 1)));
+    }
+
+    // These patterns display identically but are different.
+    let a = Pattern::not_matching(Pattern::and(vec![
+        Pattern::text("Alice"),
+        Pattern::any_text(),
+    ]));
+    let b = Pattern::and(vec![
+        Pattern::not_matching(Pattern::text("Alice")),
+        Pattern::any_text(),
+    ]);
+    assert_eq!(a.to_string(), b.to_string());
+    let bob = Envelope::new("Bob");
+    assert!(a.matches(&bob));
+    assert!(b.matches(&bob));
+    let alice = Envelope::new("Alice");
+    assert!(!a.matches(&alice));
+    assert!(!b.matches(&alice));
+    let number = Envelope::new(1);
+    assert!(a.matches(&number));
+    assert!(!b.matches(&number));
+}
+
+#[test]
+fn test_traverse_while_pattern() {
+    // Unwrap for as long as there is something to unwrap.
+    let envelope = Envelope::new("Alice").wrap().wrap();
+    let pattern =
+        Pattern::traverse_while(Pattern::wrapped(), Pattern::unwrap());
+    assert_eq!(pattern.to_string(), "while(wrapped, unwrap)");
+    assert_eq!(Pattern::parse(pattern.to_string()).unwrap(), pattern);
+
+    let paths = pattern.paths(&envelope);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        1c863f78 WRAPPED { { "Alice" } }
+            2bc17c65 WRAPPED { "Alice" }
+                13941b48 LEAF "Alice"
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+
+    // Taking no steps is a match, so an envelope failing the condition
+    // matches in place.
+    let paths = pattern.paths(&Envelope::new("Alice"));
+    assert_actual_expected!(format_paths(&paths), r#"13941b48 LEAF "Alice""#);
+
+    // Follow a chain of "knows" assertions from node to node, stopping at
+    // the leaf that ends it.
+    let envelope = Envelope::new("Alice").add_assertion(
+        "knows",
+        Envelope::new("Bob").add_assertion("knows", "Carol"),
+    );
+    let pattern = Pattern::traverse_while(
+        Pattern::any_node(),
+        Pattern::traverse(vec![
+            Pattern::assertion_with_predicate(Pattern::text("knows")),
+            Pattern::any_object(),
+        ]),
+    );
+    assert_eq!(
+        Pattern::parse(r#"while(node, assertpred("knows") -> obj)"#).unwrap(),
+        pattern
+    );
+    let paths = pattern.paths(&envelope);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        f817f848 NODE "Alice" [ "knows": "Bob" [ "knows": "Carol" ] ]
+            970da8b3 ASSERTION "knows": "Bob" [ "knows": "Carol" ]
+                15ec830a NODE "Bob" [ "knows": "Carol" ]
+                    4012caf2 ASSERTION "knows": "Carol"
+                        afb8122e LEAF "Carol"
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+
+    // The chain can be continued by a following step.
+    let pattern = Pattern::traverse(vec![pattern, Pattern::text("Carol")]);
+    assert!(pattern.matches(&envelope));
+}
@@ -379,6 +379,65 @@ fn repeat_optional_possessive() {
     assert_actual_expected!(format_paths(&paths), expected);
 }
 
+#[test]
+fn repeat_shorthands() {
+    let cases = [
+        (
+            Pattern::optional(Pattern::unwrap()),
+            Pattern::repeat(Pattern::unwrap(), 0..=1, Reluctance::Greedy),
+            "(unwrap)?",
+        ),
+        (
+            Pattern::optional_lazy(Pattern::unwrap()),
+            Pattern::repeat(Pattern::unwrap(), 0..=1, Reluctance::Lazy),
+            "(unwrap)??",
+        ),
+        (
+            Pattern::one_or_more(Pattern::unwrap()),
+            Pattern::repeat(Pattern::unwrap(), 1.., Reluctance::Greedy),
+            "(unwrap)+",
+        ),
+        (
+            Pattern::zero_or_more(Pattern::unwrap()),
+            Pattern::repeat(Pattern::unwrap(), .., Reluctance::Greedy),
+            "(unwrap)*",
+        ),
+    ];
+    for (shorthand, expanded, text) in cases {
+        assert_eq!(shorthand, expanded);
+        assert_eq!(shorthand.to_string(), text);
+        assert_eq!(Pattern::parse(text).unwrap(), shorthand);
+    }
+
+    let leaf = wrap_n(Envelope::new(42), 0);
+    let wrapped = wrap_n(Envelope::new(42), 2);
+    let then_leaf =
+        |p: Pattern| Pattern::traverse(vec![p, Pattern::any_number()]);
+
+    // `optional` unwraps at most once.
+    let pat = then_leaf(Pattern::optional(Pattern::unwrap()));
+    assert!(pat.matches(&leaf));
+    assert!(!pat.matches(&wrapped));
+
+    // `one_or_more` needs at least one unwrap.
+    let pat = then_leaf(Pattern::one_or_more(Pattern::unwrap()));
+    assert!(!pat.matches(&leaf));
+    assert!(pat.matches(&wrapped));
+
+    // `zero_or_more` accepts any depth.
+    let pat = then_leaf(Pattern::zero_or_more(Pattern::unwrap()));
+    assert!(pat.matches(&leaf));
+    let paths = pat.paths(&wrapped);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        81bb1f5e WRAPPED { { 42 } }
+            58b1ac6a WRAPPED { 42 }
+                7f83f7bd LEAF 42
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+}
+
 #[test]
 fn repeat_range_greedy() {
     let pat = Pattern::traverse(vec![
@@ -465,6 +524,27 @@ fn repeat_any_modes() {
                 e909da9a LEAF "data"
     "#}.trim();
     assert_actual_expected!(format_paths(&greedy_paths), expected);
+
+    // A possessive repeat consumes every wrapper, so it never gives back
+    // the zero-repetition match that `wrapped` needs here.
+    let pat = |mode| {
+        Pattern::traverse(vec![
+            Pattern::repeat(Pattern::unwrap(), 0.., mode),
+            Pattern::wrapped(),
+        ])
+    };
+
+    let greedy_paths = pat(Reluctance::Greedy).paths(&env);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        ee8cade0 WRAPPED { { "data" } }
+            febc1555 WRAPPED { "data" }
+    "#}.trim();
+    assert_actual_expected!(format_paths(&greedy_paths), expected);
+
+    let possessive_paths = pat(Reluctance::Possessive).paths(&env);
+    assert!(possessive_paths.is_empty());
 }
 
 #[test]
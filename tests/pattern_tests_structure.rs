@@ -1,9 +1,11 @@
 mod common;
 
 use bc_envelope::prelude::*;
-use bc_envelope_pattern::{Matcher, Pattern, format_paths};
+use bc_envelope_pattern::{DCBORPattern, Matcher, Pattern, format_paths};
 use indoc::indoc;
 
+use crate::common::test_data::{credential, redacted_credential};
+
 #[test]
 fn test_subject_pattern() {
     let envelope = Envelope::new("Alice");
@@ -96,6 +98,128 @@ fn test_wrapped_pattern() {
     assert_actual_expected!(format_paths(&paths), expected);
 }
 
+#[test]
+fn test_wrapped_content_matching_pattern() {
+    let wrapped_text = Envelope::new("Alice").wrap();
+    let wrapped_number = Envelope::new(42).wrap();
+
+    let pattern = Pattern::wrapped_content_matching(Pattern::any_text());
+    assert!(pattern.matches(&wrapped_text));
+    assert!(!pattern.matches(&wrapped_number));
+    // Does not match non-wrapped subjects.
+    assert!(!pattern.matches(&Envelope::new("Alice")));
+
+    // The path ends at the wrapped envelope, not its content.
+    let paths = pattern.paths(&wrapped_text);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        2bc17c65 WRAPPED { "Alice" }
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+
+    // Compare with `unwrap_matching`, which descends into the content.
+    let paths =
+        Pattern::unwrap_matching(Pattern::any_text()).paths(&wrapped_text);
+    assert_eq!(paths[0].len(), 2);
+
+    // Traversal continues from the wrapped envelope itself.
+    let pattern = Pattern::traverse(vec![
+        Pattern::wrapped_content_matching(Pattern::any_text()),
+        Pattern::unwrap(),
+    ]);
+    let paths = pattern.paths(&wrapped_text);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        2bc17c65 WRAPPED { "Alice" }
+            13941b48 LEAF "Alice"
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+}
+
+#[test]
+fn test_tag_with_envelope_content() {
+    let envelope = Envelope::new(CBOR::to_tagged_value(100, "Alice"));
+    let pattern =
+        Pattern::tag_with_envelope_content(100, Pattern::text("Alice"));
+    assert!(pattern.matches(&envelope));
+    assert!(
+        !Pattern::tag_with_envelope_content(101, Pattern::text("Alice"))
+            .matches(&envelope)
+    );
+    assert!(
+        !Pattern::tag_with_envelope_content(100, Pattern::text("Bob"))
+            .matches(&envelope)
+    );
+    assert!(!pattern.matches(&Envelope::new("Alice")));
+    assert_actual_expected!(pattern.to_string(), r#"untag(100, "Alice")"#);
+
+    // Content that encodes an envelope is matched as that envelope.
+    let alice = Envelope::new("Alice").add_assertion("knows", "Bob");
+    let envelope = Envelope::new(CBOR::to_tagged_value(300, alice.to_cbor()))
+        .add_assertion("note", "wrapped in a tag");
+    let knows = Pattern::traverse(vec![
+        Pattern::assertion_with_predicate(Pattern::text("knows")),
+        Pattern::any_object(),
+        Pattern::text("Bob"),
+    ]);
+    let pattern = Pattern::tag_with_envelope_content(300, knows);
+    let paths = pattern.paths(&envelope);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        78d666eb ASSERTION "knows": "Bob"
+            13b74194 LEAF "Bob"
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+    assert!(
+        !Pattern::tag_with_envelope_content(300, Pattern::text("Bob"))
+            .matches(&envelope)
+    );
+
+    // Structured content can be matched with envelope-level leaf patterns.
+    let mut map = Map::new();
+    map.insert("age", 30);
+    let envelope = Envelope::new(CBOR::to_tagged_value(100, map));
+    assert!(
+        Pattern::tag_with_envelope_content(
+            100,
+            Pattern::map_with_required_key(DCBORPattern::text("age"))
+        )
+        .matches(&envelope)
+    );
+}
+
+#[test]
+fn test_tag_with_envelope_content_captures() {
+    let envelope = Envelope::new(CBOR::to_tagged_value(100, "Alice"));
+    let pattern = Pattern::tag_with_envelope_content(
+        100,
+        Pattern::capture("name", Pattern::any_text()),
+    );
+    let (paths, captures) = pattern.paths_with_captures(&envelope);
+    assert_eq!(paths, vec![vec![envelope.clone(), Envelope::new("Alice")]]);
+    assert_eq!(captures["name"], vec![vec![Envelope::new("Alice")]]);
+
+    // No captures are reported when the tag does not match.
+    let pattern = Pattern::tag_with_envelope_content(
+        101,
+        Pattern::capture("name", Pattern::any_text()),
+    );
+    let (paths, captures) = pattern.paths_with_captures(&envelope);
+    assert!(paths.is_empty());
+    assert!(!captures.contains_key("name"));
+
+    // Display output parses back to the same pattern.
+    let pattern = Pattern::tag_with_envelope_content(
+        100,
+        Pattern::capture("name", Pattern::any_text()),
+    );
+    assert_eq!(pattern.to_string(), "untag(100, @name(text))");
+    assert_eq!(Pattern::parse(pattern.to_string()).unwrap(), pattern);
+}
+
 #[test]
 fn test_assertion_pattern() {
     let envelope_without_assertions = Envelope::new("Alice");
@@ -153,6 +277,183 @@ fn test_assertion_predicate_pattern() {
     assert_actual_expected!(format_paths(&paths), expected);
 }
 
+#[test]
+fn test_predicate_or_subject_pattern() {
+    let pattern = Pattern::predicate_or_subject(Pattern::text("Alice"));
+    assert_eq!(pattern.to_string(), r#"pred_or_subj("Alice")"#);
+
+    // Matches the subject of a node.
+    let node = Envelope::new("Alice").add_assertion("knows", "Bob");
+    let paths = pattern.paths(&node);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        8955db5e NODE "Alice" [ "knows": "Bob" ]
+            13941b48 LEAF "Alice"
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+
+    // Matches the predicate of an assertion.
+    let assertion = Envelope::new_assertion("Alice", "Bob");
+    let paths = pattern.paths(&assertion);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        13941b48 LEAF "Alice"
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+
+    // A leaf is its own subject.
+    assert!(pattern.matches(&Envelope::new("Alice")));
+    assert!(!pattern.matches(&Envelope::new("Bob")));
+
+    // Objects are not matched.
+    assert!(!pattern.matches(&Envelope::new_assertion("knows", "Alice")));
+
+    // Searching finds both positions.
+    let envelope = Envelope::new("Alice")
+        .add_assertion("knows", "Bob")
+        .add_assertion("Alice", "alias");
+    let paths = Pattern::search(pattern).paths(&envelope);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        ae6b8672 NODE "Alice" [ "Alice": "alias", "knows": "Bob" ]
+            13941b48 LEAF "Alice"
+        ae6b8672 NODE "Alice" [ "Alice": "alias", "knows": "Bob" ]
+            70300bf2 ASSERTION "Alice": "alias"
+                13941b48 LEAF "Alice"
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+}
+
+#[test]
+fn test_nth_assertion_pattern() {
+    let envelope = Envelope::new("Alice")
+        .add_assertion("knows", "Bob")
+        .add_assertion("age", 30)
+        .add_assertion("likes", "tea");
+
+    // Each index matches one assertion, in canonical (digest) order.
+    let nth = |n| Pattern::nth_assertion(n, Pattern::any()).paths(&envelope);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        0eb5609b ASSERTION "age": 30
+    "#}.trim();
+    assert_actual_expected!(format_paths(&nth(0)), expected);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        55f4740c ASSERTION "likes": "tea"
+    "#}.trim();
+    assert_actual_expected!(format_paths(&nth(1)), expected);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        78d666eb ASSERTION "knows": "Bob"
+    "#}.trim();
+    assert_actual_expected!(format_paths(&nth(2)), expected);
+    assert!(nth(3).is_empty());
+
+    // The inner pattern is matched against the assertion at the index.
+    let age = Pattern::predicate(Pattern::text("age"));
+    let matching: Vec<usize> = (0..3)
+        .filter(|&n| Pattern::nth_assertion(n, age.clone()).matches(&envelope))
+        .collect();
+    assert_eq!(matching, vec![0]);
+    assert_eq!(
+        Pattern::nth_assertion(0, age).to_string(),
+        r#"assert[0](pred("age"))"#
+    );
+
+    // A leaf has no assertions.
+    assert!(
+        !Pattern::nth_assertion(0, Pattern::any())
+            .matches(&Envelope::new("Alice"))
+    );
+}
+
+#[test]
+fn test_subject_of_node_and_object_of_assertion() {
+    let envelope = Envelope::new("Alice")
+        .add_assertion("knows", "Bob")
+        .add_assertion("age", 30);
+
+    let pattern = Pattern::subject_of_node_matching(Pattern::any_node());
+    assert_eq!(
+        pattern,
+        Pattern::traverse(vec![Pattern::any_node(), Pattern::any_subject()])
+    );
+    assert_eq!(pattern.to_string(), "node -> subj");
+    let paths = pattern.paths(&envelope);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        a47bb3d4 NODE "Alice" [ "age": 30, "knows": "Bob" ]
+            13941b48 LEAF "Alice"
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+
+    let pattern =
+        Pattern::object_of_assertion_with_predicate(Pattern::text("knows"));
+    assert_eq!(
+        pattern,
+        Pattern::traverse(vec![
+            Pattern::assertion_with_predicate(Pattern::text("knows")),
+            Pattern::any_object(),
+        ])
+    );
+    let paths = pattern.paths(&envelope);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        78d666eb ASSERTION "knows": "Bob"
+            13b74194 LEAF "Bob"
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+
+    // The traverse_to_* shorthands build the same patterns.
+    assert_eq!(
+        Pattern::traverse_to_object_of(Pattern::text("knows")),
+        pattern
+    );
+    assert_eq!(
+        Pattern::traverse_to_object_of(Pattern::text("knows")).paths(&envelope),
+        paths
+    );
+    assert_eq!(
+        Pattern::traverse_to_subject(),
+        Pattern::subject_of_node_matching(Pattern::any_node())
+    );
+    assert!(!Pattern::traverse_to_subject().matches(&Envelope::new("Alice")));
+}
+
+#[test]
+fn test_leaf_with_value_pattern() {
+    let leaf = Envelope::new("Alice");
+    let node = Envelope::new("Alice").add_assertion("knows", "Bob");
+
+    let pattern = Pattern::leaf_with_value("Alice");
+    assert_eq!(
+        pattern,
+        Pattern::and(vec![Pattern::leaf(), Pattern::cbor("Alice")])
+    );
+    assert!(pattern.matches(&leaf));
+    assert!(!pattern.matches(&Envelope::new("Bob")));
+    // `cbor` alone also matches a node whose subject is the value.
+    assert!(Pattern::cbor("Alice").matches(&node));
+    assert!(!pattern.matches(&node));
+
+    let paths = pattern.paths(&leaf);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        13941b48 LEAF "Alice"
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+}
+
 #[test]
 fn test_digest_pattern() {
     let envelope = Envelope::new("Hello, World!");
@@ -195,6 +496,83 @@ fn test_digest_pattern() {
     assert!(paths.is_empty());
 }
 
+#[test]
+fn test_deep_equal_pattern() {
+    // Leaves and known values.
+    let alice = Envelope::new("Alice");
+    assert_eq!(
+        Pattern::deep_equal(&alice).paths(&alice),
+        vec![vec![alice.clone()]]
+    );
+    assert!(!Pattern::deep_equal(&alice).matches(&Envelope::new("Bob")));
+    let is_a = Envelope::new(known_values::IS_A);
+    assert!(Pattern::deep_equal(&is_a).matches(&is_a));
+    assert!(!Pattern::deep_equal(&is_a).matches(&alice));
+
+    // A node matches only an envelope with the same elements revealed.
+    let node = Envelope::new("Alice")
+        .add_assertion("knows", "Bob")
+        .add_assertion("age", 30);
+    let pattern = Pattern::deep_equal(&node);
+    assert_eq!(pattern.paths(&node), vec![vec![node.clone()]]);
+    let elided_subject = node.elide_revealing_target(&node.subject());
+    assert!(Pattern::digest(node.digest()).matches(&elided_subject));
+    assert!(!pattern.matches(&elided_subject));
+    assert!(!pattern.matches(&node.add_assertion("likes", "Carol")));
+    assert!(!pattern.matches(&alice));
+
+    // An elided element matches only the same elided element.
+    let knows = Envelope::new_assertion("knows", "Bob");
+    let partly_elided = node.elide_removing_target(&knows);
+    let pattern = Pattern::deep_equal(&partly_elided);
+    assert!(pattern.matches(&partly_elided));
+    assert!(!pattern.matches(&node));
+
+    // Assertions and wrapped envelopes.
+    assert!(Pattern::deep_equal(&knows).matches(&knows));
+    assert!(
+        !Pattern::deep_equal(&knows)
+            .matches(&Envelope::new_assertion("knows", "Carol"))
+    );
+    let wrapped = node.wrap();
+    assert!(Pattern::deep_equal(&wrapped).matches(&wrapped));
+    assert!(!Pattern::deep_equal(&wrapped).matches(&node));
+    assert!(
+        !Pattern::deep_equal(&wrapped)
+            .matches(&wrapped.elide_removing_target(&node))
+    );
+
+    // A signed credential and its redacted form share a digest but not
+    // their structure.
+    let credential = credential();
+    let pattern = Pattern::deep_equal(&credential);
+    assert!(pattern.matches(&credential));
+    assert!(!pattern.matches(&redacted_credential()));
+}
+
+#[test]
+fn test_has_same_digest_as() {
+    let alice = Envelope::new("Alice").add_assertion("knows", "Bob");
+    let pattern = Pattern::has_same_digest_as(&alice);
+    assert_eq!(pattern, Pattern::digest(alice.digest()));
+
+    // Matches the original and an identical copy built separately.
+    assert!(pattern.matches(&alice));
+    assert!(
+        pattern.matches(&Envelope::new("Alice").add_assertion("knows", "Bob"))
+    );
+
+    // Does not match envelopes with similar content.
+    assert!(!pattern.matches(&Envelope::new("Alice")));
+    assert!(
+        !pattern.matches(&Envelope::new("Alice").add_assertion("knows", "Bo"))
+    );
+    assert!(!pattern.matches(&alice.add_assertion("age", 30)));
+
+    // An elided envelope keeps its digest.
+    assert!(pattern.matches(&alice.elide()));
+}
+
 #[test]
 fn test_digest_pattern_binary_regex() {
     let envelope = Envelope::new("Hello, World!");
@@ -397,6 +775,216 @@ fn test_node_pattern() {
     assert_actual_expected!(format_paths(&paths), expected);
 }
 
+#[test]
+fn test_node_matching_all_assertions_pattern() {
+    let knows = Pattern::node_matching_all_assertions(Pattern::predicate(
+        Pattern::text("knows"),
+    ));
+
+    // A leaf has no assertions but is not a node, so it never matches.
+    let leaf_envelope = Envelope::new("Alice");
+    assert!(!knows.matches(&leaf_envelope));
+
+    // Every assertion matches.
+    let all_knows = Envelope::new("Alice")
+        .add_assertion("knows", "Bob")
+        .add_assertion("knows", "Carol");
+    assert!(knows.matches(&all_knows));
+    let paths = knows.paths(&all_knows);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        b8d857f6 NODE "Alice" [ "knows": "Bob", "knows": "Carol" ]
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+
+    // One assertion out of several fails to match.
+    let mixed = all_knows.add_assertion("age", 30);
+    assert!(!knows.matches(&mixed));
+
+    // Patterns that accept any assertion match every node.
+    let any_pred =
+        Pattern::node_matching_all_assertions(Pattern::any_predicate());
+    assert!(any_pred.matches(&mixed));
+    assert!(!any_pred.matches(&leaf_envelope));
+}
+
+#[test]
+fn test_subject_and_assertion_pattern() {
+    let alice = Envelope::new("Alice")
+        .add_assertion("knows", "Bob")
+        .add_assertion("knows", "Carol")
+        .add_assertion("age", 30);
+    let bob = Envelope::new("Bob").add_assertion("knows", "Alice");
+
+    let pattern = Pattern::subject_and_assertion(
+        Pattern::text("Alice"),
+        Pattern::assertion_with_predicate(Pattern::text("knows")),
+    );
+    assert_eq!(
+        pattern.to_string(),
+        r#"subj("Alice") & assertpred("knows")"#
+    );
+    assert_eq!(Pattern::parse(pattern.to_string()).unwrap(), pattern);
+
+    // Every matching assertion is returned; the others are not.
+    let paths = pattern.paths(&alice);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        4012caf2 ASSERTION "knows": "Carol"
+        78d666eb ASSERTION "knows": "Bob"
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+
+    // The subject constrains the match.
+    assert!(!pattern.matches(&bob));
+
+    // So does the assertion.
+    let pattern = Pattern::subject_and_assertion(
+        Pattern::text("Alice"),
+        Pattern::assertion_with_predicate(Pattern::text("email")),
+    );
+    assert!(!pattern.matches(&alice));
+}
+
+#[test]
+fn test_all_assertions_have_predicate_in_pattern() {
+    let pattern = Pattern::all_assertions_have_predicate_in(vec![
+        Pattern::text("name"),
+        Pattern::text("age"),
+    ]);
+
+    // Every predicate is in the set, though not every set member is used.
+    let compliant = Envelope::new("Alice").add_assertion("age", 30);
+    assert!(pattern.matches(&compliant));
+    let paths = pattern.paths(&compliant);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let expected = indoc! {r#"
+        96f0d32d NODE "Alice" [ "age": 30 ]
+    "#}.trim();
+    assert_actual_expected!(format_paths(&paths), expected);
+
+    // A rogue assertion causes the whole node to fail.
+    let rogue = compliant.add_assertion("admin", true);
+    assert!(!pattern.matches(&rogue));
+
+    // Leaves are not nodes.
+    assert!(!pattern.matches(&Envelope::new("Alice")));
+
+    // Predicates can be matched by any pattern, not just exact values.
+    let pattern =
+        Pattern::all_assertions_have_predicate_in(vec![Pattern::any_text()]);
+    assert!(pattern.matches(&rogue));
+    assert!(!pattern.matches(&rogue.add_assertion(1, "one")));
+}
+
+#[test]
+fn test_node_assertion_predicate_count_pattern() {
+    let pattern =
+        Pattern::node_assertion_predicate_count(Pattern::any_text(), 2, None);
+
+    let one = Envelope::new("Alice").add_assertion("knows", "Bob");
+    assert!(!pattern.matches(&one));
+
+    // Assertions with non-text predicates are not counted.
+    let mixed = one.add_assertion(known_values::IS_A, "Person");
+    assert!(!pattern.matches(&mixed));
+
+    let two = mixed.add_assertion("age", 30);
+    assert!(pattern.matches(&two));
+    let paths = pattern.paths(&two);
+    assert_eq!(paths, vec![vec![two.clone()]]);
+
+    // The maximum is inclusive.
+    let bounded = Pattern::node_assertion_predicate_count(
+        Pattern::any_text(),
+        1,
+        Some(2),
+    );
+    assert!(bounded.matches(&one));
+    assert!(bounded.matches(&two));
+    assert!(!bounded.matches(&two.add_assertion("email", "alice@example.com")));
+
+    // A count of zero matches a node with no matching predicates, but a
+    // leaf is not a node.
+    let none = Pattern::node_assertion_predicate_count(
+        Pattern::any_number(),
+        0,
+        Some(0),
+    );
+    assert!(none.matches(&two));
+    assert!(!none.matches(&Envelope::new("Alice")));
+}
+
+#[test]
+fn test_node_with_exact_assertions_pattern() {
+    // Like `node_matching_all_assertions`, each pattern is matched against
+    // an assertion envelope.
+    let age = Pattern::predicate(Pattern::text("age"));
+    let knows = Pattern::predicate(Pattern::text("knows"));
+    let pattern = Pattern::node_with_exact_assertions(vec![
+        knows.clone(),
+        age.clone(),
+    ]);
+
+    let alice = Envelope::new("Alice")
+        .add_assertion("knows", "Bob")
+        .add_assertion("age", 30);
+    assert!(pattern.matches(&alice));
+    assert_eq!(pattern.paths(&alice), vec![vec![alice.clone()]]);
+
+    // The order of the patterns doesn't matter.
+    let reversed =
+        Pattern::node_with_exact_assertions(vec![age.clone(), knows.clone()]);
+    assert!(reversed.matches(&alice));
+
+    // No more and no fewer assertions than patterns.
+    assert!(!pattern.matches(&alice.add_assertion("email", "a@example.com")));
+    assert!(!pattern.matches(&Envelope::new("Alice").add_assertion("age", 30)));
+    assert!(!pattern.matches(&Envelope::new("Alice")));
+
+    // Each assertion pairs with a different pattern, even when one pattern
+    // matches both assertions.
+    for patterns in [
+        vec![Pattern::any(), age.clone()],
+        vec![age.clone(), Pattern::any()],
+    ] {
+        assert!(Pattern::node_with_exact_assertions(patterns).matches(&alice));
+    }
+    let twice = Pattern::node_with_exact_assertions(vec![age.clone(), age]);
+    assert!(!twice.matches(&alice));
+}
+
+#[test]
+fn test_count_matching_assertions() {
+    let knows = Pattern::predicate(Pattern::text("knows"));
+
+    // A leaf has no assertions.
+    let leaf = Envelope::new("Alice");
+    assert_eq!(Pattern::any().count_matching_assertions(&leaf, &knows), 0);
+
+    let one = leaf.add_assertion("knows", "Bob");
+    assert_eq!(
+        Pattern::any_node().count_matching_assertions(&one, &knows),
+        1
+    );
+
+    let five = one
+        .add_assertion("knows", "Carol")
+        .add_assertion("knows", "Dave")
+        .add_assertion("age", 30)
+        .add_assertion("city", "Boston");
+    let node = Pattern::any_node();
+    assert_eq!(node.count_matching_assertions(&five, &knows), 3);
+    assert_eq!(node.count_matching_assertions(&five, &Pattern::any()), 5);
+
+    // Nothing is counted when the pattern does not match.
+    let pattern = Pattern::node_with_assertions_range(6..);
+    assert_eq!(pattern.count_matching_assertions(&five, &knows), 0);
+}
+
 #[test]
 fn test_obscured_pattern() {
     let original_envelope = Envelope::new("Secret data");
@@ -449,3 +1037,112 @@ fn test_obscured_pattern() {
         assert!(!Pattern::encrypted().matches(&compressed_envelope));
     }
 }
+
+#[test]
+fn test_any_structure_and_any_obscured() {
+    use bc_components::SymmetricKey;
+
+    let leaf = Envelope::new("Alice");
+    let node = leaf.clone().add_assertion("knows", "Bob");
+    let wrapped = node.wrap();
+    let obscured = [
+        leaf.elide(),
+        leaf.encrypt_subject(&SymmetricKey::new()).unwrap(),
+        leaf.compress().unwrap(),
+    ];
+
+    for envelope in [&leaf, &node, &wrapped] {
+        assert!(Pattern::any_structure().matches(envelope));
+        assert!(!Pattern::any_obscured().matches(envelope));
+    }
+    // A node matches both as itself and through its assertion.
+    assert_eq!(Pattern::any_structure().paths(&node).len(), 2);
+    for envelope in &obscured {
+        assert!(!Pattern::any_structure().matches(envelope));
+        assert!(Pattern::any_obscured().matches(envelope));
+    }
+
+    assert_eq!(Pattern::any_obscured(), Pattern::obscured());
+    assert_eq!(Pattern::any_obscured().to_string(), "obscured");
+}
+
+#[test]
+fn test_matches_case_pattern() {
+    use bc_components::SymmetricKey;
+
+    let leaf = Envelope::new("Alice");
+    let node = leaf.clone().add_assertion("knows", "Bob");
+    let cases = [
+        ("node", node.clone()),
+        ("leaf", leaf.clone()),
+        ("wrapped", node.wrap()),
+        ("elided", leaf.elide()),
+        (
+            "encrypted",
+            leaf.encrypt_subject(&SymmetricKey::new()).unwrap(),
+        ),
+        ("compressed", leaf.compress().unwrap()),
+    ];
+    for (name, envelope) in &cases {
+        let pattern = Pattern::matches_case(name).unwrap();
+        for (other, other_envelope) in &cases {
+            assert_eq!(
+                pattern.matches(other_envelope),
+                name == other,
+                "case({name:?}) against a {other} envelope"
+            );
+        }
+        assert!(pattern.matches(envelope));
+    }
+
+    // As with `assert`, the assertion case yields the node's assertions.
+    let assertion = Pattern::matches_case("assertion").unwrap();
+    assert_eq!(assertion, Pattern::any_assertion());
+    assert_eq!(
+        assertion.paths(&node),
+        vec![vec![node.assertions()[0].clone()]]
+    );
+    assert!(!assertion.matches(&leaf));
+
+    assert!(Pattern::matches_case("Leaf").is_err());
+    assert!(Pattern::matches_case("").is_err());
+}
+
+#[test]
+fn test_has_no_obscured_patterns() {
+    let envelope = Envelope::new("Alice")
+        .add_assertion("knows", "Bob")
+        .add_assertion("age", 30);
+
+    // A fully transparent envelope passes every check.
+    assert_eq!(Pattern::has_no_elided().to_string(), "!search(elided)");
+    assert!(Pattern::has_no_elided().matches(&envelope));
+    assert!(Pattern::has_no_encrypted().matches(&envelope));
+    assert!(Pattern::has_no_obscured().matches(&envelope));
+    assert_eq!(
+        Pattern::has_no_elided().paths(&envelope),
+        vec![vec![envelope.clone()]]
+    );
+
+    // An elided assertion deep in the tree is found.
+    let target = envelope.assertion_with_predicate("age").unwrap();
+    let elided = envelope.elide_removing_target(&target);
+    assert!(!Pattern::has_no_elided().matches(&elided));
+    assert!(Pattern::has_no_encrypted().matches(&elided));
+    assert!(!Pattern::has_no_obscured().matches(&elided));
+
+    {
+        use bc_components::SymmetricKey;
+
+        let key = SymmetricKey::new();
+        // Only the wrapped subject is encrypted.
+        let encrypted = envelope
+            .wrap()
+            .encrypt_subject(&key)
+            .unwrap()
+            .add_assertion("note", "secret inside");
+        assert!(Pattern::has_no_elided().matches(&encrypted));
+        assert!(!Pattern::has_no_encrypted().matches(&encrypted));
+        assert!(!Pattern::has_no_obscured().matches(&encrypted));
+    }
+}
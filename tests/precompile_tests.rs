@@ -0,0 +1,69 @@
+#![cfg(feature = "precompile")]
+
+use bc_envelope::prelude::*;
+use bc_envelope_pattern::{Matcher, Pattern};
+
+fn envelope() -> Envelope {
+    Envelope::new("Alice")
+        .add_assertion("knows", "Bob")
+        .add_assertion("knows", "Carol")
+        .add_assertion("age", 30)
+}
+
+#[test]
+fn test_with_precompiled() {
+    let pattern = Pattern::parse(r#"search(assertpred("knows") -> obj)"#)
+        .unwrap()
+        .with_precompiled();
+    let expected = pattern.paths(&envelope());
+    assert_eq!(expected.len(), 2);
+
+    // Clones on other threads use the shared program and match the same.
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let pattern = pattern.clone();
+            std::thread::spawn(move || pattern.paths(&envelope()).len())
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), expected.len());
+    }
+
+    // Precompiling again is harmless.
+    let pattern = pattern.with_precompiled();
+    assert_eq!(pattern.paths(&envelope()), expected);
+}
+
+#[test]
+#[ignore]
+fn bench_first_use_latency() {
+    // Run with `cargo test --release --features precompile -- --ignored
+    // --nocapture`.
+    let pattern = Pattern::parse(
+        r#"search(assertpred("knows") -> obj | assertobj(>=18) -> pred)"#,
+    )
+    .unwrap();
+
+    let first_use = |pattern: &Pattern| {
+        let mut total = std::time::Duration::ZERO;
+        for _ in 0..100 {
+            // Each thread starts with an empty per-thread cache.
+            let pattern = pattern.clone();
+            total += std::thread::spawn(move || {
+                let start = std::time::Instant::now();
+                assert!(pattern.matches(&envelope()));
+                start.elapsed()
+            })
+            .join()
+            .unwrap();
+        }
+        total / 100
+    };
+
+    println!("plain: first use took {:?} on average", first_use(&pattern));
+    let pattern = pattern.with_precompiled();
+    println!(
+        "precompiled: first use took {:?} on average",
+        first_use(&pattern)
+    );
+}